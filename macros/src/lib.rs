@@ -2,13 +2,25 @@
 
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, ItemFn};
+use syn::punctuated::Punctuated;
+use syn::{parse_macro_input, FnArg, ItemFn, LitStr, Path, Token};
+
+/// Environment variable holding the build-time test name filter.
+const FILTER_ENV: &str = "ESP32_TEST_FILTER";
+
+/// Environment variable listing tags whose tests are skipped (comma-separated).
+const SKIP_TAGS_ENV: &str = "ESP32_TEST_SKIP_TAGS";
+
+/// Environment variable listing tags to run exclusively (comma-separated).
+const ONLY_TAGS_ENV: &str = "ESP32_TEST_ONLY_TAGS";
 
 /// Mark a function as a test that works on both host and ESP32.
 ///
 /// This macro:
 /// 1. Adds `#[test]` so the compiler collects it
 /// 2. Calls a shared ESP-IDF initializer on ESP32 targets
+/// 3. Optionally calls a setup function and passes its result to the test
+/// 4. Adds `#[doc(hidden)]` so `pub` tests stay out of rustdoc output
 ///
 /// # Example
 ///
@@ -26,17 +38,119 @@ use syn::{parse_macro_input, ItemFn};
 ///     panic!("error message");
 /// }
 /// ```
+///
+/// # Fixtures
+///
+/// `#[esp32_test(setup = "setup_fn")]` calls `setup_fn()` before the test body
+/// and binds the result to the test's single parameter. The setup function must
+/// be a synchronous `fn() -> T` in scope where the test is defined. Its return
+/// type is checked against the parameter type by the compiler.
+///
+/// ```ignore
+/// fn setup_fragmenter() -> Fragmenter {
+///     Fragmenter::new(20)
+/// }
+///
+/// #[esp32_test(setup = "setup_fragmenter")]
+/// fn my_fixture_test(mut fragmenter: Fragmenter) {
+///     assert!(fragmenter.fragment(&[1, 2, 3]).is_ok());
+/// }
+/// ```
+///
+/// # Filtering
+///
+/// The ESP32 test binary can't receive libtest's command-line filter, so the
+/// filter is applied at build time instead: when `ESP32_TEST_FILTER` is set
+/// while compiling, tests whose function name doesn't contain it are marked
+/// `#[ignore]`. The test runner sets it from `--filter <pattern>`.
+///
+/// # Tags
+///
+/// `#[esp32_test(tags = ["slow", "network"])]` categorizes a test, e.g. to
+/// keep slow integration tests out of every-build runs. Tags are filtered
+/// at build time like names: `ESP32_TEST_SKIP_TAGS` ignores tests with any
+/// listed tag, and `ESP32_TEST_ONLY_TAGS` ignores tests with none of the
+/// listed tags. Both take comma-separated lists and are set by the test
+/// runner from `--skip-tags` and `--only-tags`.
 #[proc_macro_attribute]
-pub fn esp32_test(_attr: TokenStream, item: TokenStream) -> TokenStream {
+pub fn esp32_test(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut setup: Option<Path> = None;
+    let mut tags: Vec<String> = Vec::new();
+    let attr_parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("setup") {
+            let name: LitStr = meta.value()?.parse()?;
+            setup = Some(name.parse()?);
+            Ok(())
+        } else if meta.path.is_ident("tags") {
+            let value = meta.value()?;
+            let content;
+            syn::bracketed!(content in value);
+            let list = Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?;
+            tags.extend(list.iter().map(LitStr::value));
+            Ok(())
+        } else {
+            Err(meta.error("unsupported esp32_test argument (expected `setup` or `tags`)"))
+        }
+    });
+    parse_macro_input!(attr with attr_parser);
+
     let input_fn = parse_macro_input!(item as ItemFn);
 
     let fn_block = &input_fn.block;
     let fn_vis = &input_fn.vis;
     let fn_attrs = &input_fn.attrs; // Preserves #[should_panic] etc.
-    let fn_sig = &input_fn.sig;
+    let mut fn_sig = input_fn.sig.clone();
+
+    // With a setup function, the test's parameter becomes a local binding
+    let fixture = match setup {
+        Some(setup_fn) => {
+            let arg = match (fn_sig.inputs.len(), fn_sig.inputs.first()) {
+                (1, Some(FnArg::Typed(arg))) => arg.clone(),
+                _ => {
+                    return syn::Error::new_spanned(
+                        &fn_sig.inputs,
+                        "a test with `setup` must take exactly one parameter for the fixture",
+                    )
+                    .to_compile_error()
+                    .into();
+                }
+            };
+            fn_sig.inputs.clear();
+            let pat = &arg.pat;
+            let ty = &arg.ty;
+            quote! { let #pat: #ty = #setup_fn(); }
+        }
+        None => quote! {},
+    };
+
+    // Build-time filters (see "Filtering" and "Tags" above). Already-ignored
+    // tests stay as is.
+    let already_ignored = fn_attrs.iter().any(|a| a.path().is_ident("ignore"));
+    let name_filtered = std::env::var(FILTER_ENV)
+        .ok()
+        .filter(|pattern| !pattern.is_empty())
+        .is_some_and(|pattern| !fn_sig.ident.to_string().contains(&pattern));
+    let has_tag_in =
+        |var: &str| env_list(var).map(|list| tags.iter().any(|tag| list.contains(tag)));
+    let filter_reason = if name_filtered {
+        Some("filtered (ESP32_TEST_FILTER)")
+    } else if has_tag_in(SKIP_TAGS_ENV) == Some(true) {
+        Some("filtered (ESP32_TEST_SKIP_TAGS)")
+    } else if has_tag_in(ONLY_TAGS_ENV) == Some(false) {
+        Some("filtered (ESP32_TEST_ONLY_TAGS)")
+    } else {
+        None
+    };
+    let filter_attr = match filter_reason {
+        Some(reason) if !already_ignored => quote! { #[ignore = #reason] },
+        _ => quote! {},
+    };
 
+    // Tests are never public API, even when declared `pub`
     let expanded = quote! {
         #[test]
+        #[doc(hidden)]
+        #filter_attr
         #(#fn_attrs)*
         #fn_vis #fn_sig {
             // Initialize ESP-IDF once (shared across all tests)
@@ -45,9 +159,31 @@ pub fn esp32_test(_attr: TokenStream, item: TokenStream) -> TokenStream {
                 crate::ensure_esp_initialized();
             }
 
+            // Makes rustc track the variables, so changing a filter rebuilds
+            // the tests and re-runs this macro
+            const _: Option<&str> = option_env!("ESP32_TEST_FILTER");
+            const _: Option<&str> = option_env!("ESP32_TEST_SKIP_TAGS");
+            const _: Option<&str> = option_env!("ESP32_TEST_ONLY_TAGS");
+
+            #fixture
+
             #fn_block
         }
     };
 
     TokenStream::from(expanded)
 }
+
+/// Read a comma-separated list from a build-time environment variable.
+///
+/// Returns `None` if the variable is unset or lists nothing.
+fn env_list(var: &str) -> Option<Vec<String>> {
+    let list: Vec<String> = std::env::var(var)
+        .ok()?
+        .split(',')
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .map(String::from)
+        .collect();
+    (!list.is_empty()).then_some(list)
+}
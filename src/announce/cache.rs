@@ -9,12 +9,16 @@
 //! This implementation uses an LRU (Least Recently Used) eviction policy when
 //! the cache reaches capacity, ensuring bounded memory usage.
 
+use log::warn;
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
 /// Hash identifying an announce (typically 16 bytes in Reticulum).
 pub type AnnounceHash = [u8; 16];
 
+/// Fill level (percent) at which the cache warns that LRU eviction is near.
+pub const NEAR_CAPACITY_PCT: f32 = 80.0;
+
 /// Configuration for the announce cache.
 ///
 /// Note: This is `Copy` for efficient passing to constructors.
@@ -175,15 +179,32 @@ impl AnnounceCache {
     /// Returns whether this is a new announce, duplicate, or better path.
     /// This helps determine whether to rebroadcast the announce.
     pub fn insert(&mut self, hash: AnnounceHash, hops: u8) -> InsertResult {
-        let now = Instant::now();
+        self.insert_at(hash, hops, Instant::now())
+    }
 
+    /// Insert an announce as if it had been seen at `first_seen`.
+    ///
+    /// Used to replay historical announce logs, so expiry can be tested
+    /// without sleeping. Capacity eviction still uses the current time.
+    #[cfg(any(test, feature = "testing"))]
+    pub fn insert_with_timestamp(
+        &mut self,
+        hash: AnnounceHash,
+        hops: u8,
+        first_seen: Instant,
+    ) -> InsertResult {
+        self.insert_at(hash, hops, first_seen)
+    }
+
+    /// Insert an announce seen at `seen_at`.
+    fn insert_at(&mut self, hash: AnnounceHash, hops: u8, seen_at: Instant) -> InsertResult {
         // First, clean up expired entries if we're at capacity
         if self.entries.len() >= self.config.max_entries {
-            self.evict_expired_or_lru(now);
+            self.evict_expired_or_lru(Instant::now());
         }
 
         if let Some(entry) = self.entries.get_mut(&hash) {
-            entry.last_accessed = now;
+            entry.last_accessed = seen_at;
             entry.seen_count = entry.seen_count.saturating_add(1);
 
             if hops < entry.hops {
@@ -202,11 +223,44 @@ impl AnnounceCache {
                 self.evict_lru();
             }
 
-            self.entries.insert(hash, AnnounceEntry::new(hops, now));
+            let was_near_capacity = self.is_near_capacity(NEAR_CAPACITY_PCT);
+            self.entries.insert(hash, AnnounceEntry::new(hops, seen_at));
+            // Warn once per crossing, not on every insert while full
+            if !was_near_capacity && self.is_near_capacity(NEAR_CAPACITY_PCT) {
+                warn!(
+                    "Announce cache at {:.0}% capacity",
+                    self.capacity_used_pct()
+                );
+            }
             InsertResult::New
         }
     }
 
+    /// Export all non-expired entries for sharing with neighbors.
+    ///
+    /// Hop counts are incremented, since the receiver is one hop further
+    /// away. Used by gateways to seed neighbors' caches without waiting for
+    /// announces to propagate.
+    pub fn export(&self) -> Vec<(AnnounceHash, u8)> {
+        let now = Instant::now();
+        self.entries
+            .iter()
+            .filter(|(_, entry)| now.duration_since(entry.first_seen) < self.config.ttl)
+            .map(|(hash, entry)| (*hash, entry.hops.saturating_add(1)))
+            .collect()
+    }
+
+    /// Import entries exported by a neighbor.
+    ///
+    /// Each entry is inserted as if its announce had just been received, so
+    /// the results say which entries are new or improve a known path.
+    pub fn import(&mut self, entries: &[(AnnounceHash, u8)]) -> Vec<InsertResult> {
+        entries
+            .iter()
+            .map(|&(hash, hops)| self.insert(hash, hops))
+            .collect()
+    }
+
     /// Check if an announce is in the cache (without updating access time).
     pub fn contains(&self, hash: &AnnounceHash) -> bool {
         self.entries.contains_key(hash)
@@ -228,6 +282,20 @@ impl AnnounceCache {
         self.entries.get(hash)
     }
 
+    /// Get the stored hop count without updating access time.
+    pub fn peek_by_hop_count(&self, hash: &AnnounceHash) -> Option<u8> {
+        self.entries.get(hash).map(|entry| entry.hops)
+    }
+
+    /// Check whether an announce arriving with `incoming_hops` is worth forwarding.
+    ///
+    /// True for unknown announces and for announces with a shorter path than
+    /// the one already cached (and therefore already forwarded).
+    pub fn should_forward(&self, hash: &AnnounceHash, incoming_hops: u8) -> bool {
+        self.peek_by_hop_count(hash)
+            .is_none_or(|stored| incoming_hops < stored)
+    }
+
     /// Remove an announce from the cache.
     pub fn remove(&mut self, hash: &AnnounceHash) -> Option<AnnounceEntry> {
         self.entries.remove(hash)
@@ -248,6 +316,19 @@ impl AnnounceCache {
         self.entries.is_empty()
     }
 
+    /// Get the fraction of `max_entries` in use, in percent.
+    pub fn capacity_used_pct(&self) -> f32 {
+        self.entries.len() as f32 * 100.0 / self.config.max_entries as f32
+    }
+
+    /// Check if the cache is at least `threshold_pct` percent full.
+    ///
+    /// Useful for alerting before LRU eviction starts dropping entries that
+    /// would otherwise suppress rebroadcasts.
+    pub fn is_near_capacity(&self, threshold_pct: f32) -> bool {
+        self.capacity_used_pct() >= threshold_pct
+    }
+
     /// Get the cache configuration.
     pub fn config(&self) -> &AnnounceCacheConfig {
         &self.config
@@ -323,6 +404,33 @@ mod tests {
         assert_eq!(cache.len(), 0);
     }
 
+    #[esp32_test]
+    fn test_capacity_used_pct() {
+        let config = AnnounceCacheConfig {
+            max_entries: 5,
+            ..Default::default()
+        };
+        let mut cache = AnnounceCache::new(config).unwrap();
+        assert_eq!(cache.capacity_used_pct(), 0.0);
+        assert!(!cache.is_near_capacity(NEAR_CAPACITY_PCT));
+
+        for i in 0..3 {
+            cache.insert(make_hash(i), 1);
+        }
+        assert_eq!(cache.capacity_used_pct(), 60.0);
+        assert!(!cache.is_near_capacity(NEAR_CAPACITY_PCT));
+
+        cache.insert(make_hash(3), 1);
+        assert_eq!(cache.capacity_used_pct(), 80.0);
+        assert!(cache.is_near_capacity(NEAR_CAPACITY_PCT));
+
+        // Eviction keeps the cache at (not above) capacity
+        for i in 4..10 {
+            cache.insert(make_hash(i), 1);
+        }
+        assert_eq!(cache.capacity_used_pct(), 100.0);
+    }
+
     #[esp32_test]
     fn test_invalid_config_zero_entries() {
         let config = AnnounceCacheConfig {
@@ -437,6 +545,24 @@ mod tests {
         assert_eq!(first_access, second_access);
     }
 
+    #[esp32_test]
+    fn test_peek_by_hop_count_and_should_forward() {
+        let mut cache = AnnounceCache::new(AnnounceCacheConfig::default()).unwrap();
+        let hash = make_hash(1);
+
+        assert_eq!(cache.peek_by_hop_count(&hash), None);
+        assert!(cache.should_forward(&hash, 5));
+
+        let _ = cache.insert(hash, 3);
+        let accessed = cache.peek(&hash).unwrap().last_accessed;
+        assert_eq!(cache.peek_by_hop_count(&hash), Some(3));
+        assert_eq!(cache.peek(&hash).unwrap().last_accessed, accessed);
+
+        assert!(cache.should_forward(&hash, 2));
+        assert!(!cache.should_forward(&hash, 3));
+        assert!(!cache.should_forward(&hash, 4));
+    }
+
     #[esp32_test]
     fn test_remove() {
         let mut cache = AnnounceCache::new(AnnounceCacheConfig::default()).unwrap();
@@ -528,6 +654,67 @@ mod tests {
         assert_eq!(config.ttl, Duration::from_secs(3600));
     }
 
+    #[esp32_test]
+    fn test_insert_with_timestamp_expires_without_sleeping() {
+        let config = AnnounceCacheConfig {
+            max_entries: 10,
+            ttl: Duration::from_millis(50),
+        };
+        let mut cache = AnnounceCache::new(config).unwrap();
+        let old = Instant::now()
+            .checked_sub(Duration::from_millis(100))
+            .expect("clock running longer than 100ms");
+
+        assert_eq!(
+            cache.insert_with_timestamp(make_hash(1), 2, old),
+            InsertResult::New
+        );
+        assert_eq!(cache.insert(make_hash(2), 2), InsertResult::New);
+        assert_eq!(cache.peek(&make_hash(1)).unwrap().first_seen, old);
+
+        // Only the replayed (already expired) announce is removed
+        assert_eq!(cache.cleanup_expired(), 1);
+        assert!(!cache.contains(&make_hash(1)));
+        assert!(cache.contains(&make_hash(2)));
+    }
+
+    #[esp32_test]
+    fn test_export_import() {
+        let config = AnnounceCacheConfig {
+            max_entries: 10,
+            ttl: Duration::from_millis(50),
+        };
+        let mut gateway = AnnounceCache::new(config).unwrap();
+        let old = Instant::now()
+            .checked_sub(Duration::from_millis(100))
+            .expect("clock running longer than 100ms");
+        assert_eq!(gateway.insert(make_hash(1), 2), InsertResult::New);
+        assert_eq!(gateway.insert(make_hash(2), 255), InsertResult::New);
+        assert_eq!(
+            gateway.insert_with_timestamp(make_hash(3), 1, old),
+            InsertResult::New
+        );
+
+        // Expired entries are skipped and hop counts saturate
+        let mut exported = gateway.export();
+        exported.sort();
+        assert_eq!(exported, vec![(make_hash(1), 3), (make_hash(2), 255)]);
+
+        let mut neighbor = AnnounceCache::default();
+        assert_eq!(neighbor.insert(make_hash(1), 5), InsertResult::New);
+        assert_eq!(
+            neighbor.import(&exported),
+            vec![
+                InsertResult::BetterPath {
+                    old_hops: 5,
+                    new_hops: 3
+                },
+                InsertResult::New,
+            ]
+        );
+        assert_eq!(neighbor.len(), 2);
+    }
+
     #[esp32_test]
     fn test_error_display() {
         let err = AnnounceCacheError::InvalidConfig("test message");
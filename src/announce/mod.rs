@@ -2,10 +2,13 @@
 //!
 //! This module provides:
 //! - [`AnnounceCache`]: LRU cache for deduplicating announces
+//! - [`spawn_announce_timer`]: Periodic re-announcement of a destination
 
 mod cache;
+mod timer;
 
 pub use cache::{
     AnnounceCache, AnnounceCacheConfig, AnnounceCacheError, AnnounceEntry, AnnounceHash,
-    InsertResult,
+    InsertResult, NEAR_CAPACITY_PCT,
 };
+pub use timer::{spawn_announce_timer, AnnounceTimerError};
@@ -0,0 +1,137 @@
+//! Periodic re-announcement of a destination.
+//!
+//! [`spawn_announce_timer`] backs both `Node::broadcast_announce` and the node
+//! binary, so both stop announcing the same way: when the returned token or
+//! its parent is cancelled.
+
+use reticulum::destination::SingleInputDestination;
+use reticulum::transport::Transport;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
+
+/// Errors that can occur when starting an announce timer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnnounceTimerError {
+    /// The interval was zero.
+    ZeroInterval,
+}
+
+impl std::fmt::Display for AnnounceTimerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ZeroInterval => write!(f, "announce interval must be greater than 0"),
+        }
+    }
+}
+
+impl std::error::Error for AnnounceTimerError {}
+
+/// Announce `destination` every `interval` from a background task.
+///
+/// The first timed announce goes out after one `interval`; callers announce
+/// once themselves for an immediate one. `on_announce` runs after each timed
+/// announce, e.g. to log or count it. The task stops when the returned token,
+/// a child of `parent`, is cancelled.
+///
+/// Must be called from within a tokio runtime.
+pub fn spawn_announce_timer<F>(
+    transport: Arc<Mutex<Transport>>,
+    destination: Arc<Mutex<SingleInputDestination>>,
+    interval: Duration,
+    parent: &CancellationToken,
+    on_announce: F,
+) -> Result<CancellationToken, AnnounceTimerError>
+where
+    F: Fn() + Send + 'static,
+{
+    if interval.is_zero() {
+        return Err(AnnounceTimerError::ZeroInterval);
+    }
+
+    let cancel = parent.child_token();
+    let task_cancel = cancel.clone();
+
+    tokio::spawn(async move {
+        let mut timer = tokio::time::interval(interval);
+        timer.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        timer.tick().await; // Skip first
+
+        loop {
+            tokio::select! {
+                _ = task_cancel.cancelled() => break,
+                _ = timer.tick() => {
+                    let t = transport.lock().await;
+                    t.send_announce(&destination, None).await;
+                    drop(t);
+                    on_announce();
+                }
+            }
+        }
+    });
+
+    Ok(cancel)
+}
+
+#[cfg(all(test, not(feature = "esp32")))]
+mod tests {
+    use super::*;
+    use rand_core::OsRng;
+    use reticulum::destination::DestinationName;
+    use reticulum::identity::PrivateIdentity;
+    use reticulum::transport::TransportConfig;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_cancel_stops_announces() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            let identity = PrivateIdentity::new_from_rand(OsRng);
+            let config = TransportConfig::new("timer_test", &identity, false);
+            let transport = Arc::new(Mutex::new(Transport::new(config)));
+            let destination = transport
+                .lock()
+                .await
+                .add_destination(identity, DestinationName::new("timer_test", "announce"))
+                .await;
+            let parent = CancellationToken::new();
+
+            let zero = spawn_announce_timer(
+                transport.clone(),
+                destination.clone(),
+                Duration::ZERO,
+                &parent,
+                || {},
+            );
+            assert!(matches!(zero, Err(AnnounceTimerError::ZeroInterval)));
+
+            let count = Arc::new(AtomicUsize::new(0));
+            let counter = count.clone();
+            let cancel = spawn_announce_timer(
+                transport,
+                destination,
+                Duration::from_millis(10),
+                &parent,
+                move || {
+                    counter.fetch_add(1, Ordering::Relaxed);
+                },
+            )
+            .unwrap();
+
+            tokio::time::sleep(Duration::from_millis(55)).await;
+            assert!(count.load(Ordering::Relaxed) >= 2);
+
+            cancel.cancel();
+            // Let the task observe the cancellation
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            let stopped_at = count.load(Ordering::Relaxed);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            assert_eq!(count.load(Ordering::Relaxed), stopped_at);
+            assert!(!parent.is_cancelled());
+        });
+    }
+}
@@ -5,13 +5,17 @@
 //! Usage:
 //!   cargo test-qemu      # Run in QEMU emulator
 //!   cargo test-esp32     # Run on real ESP32 hardware
+//!   cargo test-qemu --filter fragment   # Only tests whose name contains "fragment"
+//!   cargo test-qemu --skip-tags network # Skip tests tagged "network"
+//!   cargo test-qemu --only-tags slow    # Only tests tagged "slow"
+//!   CARGO_TEST_ARGS="--filter fragment" cargo test-qemu  # Filter from the environment
 
 // This binary only runs on the host, not on ESP32
 #![cfg(not(target_os = "espidf"))]
 
 use reticulum_rs_esp32::host_utils::{
-    find_qemu, flash_binary, get_esp32_port, list_available_ports, monitor_output, start_monitor,
-    PortResult, ProcessGuard, TerminalGuard,
+    filter_from_test_args, find_qemu, flash_binary, get_esp32_port, list_available_ports,
+    monitor_output, start_monitor, PortResult, ProcessGuard, TerminalGuard, TestResults,
 };
 use serde::Deserialize;
 use std::io::Write;
@@ -57,23 +61,61 @@ impl Target {
     }
 }
 
+/// Environment variable read by `#[esp32_test]` at build time to ignore tests
+/// whose name doesn't contain the pattern.
+const FILTER_ENV: &str = "ESP32_TEST_FILTER";
+
+/// Environment variable read by `#[esp32_test]` at build time to ignore tests
+/// with any of the listed tags.
+const SKIP_TAGS_ENV: &str = "ESP32_TEST_SKIP_TAGS";
+
+/// Environment variable read by `#[esp32_test]` at build time to ignore tests
+/// with none of the listed tags.
+const ONLY_TAGS_ENV: &str = "ESP32_TEST_ONLY_TAGS";
+
+/// Test arguments used for the name filter when `--filter` isn't given, for
+/// IDEs and CI jobs that can set environment variables but not pass arguments.
+const CARGO_TEST_ARGS_ENV: &str = "CARGO_TEST_ARGS";
+
+/// Name filter pattern used when neither `--filter` nor `CARGO_TEST_ARGS`
+/// provides one.
+const RUST_TEST_FILTER_ENV: &str = "RUST_TEST_FILTER";
+
+/// Parsed command-line options.
+struct Options {
+    target: Target,
+    /// Only run tests whose name contains this pattern.
+    filter: Option<String>,
+    /// Skip tests with any of these tags (comma-separated).
+    skip_tags: Option<String>,
+    /// Only run tests with at least one of these tags (comma-separated).
+    only_tags: Option<String>,
+}
+
 fn main() {
-    let target = parse_args();
+    let options = parse_args();
 
-    if let Err(e) = run(target) {
+    if let Err(e) = run(&options) {
         eprintln!("Error: {}", e);
         exit(1);
     }
 }
 
-fn parse_args() -> Target {
+fn parse_args() -> Options {
     let args: Vec<String> = std::env::args().collect();
+    let mut target = None;
+    let mut filter = None;
+    let mut skip_tags = None;
+    let mut only_tags = None;
 
-    // Check for explicit flag
-    for arg in &args[1..] {
+    let mut rest = args[1..].iter();
+    while let Some(arg) = rest.next() {
         match arg.as_str() {
-            "--qemu" | "-q" => return Target::Qemu,
-            "--hardware" | "--hw" => return Target::Hardware,
+            "--qemu" | "-q" => target = target.or(Some(Target::Qemu)),
+            "--hardware" | "--hw" => target = target.or(Some(Target::Hardware)),
+            "--filter" => filter = Some(option_value(&mut rest, "--filter", "a pattern")),
+            "--skip-tags" => skip_tags = Some(option_value(&mut rest, "--skip-tags", "tags")),
+            "--only-tags" => only_tags = Some(option_value(&mut rest, "--only-tags", "tags")),
             "--help" => {
                 println!("ESP32 Test Runner");
                 println!();
@@ -81,16 +123,65 @@ fn parse_args() -> Target {
                 println!("  {} [OPTIONS]", args[0]);
                 println!();
                 println!("Options:");
-                println!("  --qemu, -q       Run tests in QEMU emulator (default)");
-                println!("  --hardware, --hw Run tests on real ESP32 hardware");
-                println!("  --help           Show this help");
+                println!("  --qemu, -q         Run tests in QEMU emulator (default)");
+                println!("  --hardware, --hw   Run tests on real ESP32 hardware");
+                println!("  --filter <pattern> Only run tests whose name contains <pattern>");
+                println!("  --skip-tags <tags> Skip tests with any of <tags> (comma-separated)");
+                println!(
+                    "  --only-tags <tags> Only run tests with one of <tags> (comma-separated)"
+                );
+                println!("  --help             Show this help");
+                println!();
+                println!("Environment:");
+                println!("  CARGO_TEST_ARGS    Test arguments, e.g. \"--filter fragment\"");
+                println!("  RUST_TEST_FILTER   Name filter pattern");
+                println!("  Both are used as --filter when it isn't given on the command line");
                 exit(0);
             }
             _ => {}
         }
     }
 
-    // Auto-detect based on binary name
+    Options {
+        target: target.unwrap_or_else(|| target_from_binary_name(&args)),
+        filter: filter.or_else(filter_from_env),
+        skip_tags,
+        only_tags,
+    }
+}
+
+/// Take the value following an option, exiting if it's missing.
+fn option_value<'a>(
+    rest: &mut impl Iterator<Item = &'a String>,
+    option: &str,
+    what: &str,
+) -> String {
+    match rest.next() {
+        Some(value) => value.clone(),
+        None => {
+            eprintln!("Error: {} requires {}", option, what);
+            exit(2);
+        }
+    }
+}
+
+/// Read the name filter from the fallback environment variables.
+///
+/// `CARGO_TEST_ARGS` wins over `RUST_TEST_FILTER`; the `--filter` option
+/// wins over both.
+fn filter_from_env() -> Option<String> {
+    let from_args = std::env::var(CARGO_TEST_ARGS_ENV)
+        .ok()
+        .and_then(|args| filter_from_test_args(&args));
+    from_args.or_else(|| {
+        let pattern = std::env::var(RUST_TEST_FILTER_ENV).ok()?;
+        let pattern = pattern.trim();
+        (!pattern.is_empty()).then(|| pattern.to_string())
+    })
+}
+
+/// Pick the target from the binary name when no flag was given.
+fn target_from_binary_name(args: &[String]) -> Target {
     if let Some(name) = args.first().and_then(|s| s.split('/').next_back()) {
         if name.contains("esp32") && !name.contains("qemu") {
             return Target::Hardware;
@@ -101,11 +192,31 @@ fn parse_args() -> Target {
     Target::Qemu
 }
 
-fn run(target: Target) -> Result<(), Box<dyn std::error::Error>> {
+fn run(options: &Options) -> Result<(), Box<dyn std::error::Error>> {
+    let target = options.target;
     println!("=== Building tests for {} ===", target.name());
 
+    // The device can't take libtest arguments, so filters are baked in at
+    // build time by #[esp32_test] (non-matching tests become ignored)
+    let mut cargo = Command::new("cargo");
+    for (var, value, label) in [
+        (FILTER_ENV, &options.filter, "name"),
+        (SKIP_TAGS_ENV, &options.skip_tags, "skipped tags"),
+        (ONLY_TAGS_ENV, &options.only_tags, "required tags"),
+    ] {
+        match value {
+            Some(value) => {
+                println!("Filtering tests by {}: {}", label, value);
+                cargo.env(var, value);
+            }
+            None => {
+                cargo.env_remove(var);
+            }
+        }
+    }
+
     // Build with JSON output to reliably find the test binary
-    let output = Command::new("cargo")
+    let output = cargo
         .args([
             "test",
             "--no-run",
@@ -156,8 +267,7 @@ fn run_qemu_tests(test_binary: &Path) -> Result<(), Box<dyn std::error::Error>>
     }
 
     // Find QEMU
-    let qemu_path = find_qemu()
-        .ok_or("QEMU not found. Install from: https://github.com/espressif/qemu/releases")?;
+    let qemu_path = find_qemu()?;
     println!("Using QEMU: {}", qemu_path.display());
 
     // Run QEMU
@@ -258,6 +368,7 @@ enum TestResult {
 fn run_test_monitor(stdout: impl std::io::Read) -> Result<(), Box<dyn std::error::Error>> {
     let mut test_state = TestState::Booting;
     let mut test_result: Option<TestResult> = None;
+    let mut results = TestResults::default();
 
     let monitor_result: Result<(), String> = monitor_output(stdout, TEST_TIMEOUT_SECS, |line| {
         // Update test state based on output
@@ -273,6 +384,7 @@ fn run_test_monitor(stdout: impl std::io::Read) -> Result<(), Box<dyn std::error
         if test_state != TestState::Booting {
             print!("{}\r\n", line);
             let _ = std::io::stdout().flush();
+            results.record_line(line);
         }
 
         // Check for test completion
@@ -309,6 +421,7 @@ fn run_test_monitor(stdout: impl std::io::Read) -> Result<(), Box<dyn std::error
     }
 
     print!("\r\n");
+    results.print_summary();
     match test_result {
         Some(TestResult::Passed) => {
             print!("=== All tests passed ===\r\n");
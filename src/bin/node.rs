@@ -9,13 +9,21 @@
 //! Connect via serial monitor and type commands:
 //! - `msg <id> <text>` - Send message to destination
 //! - `broadcast <text>` - Send to all known destinations
+//! - `connect <id>` - Establish a link without sending a message
+//! - `disconnect <id>` - Close the link to a destination
 //! - `list` - Show known destinations
 //! - `status` - Show node status
 //! - `help` - Show help
 //!
+//! ## Logging
+//!
+//! On host, set `RUST_LOG_FORMAT=json` to emit one JSON object per log line:
+//! `{"ts":"2024-01-01T00:00:00Z","level":"INFO","module":"node","msg":"..."}`.
+//!
 //! ## Endpoints
 //!
 //! - Stats: http://localhost:8080/stats
+//! - Health: http://localhost:8080/health (200 or 503)
 //!
 //! ## Lock Ordering
 //!
@@ -38,18 +46,22 @@
 
 use log::{debug, error, info, warn};
 use reticulum::destination::link::{Link, LinkEvent, LinkStatus};
-use reticulum::destination::{DestinationDesc, DestinationName, SingleInputDestination};
+use reticulum::destination::{DestinationDesc, DestinationName};
 use reticulum::hash::AddressHash;
 use reticulum::iface::tcp_client::TcpClient;
 use reticulum::transport::{Transport, TransportConfig};
+use reticulum_rs_esp32::announce::spawn_announce_timer;
 use reticulum_rs_esp32::chat::{self, ChatCommand, ChatState};
 use reticulum_rs_esp32::message_queue::{QueuedMessage, MAX_QUEUED_MESSAGES_PER_DEST};
-use reticulum_rs_esp32::{NodeStats, StatsServer, DEFAULT_STATS_PORT};
+#[cfg(not(feature = "esp32"))]
+use reticulum_rs_esp32::ShutdownManager;
+use reticulum_rs_esp32::{NetworkProvider, NodeStats, StatsServer, DEFAULT_STATS_PORT};
 use std::collections::HashMap;
 use std::io::{BufRead, Write};
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
 use tokio::sync::Mutex;
 use tokio_util::sync::CancellationToken;
 
@@ -59,6 +71,10 @@ type LinkCache = Arc<Mutex<HashMap<AddressHash, Arc<Mutex<Link>>>>>;
 /// Type alias for pending message queues per destination.
 type PendingMessages = Arc<Mutex<HashMap<AddressHash, Vec<QueuedMessage>>>>;
 
+/// Type alias for the platform network provider.
+/// Uses a std mutex because connect/reconnect block and run in `spawn_blocking`.
+type SharedNetwork = Arc<std::sync::Mutex<Box<dyn NetworkProvider>>>;
+
 /// Default testnet server. Dublin chosen for geographic diversity from
 /// Frankfurt (the other main server). See `src/testnet/config.rs` for alternatives.
 const TESTNET_SERVER: &str = "dublin.connect.reticulum.network:4965";
@@ -79,6 +95,12 @@ const MAX_CONCURRENT_LINKS: usize = 20;
 /// infrequent enough to avoid unnecessary lock contention.
 const QUEUE_CLEANUP_INTERVAL: Duration = Duration::from_secs(10);
 
+/// How often to check whether the network (WiFi on ESP32) is still up.
+const NETWORK_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Delay between reconnect attempts after the network drops.
+const RECONNECT_BACKOFF: Duration = Duration::from_secs(5);
+
 // ESP32: Initialize ESP-IDF before anything else
 #[cfg(feature = "esp32")]
 fn platform_init() {
@@ -87,10 +109,33 @@ fn platform_init() {
     info!("ESP-IDF initialized");
 }
 
+/// Environment variable selecting the host log format. `json` emits one JSON
+/// object per line for log shippers; anything else keeps plain text.
+#[cfg(not(feature = "esp32"))]
+const LOG_FORMAT_ENV: &str = "RUST_LOG_FORMAT";
+
 // Host: Just initialize env_logger
 #[cfg(not(feature = "esp32"))]
 fn platform_init() {
-    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+    let mut builder =
+        env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info"));
+    if std::env::var(LOG_FORMAT_ENV).is_ok_and(|format| format.eq_ignore_ascii_case("json")) {
+        builder.format(|buf, record| {
+            // Fields are written by hand to keep `ts` first; serde_json's
+            // map would sort the keys.
+            let quote = |s: &str| serde_json::to_string(s).unwrap_or_default();
+            let ts = buf.timestamp();
+            writeln!(
+                buf,
+                r#"{{"ts":"{}","level":"{}","module":{},"msg":{}}}"#,
+                ts,
+                record.level(),
+                quote(record.target()),
+                quote(&record.args().to_string())
+            )
+        });
+    }
+    builder.init();
 }
 
 /// Print a message to stdout (for chat output).
@@ -111,17 +156,73 @@ fn print_chat_with_prompt(msg: &str) {
     print_prompt();
 }
 
+/// Create the platform network provider.
+#[cfg(feature = "esp32")]
+fn create_network() -> Box<dyn NetworkProvider> {
+    let peripherals =
+        esp_idf_svc::hal::peripherals::Peripherals::take().expect("Failed to take peripherals");
+    let sysloop =
+        esp_idf_svc::eventloop::EspSystemEventLoop::take().expect("Failed to take event loop");
+    Box::new(
+        reticulum_rs_esp32::WifiNetwork::new(peripherals.modem, sysloop)
+            .expect("Failed to initialize WiFi"),
+    )
+}
+
+/// Create the platform network provider.
+#[cfg(not(feature = "esp32"))]
+fn create_network() -> Box<dyn NetworkProvider> {
+    Box::new(reticulum_rs_esp32::HostNetwork::new())
+}
+
+/// Check the provider's connection state, treating a poisoned lock as down.
+fn network_connected(network: &SharedNetwork) -> bool {
+    network.lock().map(|n| n.is_connected()).unwrap_or(false)
+}
+
+/// Reconnect the network, retrying with a fixed backoff until it succeeds
+/// or the node is shutting down.
+async fn reconnect_network(network: &SharedNetwork, cancel: &CancellationToken) {
+    let mut attempt: u32 = 0;
+    loop {
+        attempt += 1;
+        warn!("Network connection lost, reconnect attempt {}", attempt);
+
+        let net = network.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            // Recover from poisoning - the provider holds no invariants we could break
+            let mut guard = net.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            guard.reconnect()
+        })
+        .await;
+
+        match result {
+            Ok(Ok(())) => {
+                info!("Network reconnected after {} attempt(s)", attempt);
+                return;
+            }
+            Ok(Err(e)) => warn!("Reconnect attempt {} failed: {}", attempt, e),
+            Err(e) => warn!("Reconnect attempt {} panicked: {}", attempt, e),
+        }
+
+        tokio::select! {
+            _ = cancel.cancelled() => return,
+            _ = tokio::time::sleep(RECONNECT_BACKOFF) => {}
+        }
+    }
+}
+
 /// Spawn the network task that handles announces, link events, and message queuing.
 ///
 /// Returns a JoinHandle for the spawned task.
 fn spawn_network_task(
+    network: SharedNetwork,
     transport: Arc<Mutex<Transport>>,
     stats: Arc<NodeStats>,
     cancel: CancellationToken,
     chat_state: Arc<Mutex<ChatState>>,
     links: LinkCache,
     pending_messages: PendingMessages,
-    destination: Arc<Mutex<SingleInputDestination>>,
 ) -> tokio::task::JoinHandle<()> {
     tokio::spawn(async move {
         // Get all channel receivers in a single lock acquisition
@@ -134,14 +235,18 @@ fn spawn_network_task(
             )
         };
 
-        let mut announce_timer = tokio::time::interval(ANNOUNCE_INTERVAL);
-        announce_timer.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
-        announce_timer.tick().await; // Skip first
-
         let mut queue_cleanup_timer = tokio::time::interval(QUEUE_CLEANUP_INTERVAL);
         queue_cleanup_timer.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
         queue_cleanup_timer.tick().await; // Skip first
 
+        let mut network_check_timer = tokio::time::interval(NETWORK_CHECK_INTERVAL);
+        network_check_timer.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        network_check_timer.tick().await; // Skip first
+
+        // Reconnects run in their own task so announces and link events keep
+        // flowing while WiFi is down
+        let mut reconnect_task: Option<tokio::task::JoinHandle<()>> = None;
+
         loop {
             tokio::select! {
                 _ = cancel.cancelled() => {
@@ -149,12 +254,24 @@ fn spawn_network_task(
                     break;
                 }
 
-                // Periodic re-announcement
-                _ = announce_timer.tick() => {
-                    debug!("Sending periodic announce...");
-                    let t = transport.lock().await;
-                    t.send_announce(&destination, None).await;
-                    stats.testnet.record_tx();
+                // Periodic connectivity check (WiFi drops on ESP32)
+                _ = network_check_timer.tick() => {
+                    // A reconnect in progress holds the provider lock; don't block on it
+                    if reconnect_task.as_ref().is_some_and(|task| !task.is_finished()) {
+                        continue;
+                    }
+                    let connected = network_connected(&network);
+                    stats.wifi_connected.store(connected, Ordering::Relaxed);
+                    if !connected {
+                        let network = network.clone();
+                        let stats = stats.clone();
+                        let cancel = cancel.clone();
+                        reconnect_task = Some(tokio::spawn(async move {
+                            reconnect_network(&network, &cancel).await;
+                            let connected = network_connected(&network);
+                            stats.wifi_connected.store(connected, Ordering::Relaxed);
+                        }));
+                    }
                 }
 
                 // Periodic cleanup of expired queued messages
@@ -194,14 +311,27 @@ fn spawn_network_task(
                             debug!("Received announce: {:?}", hash);
                             stats.testnet.record_rx();
 
-                            // Add to chat state (only increment cache size if actually added)
-                            let added = {
+                            // Add to chat state, keeping the cache size in step with evictions
+                            let result = {
                                 let mut state = chat_state.lock().await;
                                 state.add_destination(hash, desc)
                             };
-                            if added {
+                            if result.was_new {
                                 stats.routing.announce_cache_size.fetch_add(1, Ordering::Relaxed);
                             }
+                            if let Some(evicted) = result.evicted {
+                                debug!("Evicted destination {:?} to make room", evicted);
+                                stats.routing.announce_cache_size.fetch_sub(1, Ordering::Relaxed);
+                            }
+                        }
+                        Err(RecvError::Lagged(n)) => {
+                            // Skip the backlog rather than process stale announces
+                            announces = announces.resubscribe();
+                            warn!("Announce channel lagged by {} messages, resubscribed", n);
+                            stats
+                                .routing
+                                .announces_lagged
+                                .fetch_add(n as usize, Ordering::Relaxed);
                         }
                         Err(e) => {
                             warn!("Announce channel error: {}", e);
@@ -239,7 +369,15 @@ fn spawn_network_task(
                     if let Ok(event) = result {
                         match event.event {
                             LinkEvent::Activated => {
-                                debug!("Outbound link activated: {:?}", event.id);
+                                let name = chat_state
+                                    .lock()
+                                    .await
+                                    .destination_by_hash(&event.id)
+                                    .map(|d| d.display_name.clone());
+                                match name {
+                                    Some(name) => debug!("Outbound link activated: {}", name),
+                                    None => debug!("Outbound link activated: {:?}", event.id),
+                                }
                                 // Flush any queued messages for this destination
                                 let messages = {
                                     let mut pending = pending_messages.lock().await;
@@ -373,7 +511,7 @@ async fn main() {
     };
 
     #[cfg(not(feature = "esp32"))]
-    let identity = reticulum_rs_esp32::persistence_host::load_or_create_identity()
+    let identity = reticulum_rs_esp32::persistence_host::load_or_create_identity_locked()
         .expect("Failed to load/create identity");
 
     let identity_hash = identity.address_hash().to_string();
@@ -402,6 +540,15 @@ async fn main() {
     // Create reticulum transport
     let transport = Arc::new(Mutex::new(Transport::new(TransportConfig::default())));
 
+    // Bring up the network (WiFi on ESP32). Failure is not fatal - the
+    // network task keeps retrying in the background.
+    let mut network = create_network();
+    match network.connect() {
+        Ok(()) => stats.wifi_connected.store(true, Ordering::Relaxed),
+        Err(e) => warn!("Network connect failed: {} (will retry)", e),
+    }
+    let network: SharedNetwork = Arc::new(std::sync::Mutex::new(network));
+
     // Connect to testnet (may fail if no WiFi configured - that's OK for local testing)
     info!("Connecting to testnet: {}", TESTNET_SERVER);
     {
@@ -446,15 +593,29 @@ async fn main() {
 
     // Spawn network task (announces, incoming messages, link events)
     let network_task = spawn_network_task(
+        network.clone(),
         transport.clone(),
         stats.clone(),
         cancel.clone(),
         chat_state.clone(),
         links.clone(),
         pending_messages.clone(),
-        destination.clone(),
     );
 
+    // Periodic re-announcement, stopped along with everything else by `cancel`
+    let announce_stats = stats.clone();
+    let _announce = spawn_announce_timer(
+        transport.clone(),
+        destination.clone(),
+        ANNOUNCE_INTERVAL,
+        &cancel,
+        move || {
+            debug!("Sent periodic announce");
+            announce_stats.testnet.record_tx();
+        },
+    )
+    .expect("ANNOUNCE_INTERVAL is non-zero");
+
     // Print welcome message
     print_chat("");
     print_chat("=== Reticulum Chat ===");
@@ -500,10 +661,17 @@ async fn main() {
     });
 
     // Wait for shutdown
+    #[cfg(not(feature = "esp32"))]
+    let shutdown = build_shutdown_manager(&links, &pending_messages);
+
     #[cfg(not(feature = "esp32"))]
     tokio::select! {
         _ = tokio::signal::ctrl_c() => {
             print_chat("\nShutting down...");
+            // Hooks block (file writes, std locks), keep them off the runtime threads
+            if let Err(e) = tokio::task::spawn_blocking(move || shutdown.run()).await {
+                error!("Shutdown hooks panicked: {}", e);
+            }
             cancel.cancel();
         }
         result = network_task => {
@@ -538,6 +706,40 @@ async fn main() {
     info!("Shutdown complete");
 }
 
+/// Register the hooks that run on Ctrl-C before tasks are cancelled.
+///
+/// Hooks run on a plain thread (not a runtime worker), so they use
+/// `blocking_lock` on the tokio mutexes.
+#[cfg(not(feature = "esp32"))]
+fn build_shutdown_manager(
+    links: &LinkCache,
+    pending_messages: &PendingMessages,
+) -> ShutdownManager {
+    let mut shutdown = ShutdownManager::new();
+
+    // Report queued messages that will never be sent
+    let pending = pending_messages.clone();
+    shutdown.register(move || {
+        let dropped: usize = pending.blocking_lock().values().map(Vec::len).sum();
+        if dropped > 0 {
+            warn!("Dropping {} queued message(s) on shutdown", dropped);
+        }
+    });
+
+    // Close links so peers don't wait for them to go stale
+    let links = links.clone();
+    shutdown.register(move || {
+        let links = std::mem::take(&mut *links.blocking_lock());
+        for link in links.values() {
+            link.blocking_lock().close();
+        }
+        debug!("Closed {} link(s)", links.len());
+    });
+
+    shutdown.register(|| info!("Clean shutdown complete"));
+    shutdown
+}
+
 /// Result of attempting to get or create a link.
 enum GetLinkResult {
     /// Found existing link.
@@ -574,6 +776,46 @@ async fn get_or_create_link(
     GetLinkResult::Created(new_link)
 }
 
+/// Get or create links to many destinations at once.
+///
+/// Like [`get_or_create_link`], but takes the transport lock at most once for
+/// all new links instead of once per unknown destination. Returns the links
+/// in destination order (`None` where the concurrent link limit was reached).
+///
+/// Lock ordering matches `get_or_create_link`: links cache, then transport.
+async fn get_or_create_links(
+    links: &LinkCache,
+    transport: &Arc<Mutex<Transport>>,
+    destinations: &[(AddressHash, DestinationDesc)],
+) -> Vec<Option<Arc<Mutex<Link>>>> {
+    let mut links_guard = links.lock().await;
+
+    let mut result = Vec::with_capacity(destinations.len());
+    let mut to_create = Vec::new();
+    let mut available = MAX_CONCURRENT_LINKS.saturating_sub(links_guard.len());
+    for (index, (hash, descriptor)) in destinations.iter().enumerate() {
+        if let Some(link) = links_guard.get(hash) {
+            result.push(Some(link.clone()));
+        } else if available > 0 {
+            available -= 1;
+            to_create.push((index, *hash, *descriptor));
+            result.push(None);
+        } else {
+            result.push(None);
+        }
+    }
+
+    if !to_create.is_empty() {
+        let t = transport.lock().await;
+        for (index, hash, descriptor) in to_create {
+            let new_link = t.link(descriptor).await;
+            links_guard.insert(hash, new_link.clone());
+            result[index] = Some(new_link);
+        }
+    }
+    result
+}
+
 /// Handle a parsed chat command.
 async fn handle_command(
     cmd: ChatCommand,
@@ -585,6 +827,10 @@ async fn handle_command(
 ) {
     match cmd {
         ChatCommand::Message { dest_id, text } => {
+            if let Err(e) = chat::validate_message_text(&text) {
+                print_chat(&format!("Not sent: {}", e));
+                return;
+            }
             let state = chat_state.lock().await;
             if let Some(dest) = state.get_destination(&dest_id) {
                 let hash = dest.hash;
@@ -639,7 +885,7 @@ async fn handle_command(
                         return;
                     }
                     queue.push(QueuedMessage::new(text));
-                    stats.queue.queued_messages.fetch_add(1, Ordering::Relaxed);
+                    stats.queue.record_queued();
                     let queue_len = queue.len();
                     drop(pending);
                     print_chat(&format!(
@@ -670,8 +916,16 @@ async fn handle_command(
         }
 
         ChatCommand::Broadcast { text } => {
+            if let Err(e) = chat::validate_message_text(&text) {
+                print_chat(&format!("Not sent: {}", e));
+                return;
+            }
             let state = chat_state.lock().await;
-            let destinations: Vec<_> = state.all_destinations().to_vec();
+            let destinations: Vec<_> = state
+                .known_destinations_sorted_by_recent()
+                .into_iter()
+                .cloned()
+                .collect();
             drop(state);
 
             if destinations.is_empty() {
@@ -679,19 +933,23 @@ async fn handle_command(
                 return;
             }
 
-            // Phase 1: Collect all packets (brief lock per link)
+            // The transport lock is taken at most once per phase, never
+            // per destination, and never while a link lock is held:
+            // Phase 1: Warm up links, creating any missing ones in one go
+            let targets: Vec<_> = destinations
+                .iter()
+                .map(|dest| (dest.hash, dest.descriptor))
+                .collect();
+            let dest_links = get_or_create_links(links, transport, &targets).await;
+
+            // Phase 2: Collect all packets (brief lock per link, no transport)
             let mut packets = Vec::new();
             let mut skipped = 0;
-            for dest in destinations {
-                // Get or create link
-                let link =
-                    match get_or_create_link(links, transport, dest.hash, dest.descriptor).await {
-                        GetLinkResult::Existing(link) | GetLinkResult::Created(link) => link,
-                        GetLinkResult::LimitReached => {
-                            skipped += 1;
-                            continue;
-                        }
-                    };
+            for link in dest_links {
+                let Some(link) = link else {
+                    skipped += 1;
+                    continue;
+                };
 
                 // Only send on active links
                 let link_guard = link.lock().await;
@@ -706,7 +964,7 @@ async fn handle_command(
                 // link_guard dropped here
             }
 
-            // Phase 2: Send all packets in single transport lock
+            // Phase 3: Send all packets in single transport lock
             let sent = packets.len();
             if !packets.is_empty() {
                 let t = transport.lock().await;
@@ -726,9 +984,84 @@ async fn handle_command(
             }
         }
 
-        ChatCommand::List => {
+        ChatCommand::Connect { dest_id } => {
+            let state = chat_state.lock().await;
+            let Some(dest) = state.get_destination(&dest_id) else {
+                print_chat(&format!("Unknown destination: {}", dest_id));
+                return;
+            };
+            let hash = dest.hash;
+            let descriptor = dest.descriptor;
+            let display_name = dest.display_name.clone();
+            drop(state);
+
+            match get_or_create_link(links, transport, hash, descriptor).await {
+                GetLinkResult::Existing(_) => {
+                    print_chat(&format!("Already linked to {}", display_name));
+                }
+                GetLinkResult::Created(_) => {
+                    print_chat(&format!("Connecting to {}...", display_name));
+                }
+                GetLinkResult::LimitReached => {
+                    print_chat("Too many active links. Wait for some to close.");
+                }
+            }
+        }
+
+        ChatCommand::Disconnect { dest_id } => {
+            let state = chat_state.lock().await;
+            let Some(dest) = state.get_destination(&dest_id) else {
+                print_chat(&format!("Unknown destination: {}", dest_id));
+                return;
+            };
+            let hash = dest.hash;
+            let display_name = dest.display_name.clone();
+            drop(state);
+
+            // Drop queued messages first (per lock ordering: pending_messages before links)
+            let dropped_count = pending_messages
+                .lock()
+                .await
+                .remove(&hash)
+                .map_or(0, |d| d.len());
+            if dropped_count > 0 {
+                stats
+                    .queue
+                    .queued_messages
+                    .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |val| {
+                        Some(val.saturating_sub(dropped_count))
+                    })
+                    .ok();
+            }
+
+            let link = links.lock().await.remove(&hash);
+            match link {
+                Some(link) => {
+                    link.lock().await.close();
+                    print_chat(&format!("Disconnected from {}", display_name));
+                }
+                None => {
+                    print_chat(&format!("No link to {}", display_name));
+                }
+            }
+        }
+
+        ChatCommand::List | ChatCommand::ListPage { .. } => {
+            let page = match cmd {
+                ChatCommand::ListPage { page } => page,
+                _ => 0,
+            };
+            // Snapshot link states first so chat_state isn't held across link locks
+            let link_statuses = {
+                let links_guard = links.lock().await;
+                let mut statuses = HashMap::with_capacity(links_guard.len());
+                for (hash, link) in links_guard.iter() {
+                    statuses.insert(*hash, link.lock().await.status());
+                }
+                statuses
+            };
             let state = chat_state.lock().await;
-            print_chat(&state.format_list());
+            print_chat(&state.format_list_page(&link_statuses, page, chat::LIST_PAGE_SIZE));
         }
 
         ChatCommand::Status => {
@@ -15,6 +15,13 @@
 //! - Bit 0: FIRST_FRAGMENT - This is the first fragment of a packet
 //! - Bit 1: MORE_FRAGMENTS - More fragments follow this one
 //!
+//! # Zero-Copy Fragmentation
+//!
+//! [`Fragmenter::fragment`] allocates a `Vec<u8>` per fragment. On the send
+//! path, [`Fragmenter::fragment_iter`] instead yields [`FragmentRef`]s that
+//! borrow their payload from the original packet, so fragments can be
+//! written straight into a transmit buffer with [`FragmentRef::write_to`].
+//!
 //! # Source Address Tracking
 //!
 //! The `Reassembler` tracks source addresses (BLE MAC addresses) to properly
@@ -45,11 +52,35 @@
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
+/// Kind of BLE device address (Bluetooth Core Spec Vol 6, Part B, 1.3).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BleAddressType {
+    /// IEEE-assigned public address (OUI-based).
+    PublicDevice,
+    /// Random address fixed for the device's power cycle (top bits `11`).
+    RandomStatic,
+    /// Random address resolvable with the peer's IRK (top bits `01`).
+    RandomResolvablePrivate,
+    /// Random address that can't be traced back to the device (top bits `00`).
+    RandomNonResolvablePrivate,
+}
+
 /// BLE device address (MAC address).
 ///
 /// A 6-byte Bluetooth device address used to identify the source of fragments
 /// for proper reassembly disambiguation.
+///
+/// # Invariants
+///
+/// `BleAddress` stores only the address bytes, not the public/random flag
+/// that the link layer carries alongside them. `Eq` and `Hash` therefore
+/// compare bytes alone: a public and a random NimBLE address with the same
+/// bytes convert to equal `BleAddress`es and share one reassembly. This is
+/// intentional; such a collision needs a random address that matches a
+/// nearby public one bit for bit, and keying reassembly by bytes keeps a
+/// peer's fragments together whichever way the stack reports its type.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BleAddress([u8; 6]);
 
 impl BleAddress {
@@ -67,6 +98,57 @@ impl BleAddress {
     pub const fn zero() -> Self {
         Self([0; 6])
     }
+
+    /// Check if this is the BLE broadcast address (`FF:FF:FF:FF:FF:FF`).
+    ///
+    /// Fragments from this address can't be attributed to a single peer.
+    pub fn is_broadcast(&self) -> bool {
+        self.0 == [0xFF; 6]
+    }
+
+    /// Classify the address by the two most significant bits of byte 0.
+    ///
+    /// Whether an address is public or random is signalled separately (the
+    /// TxAdd/RxAdd header bit), which `BleAddress` does not track. This
+    /// assumes a random address and only reports [`PublicDevice`] for the
+    /// `10` pattern that no random subtype uses, so a public address may
+    /// also show up as a random type. Don't look up the OUI of an address
+    /// classified as random.
+    ///
+    /// [`PublicDevice`]: BleAddressType::PublicDevice
+    pub fn address_type(&self) -> BleAddressType {
+        match self.0[0] >> 6 {
+            0b11 => BleAddressType::RandomStatic,
+            0b01 => BleAddressType::RandomResolvablePrivate,
+            0b00 => BleAddressType::RandomNonResolvablePrivate,
+            _ => BleAddressType::PublicDevice,
+        }
+    }
+
+    /// Check the IEEE locally administered bit (bit 1 of byte 0).
+    ///
+    /// Set for addresses not assigned from a manufacturer's OUI.
+    pub fn is_locally_administered(&self) -> bool {
+        self.0[0] & 0x02 != 0
+    }
+}
+
+// The BLE stack here is NimBLE (`esp_idf_svc::bt::BdAddr` only exists with
+// Bluedroid), so convert from its address type. Bytes are kept in display
+// (big-endian) order to match `Display`.
+#[cfg(feature = "esp32")]
+impl From<esp32_nimble::BLEAddress> for BleAddress {
+    fn from(addr: esp32_nimble::BLEAddress) -> Self {
+        Self(addr.as_be_bytes())
+    }
+}
+
+/// Converts to a public address; `BleAddress` does not track the address type.
+#[cfg(feature = "esp32")]
+impl From<BleAddress> for esp32_nimble::BLEAddress {
+    fn from(addr: BleAddress) -> Self {
+        esp32_nimble::BLEAddress::from_be_bytes(addr.0, esp32_nimble::BLEAddressType::Public)
+    }
 }
 
 impl std::fmt::Display for BleAddress {
@@ -103,6 +185,7 @@ const DEFAULT_MAX_FRAGMENTS: usize = 32;
 
 /// A single fragment of a larger packet.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Fragment {
     /// Sequence number (0-255, wraps around).
     pub sequence: u8,
@@ -165,9 +248,23 @@ impl Fragment {
 
     /// Deserialize fragment from bytes.
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, FragmentError> {
+        Self::from_bytes_with_mtu(bytes, usize::MAX)
+    }
+
+    /// Deserialize fragment from bytes, rejecting fragments longer than `mtu`.
+    ///
+    /// A peer can't legitimately send more than the negotiated MTU in one
+    /// write, so an oversized fragment indicates a buggy or hostile sender.
+    pub fn from_bytes_with_mtu(bytes: &[u8], mtu: usize) -> Result<Self, FragmentError> {
         if bytes.len() < HEADER_SIZE {
             return Err(FragmentError::TooShort);
         }
+        if bytes.len() > mtu {
+            return Err(FragmentError::PayloadTooLarge {
+                size: bytes.len(),
+                max: mtu,
+            });
+        }
         Ok(Self {
             sequence: bytes[0],
             flags: bytes[1],
@@ -176,8 +273,114 @@ impl Fragment {
     }
 }
 
+/// A fragment whose payload borrows from the packet being fragmented.
+///
+/// Produced by [`Fragmenter::fragment_iter`]. Use [`into_owned`](Self::into_owned)
+/// to get a [`Fragment`] when the payload must outlive the packet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FragmentRef<'a> {
+    /// Sequence number (0-255, wraps around).
+    pub sequence: u8,
+    /// Fragment flags.
+    pub flags: u8,
+    /// Payload data (without header), borrowed from the original packet.
+    pub payload: &'a [u8],
+}
+
+impl FragmentRef<'_> {
+    /// Check if this is the first fragment of a packet.
+    #[inline]
+    pub fn is_first(&self) -> bool {
+        self.flags & FLAG_FIRST_FRAGMENT != 0
+    }
+
+    /// Check if more fragments follow this one.
+    #[inline]
+    pub fn has_more(&self) -> bool {
+        self.flags & FLAG_MORE_FRAGMENTS != 0
+    }
+
+    /// Serialize fragment to bytes (header + payload).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_SIZE + self.payload.len());
+        bytes.push(self.sequence);
+        bytes.push(self.flags);
+        bytes.extend_from_slice(self.payload);
+        bytes
+    }
+
+    /// Serialize fragment into provided buffer.
+    ///
+    /// Returns the number of bytes written, or error if buffer too small.
+    pub fn write_to(&self, buf: &mut [u8]) -> Result<usize, FragmentError> {
+        let total_len = HEADER_SIZE + self.payload.len();
+        if buf.len() < total_len {
+            return Err(FragmentError::BufferTooSmall);
+        }
+        buf[0] = self.sequence;
+        buf[1] = self.flags;
+        buf[HEADER_SIZE..total_len].copy_from_slice(self.payload);
+        Ok(total_len)
+    }
+
+    /// Copy the payload into an owned [`Fragment`].
+    pub fn into_owned(self) -> Fragment {
+        Fragment::new(self.sequence, self.flags, self.payload.to_vec())
+    }
+}
+
+/// Lazy iterator over the fragments of a packet.
+///
+/// Created by [`Fragmenter::fragment_iter`]. Sequence numbers are taken from
+/// the fragmenter as each fragment is yielded.
+#[derive(Debug)]
+pub struct FragmentIter<'a> {
+    packet: &'a [u8],
+    offset: usize,
+    max_payload: usize,
+    next_sequence: &'a mut u8,
+}
+
+impl<'a> Iterator for FragmentIter<'a> {
+    type Item = FragmentRef<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.offset >= self.packet.len() {
+            return None;
+        }
+
+        let payload_len = (self.packet.len() - self.offset).min(self.max_payload);
+        let end = self.offset + payload_len;
+
+        let mut flags = 0u8;
+        if self.offset == 0 {
+            flags |= FLAG_FIRST_FRAGMENT;
+        }
+        if end < self.packet.len() {
+            flags |= FLAG_MORE_FRAGMENTS;
+        }
+
+        let fragment = FragmentRef {
+            sequence: *self.next_sequence,
+            flags,
+            payload: &self.packet[self.offset..end],
+        };
+        *self.next_sequence = self.next_sequence.wrapping_add(1);
+        self.offset = end;
+        Some(fragment)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.packet.len() - self.offset).div_ceil(self.max_payload);
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for FragmentIter<'_> {}
+
 /// Errors that can occur during fragmentation/reassembly.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FragmentError {
     /// Fragment data is too short to contain header.
     TooShort,
@@ -191,6 +394,10 @@ pub enum FragmentError {
     MissingFragment(u8),
     /// Invalid flags on fragment.
     InvalidFlags,
+    /// Fragment (header included) exceeds the MTU.
+    PayloadTooLarge { size: usize, max: usize },
+    /// More than one fragment is flagged as the first.
+    MultipleFirstFragments,
 }
 
 impl std::fmt::Display for FragmentError {
@@ -202,6 +409,10 @@ impl std::fmt::Display for FragmentError {
             Self::BufferTooSmall => write!(f, "buffer too small for fragment"),
             Self::MissingFragment(seq) => write!(f, "missing fragment with sequence {}", seq),
             Self::InvalidFlags => write!(f, "invalid flags on fragment"),
+            Self::PayloadTooLarge { size, max } => {
+                write!(f, "fragment too large: {} bytes (max {})", size, max)
+            }
+            Self::MultipleFirstFragments => write!(f, "multiple first fragments"),
         }
     }
 }
@@ -251,48 +462,137 @@ impl Fragmenter {
 
     /// Fragment a packet into one or more fragments.
     ///
-    /// Returns an iterator over fragments. The first fragment will have
+    /// Returns the fragments in order. The first fragment will have
     /// FLAG_FIRST_FRAGMENT set. All fragments except the last will have
     /// FLAG_MORE_FRAGMENTS set.
+    #[must_use = "dropping fragments means the packet is lost"]
     pub fn fragment(&mut self, packet: &[u8]) -> Result<Vec<Fragment>, FragmentError> {
         if packet.is_empty() {
             return Err(FragmentError::EmptyPacket);
         }
 
-        let max_payload = self.max_payload();
-        let fragment_count = packet.len().div_ceil(max_payload);
-        let mut fragments = Vec::with_capacity(fragment_count);
-        let mut offset = 0;
-        let mut is_first = true;
-
-        while offset < packet.len() {
-            let remaining = packet.len() - offset;
-            let payload_len = remaining.min(max_payload);
-            let has_more = offset + payload_len < packet.len();
-
-            let mut flags = 0u8;
-            if is_first {
-                flags |= FLAG_FIRST_FRAGMENT;
-                is_first = false;
-            }
-            if has_more {
-                flags |= FLAG_MORE_FRAGMENTS;
-            }
-
-            let payload = packet[offset..offset + payload_len].to_vec();
-            fragments.push(Fragment::new(self.next_sequence, flags, payload));
+        Ok(self
+            .fragment_iter(packet)
+            .map(FragmentRef::into_owned)
+            .collect())
+    }
 
-            self.next_sequence = self.next_sequence.wrapping_add(1);
-            offset += payload_len;
+    /// Lazily fragment a packet without copying its payload.
+    ///
+    /// Yields the same fragments as [`fragment`](Self::fragment), but each
+    /// payload borrows from `packet`. An empty packet yields no fragments.
+    /// Sequence numbers are consumed only for fragments actually yielded.
+    pub fn fragment_iter<'a>(&'a mut self, packet: &'a [u8]) -> FragmentIter<'a> {
+        FragmentIter {
+            packet,
+            offset: 0,
+            max_payload: self.max_payload(),
+            next_sequence: &mut self.next_sequence,
         }
-
-        Ok(fragments)
     }
 
     /// Check if a packet needs fragmentation for this MTU.
+    #[must_use = "ignoring the result means you may send unfragmented packets over a small-MTU link"]
     pub fn needs_fragmentation(&self, packet_len: usize) -> bool {
         packet_len > self.max_payload()
     }
+
+    /// Number of fragments a packet of `packet_len` bytes produces at `mtu`.
+    ///
+    /// Returns 0 for an empty packet. Useful for pre-allocating BLE writes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if MTU is less than HEADER_SIZE + 1 (minimum 3 bytes).
+    pub fn fragment_count(packet_len: usize, mtu: usize) -> usize {
+        Self::try_fragment_count(packet_len, mtu).expect("MTU must be greater than header size")
+    }
+
+    /// Number of fragments a packet of `packet_len` bytes produces at `mtu`.
+    ///
+    /// Returns `Err(FragmentError::MtuTooSmall)` if MTU is less than
+    /// HEADER_SIZE + 1 (minimum 3 bytes).
+    pub fn try_fragment_count(packet_len: usize, mtu: usize) -> Result<usize, FragmentError> {
+        if mtu <= HEADER_SIZE {
+            return Err(FragmentError::MtuTooSmall);
+        }
+        Ok(packet_len.div_ceil(mtu - HEADER_SIZE))
+    }
+
+    /// Total on-air bytes (headers included) for a packet of `packet_len` bytes.
+    ///
+    /// Equal to `fragment_count * mtu` minus the unused space in the last
+    /// fragment, i.e. the exact BLE buffer size needed for all fragments.
+    ///
+    /// # Panics
+    ///
+    /// Panics if MTU is less than HEADER_SIZE + 1 (minimum 3 bytes).
+    pub fn total_bytes_for_fragments(packet_len: usize, mtu: usize) -> usize {
+        Self::fragment_count(packet_len, mtu) * HEADER_SIZE + packet_len
+    }
+
+    /// Check whether `fragments` form one complete packet.
+    ///
+    /// See [`validate_fragment_sequence`] for the checks and error details.
+    pub fn can_reassemble_without_loss(fragments: &[Fragment]) -> bool {
+        validate_fragment_sequence(fragments).is_ok()
+    }
+}
+
+/// Validate that `fragments` form one complete packet, in any order.
+///
+/// Checks that all flags are valid, exactly one fragment is first, exactly
+/// one is last (no `FLAG_MORE_FRAGMENTS`), and sequence numbers from first to
+/// last have no gaps. Duplicates are allowed since the [`Reassembler`]
+/// tolerates them.
+///
+/// # Errors
+///
+/// - [`FragmentError::EmptyPacket`] if `fragments` is empty
+/// - [`FragmentError::InvalidFlags`] for undefined flag bits, no first
+///   fragment, several last fragments or fragments after the last
+/// - [`FragmentError::MultipleFirstFragments`] if several are flagged first
+/// - [`FragmentError::MissingFragment`] with the first missing sequence number
+pub fn validate_fragment_sequence(fragments: &[Fragment]) -> Result<(), FragmentError> {
+    if fragments.is_empty() {
+        return Err(FragmentError::EmptyPacket);
+    }
+    if !fragments.iter().all(Fragment::has_valid_flags) {
+        return Err(FragmentError::InvalidFlags);
+    }
+
+    let mut firsts = fragments.iter().filter(|f| f.is_first());
+    let first = firsts.next().ok_or(FragmentError::InvalidFlags)?;
+    if firsts.next().is_some() {
+        return Err(FragmentError::MultipleFirstFragments);
+    }
+
+    // Positions relative to the first fragment, so wraparound needs no care
+    let offset = |f: &Fragment| f.sequence.wrapping_sub(first.sequence);
+    let mut lasts = fragments.iter().filter(|f| !f.has_more()).map(offset);
+    let last = match lasts.next() {
+        Some(last) => last,
+        None => {
+            let highest = fragments.iter().map(offset).max().unwrap_or(0);
+            return Err(FragmentError::MissingFragment(
+                first.sequence.wrapping_add(highest).wrapping_add(1),
+            ));
+        }
+    };
+    if lasts.next().is_some() || fragments.iter().any(|f| offset(f) > last) {
+        return Err(FragmentError::InvalidFlags);
+    }
+
+    let mut seen = [false; 256];
+    for fragment in fragments {
+        seen[offset(fragment) as usize] = true;
+    }
+    match (0..=last).find(|&i| !seen[i as usize]) {
+        Some(missing) => Err(FragmentError::MissingFragment(
+            first.sequence.wrapping_add(missing),
+        )),
+        None => Ok(()),
+    }
 }
 
 /// Key for identifying a pending packet reassembly.
@@ -369,6 +669,26 @@ impl PendingPacket {
     }
 }
 
+/// What to do when a first fragment arrives for a reassembly already in progress.
+///
+/// This happens when the sender retransmits a packet from the start, e.g.
+/// because it never received an acknowledgement.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RetransmitPolicy {
+    /// Ignore the retransmitted first fragment and keep the existing reassembly.
+    #[default]
+    DropRetransmit,
+    /// Discard the existing reassembly and start over from the new first fragment.
+    RestartReassembly,
+}
+
+/// Counters describing reassembler activity.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReassemblerStats {
+    /// First fragments received while a reassembly with the same key was pending.
+    pub retransmits_detected: u64,
+}
+
 /// Reassembles fragments back into complete packets.
 ///
 /// # Memory Safety
@@ -387,6 +707,12 @@ pub struct Reassembler {
     max_pending: usize,
     /// Maximum fragments allowed per packet.
     max_fragments_per_packet: usize,
+    /// How to handle a retransmitted first fragment.
+    retransmit_policy: RetransmitPolicy,
+    /// Largest accepted fragment (header included), if limited.
+    max_fragment_size: Option<usize>,
+    /// Activity counters.
+    stats: ReassemblerStats,
 }
 
 impl Reassembler {
@@ -418,9 +744,68 @@ impl Reassembler {
             timeout,
             max_pending,
             max_fragments_per_packet,
+            retransmit_policy: RetransmitPolicy::default(),
+            max_fragment_size: None,
+            stats: ReassemblerStats::default(),
         }
     }
 
+    /// Set how retransmitted first fragments are handled.
+    pub fn with_retransmit_policy(mut self, policy: RetransmitPolicy) -> Self {
+        self.retransmit_policy = policy;
+        self
+    }
+
+    /// Reject fragments larger than `mtu` bytes (header included).
+    ///
+    /// Set this to the negotiated BLE MTU. Unlimited by default.
+    pub fn with_max_fragment_size(mut self, mtu: usize) -> Self {
+        self.max_fragment_size = Some(mtu);
+        self
+    }
+
+    /// Get the configured retransmit policy.
+    pub fn retransmit_policy(&self) -> RetransmitPolicy {
+        self.retransmit_policy
+    }
+
+    /// Get activity counters.
+    pub fn stats(&self) -> ReassemblerStats {
+        self.stats
+    }
+
+    /// Get the timeout for incomplete packets.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Get the age of the oldest pending reassembly (None if nothing is pending).
+    ///
+    /// Expired entries are only removed when fragments arrive, so an age well
+    /// past [`timeout`](Self::timeout) (say twice) means cleanup isn't running.
+    pub fn oldest_pending_age(&self) -> Option<Duration> {
+        let now = Instant::now();
+        self.pending
+            .values()
+            .map(|p| now.saturating_duration_since(p.started))
+            .max()
+    }
+
+    /// Parse raw fragment bytes and add the fragment.
+    ///
+    /// Uses [`Fragment::from_bytes_with_mtu`] when a maximum fragment size is
+    /// configured, so oversized writes are reported as errors rather than
+    /// silently dropped.
+    pub fn add_fragment_bytes(
+        &mut self,
+        source: BleAddress,
+        bytes: &[u8],
+    ) -> Result<Option<Vec<u8>>, FragmentError> {
+        let fragment =
+            Fragment::from_bytes_with_mtu(bytes, self.max_fragment_size.unwrap_or(usize::MAX))?;
+        Ok(self.add_fragment(source, fragment))
+    }
+
     /// Add a fragment and return the complete packet if reassembly is done.
     ///
     /// # Arguments
@@ -433,6 +818,7 @@ impl Reassembler {
     ///
     /// Fragments are rejected if:
     /// - They have invalid flags
+    /// - They exceed the configured maximum fragment size
     /// - The reassembly would exceed fragment limits
     /// - No matching reassembly exists for non-first fragments
     pub fn add_fragment(&mut self, source: BleAddress, fragment: Fragment) -> Option<Vec<u8>> {
@@ -441,6 +827,12 @@ impl Reassembler {
             return None;
         }
 
+        if let Some(mtu) = self.max_fragment_size {
+            if HEADER_SIZE + fragment.payload.len() > mtu {
+                return None;
+            }
+        }
+
         // Clean up expired entries
         self.cleanup_expired();
 
@@ -456,6 +848,17 @@ impl Reassembler {
                 first_sequence: fragment.sequence,
             };
 
+            // Sender restarted a packet we're still reassembling
+            if self.pending.contains_key(&key) {
+                self.stats.retransmits_detected += 1;
+                match self.retransmit_policy {
+                    RetransmitPolicy::DropRetransmit => return None,
+                    RetransmitPolicy::RestartReassembly => {
+                        self.pending.remove(&key);
+                    }
+                }
+            }
+
             // Enforce max_pending limit by evicting oldest if needed
             if self.pending.len() >= self.max_pending {
                 if let Some(oldest_key) = self.find_oldest_pending() {
@@ -463,11 +866,6 @@ impl Reassembler {
                 }
             }
 
-            // Don't overwrite existing reassembly with same key
-            if self.pending.contains_key(&key) {
-                return None;
-            }
-
             let mut pending = PendingPacket::new(fragment.sequence);
             pending
                 .fragments
@@ -512,24 +910,37 @@ impl Reassembler {
     /// Since we know the source address, we only search among reassemblies from
     /// this source. This is typically O(1) since most sources only have one
     /// active reassembly at a time.
+    ///
+    /// When a source has several reassemblies in flight, the fragment belongs
+    /// to the one whose first sequence is closest before it. Reassemblies that
+    /// already saw their last fragment are skipped once the fragment lies past
+    /// it, so a middle fragment of an earlier packet arriving after a later
+    /// packet's last fragment still lands in the right place.
     fn find_key_for_fragment(
         &self,
         source: BleAddress,
         fragment: &Fragment,
     ) -> Option<ReassemblyKey> {
-        for key in self.pending.keys() {
+        self.pending
+            .iter()
             // Only consider reassemblies from this source
-            if key.source != source {
-                continue;
-            }
-            // Check if this fragment's sequence is within reasonable range
-            // of the first fragment's sequence (within MAX_SEQUENCE_DISTANCE)
-            let seq_diff = fragment.sequence.wrapping_sub(key.first_sequence);
-            if seq_diff > 0 && seq_diff < MAX_SEQUENCE_DISTANCE {
-                return Some(*key);
-            }
-        }
-        None
+            .filter(|(key, _)| key.source == source)
+            .filter_map(|(key, pending)| {
+                // Check if this fragment's sequence is within reasonable range
+                // of the first fragment's sequence (within MAX_SEQUENCE_DISTANCE)
+                let seq_diff = fragment.sequence.wrapping_sub(key.first_sequence);
+                if seq_diff == 0 || seq_diff >= MAX_SEQUENCE_DISTANCE {
+                    return None;
+                }
+                if let Some(last) = pending.last_sequence {
+                    if seq_diff > last.wrapping_sub(key.first_sequence) {
+                        return None;
+                    }
+                }
+                Some((seq_diff, *key))
+            })
+            .min_by_key(|(seq_diff, _)| *seq_diff)
+            .map(|(_, key)| key)
     }
 
     /// Find the oldest pending reassembly for eviction.
@@ -570,6 +981,33 @@ mod tests {
     /// Second source address for multi-peer tests.
     const TEST_SOURCE_2: BleAddress = BleAddress::new([0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF]);
 
+    // ==================== BleAddress Tests ====================
+
+    #[esp32_test]
+    fn test_ble_address_is_broadcast() {
+        assert!(BleAddress::new([0xFF; 6]).is_broadcast());
+        assert!(!BleAddress::zero().is_broadcast());
+        assert!(!BleAddress::new([0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFE]).is_broadcast());
+    }
+
+    #[esp32_test]
+    fn test_ble_address_classification() {
+        let addr = |first| BleAddress::new([first, 0, 0, 0, 0, 1]);
+        assert_eq!(addr(0xC1).address_type(), BleAddressType::RandomStatic);
+        assert_eq!(
+            addr(0x4A).address_type(),
+            BleAddressType::RandomResolvablePrivate
+        );
+        assert_eq!(
+            addr(0x3F).address_type(),
+            BleAddressType::RandomNonResolvablePrivate
+        );
+        assert_eq!(addr(0x80).address_type(), BleAddressType::PublicDevice);
+
+        assert!(addr(0x02).is_locally_administered());
+        assert!(!addr(0xFD).is_locally_administered());
+    }
+
     // ==================== Fragment Tests ====================
 
     #[esp32_test]
@@ -638,6 +1076,16 @@ mod tests {
         assert!(Fragment::from_bytes(&[0, 0]).is_ok());
     }
 
+    #[esp32_test]
+    fn test_fragment_from_bytes_with_mtu() {
+        let bytes = [0, FLAG_FIRST_FRAGMENT, 1, 2, 3];
+        assert!(Fragment::from_bytes_with_mtu(&bytes, 5).is_ok());
+        assert_eq!(
+            Fragment::from_bytes_with_mtu(&bytes, 4),
+            Err(FragmentError::PayloadTooLarge { size: 5, max: 4 })
+        );
+    }
+
     // ==================== Fragmenter Tests ====================
 
     #[esp32_test]
@@ -752,6 +1200,120 @@ mod tests {
         assert!(Fragmenter::try_new(3).is_ok()); // Minimum valid MTU
     }
 
+    #[esp32_test]
+    fn test_fragment_iter_matches_fragment() {
+        let packet: Vec<u8> = (0..50).collect();
+        let mut eager = Fragmenter::new(20);
+        let mut lazy = Fragmenter::new(20);
+
+        let expected = eager.fragment(&packet).unwrap();
+        let iter = lazy.fragment_iter(&packet);
+        assert_eq!(iter.len(), expected.len());
+
+        let borrowed: Vec<FragmentRef> = iter.collect();
+        for (frag_ref, frag) in borrowed.iter().zip(&expected) {
+            // Payload is a slice of the original packet, not a copy
+            assert!(packet.as_ptr_range().contains(&frag_ref.payload.as_ptr()));
+            assert_eq!(frag_ref.to_bytes(), frag.to_bytes());
+            assert_eq!(frag_ref.into_owned(), *frag);
+        }
+
+        // Both fragmenters advanced their sequence numbers identically
+        assert_eq!(
+            lazy.fragment_iter(&[1]).next().unwrap().sequence,
+            eager.fragment(&[1]).unwrap()[0].sequence
+        );
+    }
+
+    #[esp32_test]
+    fn test_fragment_iter_lazy_sequence_and_empty() {
+        let mut fragmenter = Fragmenter::new(5); // 3 byte payload
+        assert_eq!(fragmenter.fragment_iter(&[]).next(), None);
+
+        // Only consumed fragments advance the sequence number
+        let first = fragmenter
+            .fragment_iter(&[1, 2, 3, 4, 5, 6, 7])
+            .next()
+            .unwrap();
+        assert!(first.is_first() && first.has_more());
+        assert_eq!(first.sequence, 0);
+
+        let mut buf = [0u8; 5];
+        let next = fragmenter.fragment_iter(&[9]).next().unwrap();
+        assert_eq!(next.write_to(&mut buf), Ok(3));
+        assert_eq!(&buf[..3], &[1, FLAG_FIRST_FRAGMENT, 9]);
+    }
+
+    #[esp32_test]
+    fn test_fragment_count_matches_fragment() {
+        for mtu in [3, 20, 23, 185] {
+            for len in [1, 2, 17, 18, 19, 100, 500] {
+                let fragments = Fragmenter::new(mtu).fragment(&vec![0xAB; len]).unwrap();
+                assert_eq!(Fragmenter::fragment_count(len, mtu), fragments.len());
+                let total: usize = fragments.iter().map(|f| f.to_bytes().len()).sum();
+                assert_eq!(Fragmenter::total_bytes_for_fragments(len, mtu), total);
+            }
+        }
+
+        assert_eq!(Fragmenter::fragment_count(0, 20), 0);
+        assert_eq!(Fragmenter::total_bytes_for_fragments(0, 20), 0);
+        assert_eq!(
+            Fragmenter::try_fragment_count(10, HEADER_SIZE),
+            Err(FragmentError::MtuTooSmall)
+        );
+    }
+
+    #[esp32_test]
+    fn test_validate_fragment_sequence() {
+        let mut fragmenter = Fragmenter::new(5);
+        fragmenter.next_sequence = 254; // Sequence wraps mid-packet
+        let fragments = fragmenter.fragment(&[0u8; 12]).unwrap();
+        assert_eq!(fragments.len(), 4);
+        assert_eq!(validate_fragment_sequence(&fragments), Ok(()));
+
+        // Order doesn't matter
+        let mut shuffled = fragments.clone();
+        shuffled.reverse();
+        assert!(Fragmenter::can_reassemble_without_loss(&shuffled));
+
+        let mut gap = fragments.clone();
+        gap.remove(2);
+        assert_eq!(
+            validate_fragment_sequence(&gap),
+            Err(FragmentError::MissingFragment(0))
+        );
+
+        let no_last = &fragments[..3];
+        assert_eq!(
+            validate_fragment_sequence(no_last),
+            Err(FragmentError::MissingFragment(1))
+        );
+
+        let mut two_firsts = fragments.clone();
+        two_firsts[1].flags |= FLAG_FIRST_FRAGMENT;
+        assert_eq!(
+            validate_fragment_sequence(&two_firsts),
+            Err(FragmentError::MultipleFirstFragments)
+        );
+
+        let mut bad_flags = fragments.clone();
+        bad_flags[1].flags |= 0x80;
+        assert_eq!(
+            validate_fragment_sequence(&bad_flags),
+            Err(FragmentError::InvalidFlags)
+        );
+
+        assert_eq!(
+            validate_fragment_sequence(&fragments[1..]),
+            Err(FragmentError::InvalidFlags)
+        );
+        assert_eq!(
+            validate_fragment_sequence(&[]),
+            Err(FragmentError::EmptyPacket)
+        );
+        assert!(!Fragmenter::can_reassemble_without_loss(&gap));
+    }
+
     // ==================== Reassembler Tests ====================
 
     #[esp32_test]
@@ -798,6 +1360,62 @@ mod tests {
         assert_eq!(result, Some(vec![1, 2, 3, 4, 5, 6]));
     }
 
+    #[esp32_test]
+    fn test_reassembler_last_before_middle_with_overlapping_packets() {
+        let mut reassembler = Reassembler::new(Duration::from_secs(5));
+
+        // Two back-to-back packets from one source: A = 10..=12, B = 13..=14.
+        // Both are within MAX_SEQUENCE_DISTANCE of each other's first sequence.
+        let a1 = Fragment::new(10, FLAG_FIRST_FRAGMENT | FLAG_MORE_FRAGMENTS, vec![1]);
+        let a2 = Fragment::new(11, FLAG_MORE_FRAGMENTS, vec![2]);
+        let a3 = Fragment::new(12, 0, vec![3]);
+        let b1 = Fragment::new(13, FLAG_FIRST_FRAGMENT | FLAG_MORE_FRAGMENTS, vec![10]);
+        let b2 = Fragment::new(14, 0, vec![20]);
+
+        // A's last fragment arrives before its middle one, and B starts
+        // before A completes
+        assert_eq!(reassembler.add_fragment(TEST_SOURCE, a1), None);
+        assert_eq!(reassembler.add_fragment(TEST_SOURCE, a3), None);
+        assert_eq!(reassembler.add_fragment(TEST_SOURCE, b1), None);
+        assert_eq!(reassembler.pending_count(), 2);
+
+        // B's last fragment must not be claimed by A, which is 4 sequences back
+        assert_eq!(
+            reassembler.add_fragment(TEST_SOURCE, b2),
+            Some(vec![10, 20])
+        );
+        assert_eq!(reassembler.pending_count(), 1);
+
+        // A's middle fragment finally arrives and completes A
+        assert_eq!(
+            reassembler.add_fragment(TEST_SOURCE, a2),
+            Some(vec![1, 2, 3])
+        );
+        assert_eq!(reassembler.pending_count(), 0);
+    }
+
+    #[esp32_test]
+    fn test_reassembler_middle_fragment_goes_to_closest_first() {
+        let mut reassembler = Reassembler::new(Duration::from_secs(5));
+
+        // A = 10..=12 and B = 13..=15, last fragments not yet seen
+        let a1 = Fragment::new(10, FLAG_FIRST_FRAGMENT | FLAG_MORE_FRAGMENTS, vec![1]);
+        let b1 = Fragment::new(13, FLAG_FIRST_FRAGMENT | FLAG_MORE_FRAGMENTS, vec![10]);
+        let b2 = Fragment::new(14, FLAG_MORE_FRAGMENTS, vec![20]);
+        let b3 = Fragment::new(15, 0, vec![30]);
+
+        assert_eq!(reassembler.add_fragment(TEST_SOURCE, a1), None);
+        assert_eq!(reassembler.add_fragment(TEST_SOURCE, b1), None);
+
+        // 14 is within range of both, but belongs to B
+        assert_eq!(reassembler.add_fragment(TEST_SOURCE, b2), None);
+        assert_eq!(
+            reassembler.add_fragment(TEST_SOURCE, b3),
+            Some(vec![10, 20, 30])
+        );
+        assert_eq!(reassembler.pending_count(), 1);
+    }
+
     #[esp32_test]
     fn test_reassembler_duplicate_fragment() {
         let mut reassembler = Reassembler::new(Duration::from_secs(5));
@@ -875,6 +1493,33 @@ mod tests {
         assert_eq!(reassembler.pending_count(), 0);
     }
 
+    #[esp32_test]
+    fn test_reassembler_oldest_pending_age() {
+        let mut reassembler = Reassembler::new(Duration::from_secs(5));
+        assert_eq!(reassembler.oldest_pending_age(), None);
+
+        let src1 = BleAddress::new([1, 0, 0, 0, 0, 0]);
+        let src2 = BleAddress::new([2, 0, 0, 0, 0, 0]);
+        for src in [src1, src2] {
+            reassembler.add_fragment(
+                src,
+                Fragment::new(0, FLAG_FIRST_FRAGMENT | FLAG_MORE_FRAGMENTS, vec![1]),
+            );
+        }
+
+        // Backdate one reassembly instead of sleeping
+        let backdate = Duration::from_millis(100);
+        let key = *reassembler.pending.keys().next().unwrap();
+        let pending = reassembler.pending.get_mut(&key).unwrap();
+        pending.started = pending.started.checked_sub(backdate).unwrap();
+
+        let age = reassembler.oldest_pending_age().unwrap();
+        assert!(age >= backdate && age < reassembler.timeout());
+
+        reassembler.clear();
+        assert_eq!(reassembler.oldest_pending_age(), None);
+    }
+
     #[esp32_test]
     fn test_reassembler_clear() {
         let mut reassembler = Reassembler::new(Duration::from_secs(5));
@@ -907,6 +1552,24 @@ mod tests {
         assert_eq!(result, Some(original));
     }
 
+    #[esp32_test]
+    fn test_reassembler_max_fragment_size() {
+        let mut reassembler = Reassembler::new(Duration::from_secs(5)).with_max_fragment_size(10);
+
+        let oversized = Fragment::new(0, FLAG_FIRST_FRAGMENT, vec![0; 9]);
+        assert_eq!(
+            reassembler.add_fragment_bytes(TEST_SOURCE, &oversized.to_bytes()),
+            Err(FragmentError::PayloadTooLarge { size: 11, max: 10 })
+        );
+        assert_eq!(reassembler.add_fragment(TEST_SOURCE, oversized), None);
+
+        let fitting = Fragment::new(1, FLAG_FIRST_FRAGMENT, vec![7; 8]);
+        assert_eq!(
+            reassembler.add_fragment_bytes(TEST_SOURCE, &fitting.to_bytes()),
+            Ok(Some(vec![7; 8]))
+        );
+    }
+
     #[esp32_test]
     fn test_multiple_concurrent_reassemblies() {
         let mut fragmenter = Fragmenter::new(5);
@@ -934,11 +1597,18 @@ mod tests {
         assert_eq!(reassembler.pending_count(), 0);
     }
 
-    #[esp32_test]
-    fn test_large_packet_fragmentation() {
-        let mut fragmenter = Fragmenter::new(20); // Typical BLE default MTU
-        let mut reassembler = Reassembler::new(Duration::from_secs(5));
+    /// Typical BLE default MTU with the default reassembly timeout.
+    fn setup_fragmenter() -> (Fragmenter, Reassembler) {
+        (
+            Fragmenter::new(20),
+            Reassembler::new(Duration::from_secs(5)),
+        )
+    }
 
+    #[esp32_test(setup = "setup_fragmenter")]
+    fn test_large_packet_fragmentation(
+        (mut fragmenter, mut reassembler): (Fragmenter, Reassembler),
+    ) {
         // 500 byte Reticulum packet
         let original: Vec<u8> = (0..=255).cycle().take(500).collect();
 
@@ -1006,4 +1676,91 @@ mod tests {
 
         assert_eq!(reassembler.pending_count(), 0);
     }
+
+    // ==================== Retransmit Tests ====================
+
+    #[esp32_test]
+    fn test_retransmit_dropped_by_default() {
+        let mut reassembler = Reassembler::new(Duration::from_secs(5));
+        assert_eq!(
+            reassembler.retransmit_policy(),
+            RetransmitPolicy::DropRetransmit
+        );
+
+        let first = Fragment::new(0, FLAG_FIRST_FRAGMENT | FLAG_MORE_FRAGMENTS, vec![1, 2]);
+        assert_eq!(reassembler.add_fragment(TEST_SOURCE, first), None);
+
+        // Retransmitted first fragment with different content is ignored
+        let retransmit = Fragment::new(0, FLAG_FIRST_FRAGMENT | FLAG_MORE_FRAGMENTS, vec![9, 9]);
+        assert_eq!(reassembler.add_fragment(TEST_SOURCE, retransmit), None);
+        assert_eq!(reassembler.stats().retransmits_detected, 1);
+
+        let last = Fragment::new(1, 0, vec![3, 4]);
+        assert_eq!(
+            reassembler.add_fragment(TEST_SOURCE, last),
+            Some(vec![1, 2, 3, 4])
+        );
+    }
+
+    #[esp32_test]
+    fn test_retransmit_restarts_reassembly() {
+        let mut reassembler = Reassembler::new(Duration::from_secs(5))
+            .with_retransmit_policy(RetransmitPolicy::RestartReassembly);
+
+        let first = Fragment::new(0, FLAG_FIRST_FRAGMENT | FLAG_MORE_FRAGMENTS, vec![1, 2]);
+        assert_eq!(reassembler.add_fragment(TEST_SOURCE, first), None);
+        let middle = Fragment::new(1, FLAG_MORE_FRAGMENTS, vec![3, 4]);
+        assert_eq!(reassembler.add_fragment(TEST_SOURCE, middle), None);
+
+        // Sender restarts the packet; stale middle fragment must be discarded
+        let retransmit = Fragment::new(0, FLAG_FIRST_FRAGMENT | FLAG_MORE_FRAGMENTS, vec![5, 6]);
+        assert_eq!(reassembler.add_fragment(TEST_SOURCE, retransmit), None);
+        assert_eq!(reassembler.pending_count(), 1);
+        assert_eq!(reassembler.stats().retransmits_detected, 1);
+
+        let last = Fragment::new(1, 0, vec![7, 8]);
+        assert_eq!(
+            reassembler.add_fragment(TEST_SOURCE, last),
+            Some(vec![5, 6, 7, 8])
+        );
+    }
+
+    // ==================== Serde Fixture Tests ====================
+
+    /// Fixture format for `tests/fixtures/ble_fragments.json`.
+    #[cfg(all(feature = "serde", not(target_os = "espidf")))]
+    #[derive(serde::Deserialize)]
+    struct FragmentFixture {
+        source: BleAddress,
+        packet: Vec<u8>,
+        fragments: Vec<Fragment>,
+    }
+
+    #[cfg(all(feature = "serde", not(target_os = "espidf")))]
+    #[esp32_test]
+    fn test_reassemble_json_fixture() {
+        let fixture: FragmentFixture =
+            serde_json::from_str(include_str!("../../tests/fixtures/ble_fragments.json"))
+                .expect("fixture should parse");
+
+        let mut reassembler = Reassembler::new(Duration::from_secs(5));
+        let mut result = None;
+        for fragment in fixture.fragments {
+            result = reassembler.add_fragment(fixture.source, fragment);
+        }
+        assert_eq!(result, Some(fixture.packet));
+    }
+
+    #[cfg(all(feature = "serde", not(target_os = "espidf")))]
+    #[esp32_test]
+    fn test_fragment_serde_roundtrip() {
+        let fragment = Fragment::new(7, FLAG_FIRST_FRAGMENT, vec![1, 2, 3]);
+        let json = serde_json::to_string(&fragment).unwrap();
+        assert_eq!(json, r#"{"sequence":7,"flags":1,"payload":[1,2,3]}"#);
+        assert_eq!(serde_json::from_str::<Fragment>(&json).unwrap(), fragment);
+
+        let err = FragmentError::MissingFragment(3);
+        let json = serde_json::to_string(&err).unwrap();
+        assert_eq!(serde_json::from_str::<FragmentError>(&json).unwrap(), err);
+    }
 }
@@ -5,4 +5,7 @@
 
 mod fragmentation;
 
-pub use fragmentation::{BleAddress, Fragment, FragmentError, Fragmenter, Reassembler};
+pub use fragmentation::{
+    validate_fragment_sequence, BleAddress, BleAddressType, Fragment, FragmentError, FragmentIter,
+    FragmentRef, Fragmenter, Reassembler, ReassemblerStats, RetransmitPolicy,
+};
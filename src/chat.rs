@@ -16,8 +16,8 @@
 //! ```text
 //! > list
 //! Known destinations:
-//!   [0] a1b2c3d4 (seen 30s ago)
-//!   [1] e5f6g7h8 (seen 5s ago)
+//!   [0] a1b2c3d4 (seen 30s ago) [ACTIVE]
+//!   [1] e5f6g7h8 (seen 5s ago) [none]
 //!
 //! > msg 0 Hello from node A!
 //! Sent to a1b2c3d4
@@ -29,6 +29,7 @@
 //! ```
 
 use log::info;
+use reticulum::destination::link::LinkStatus;
 use reticulum::destination::DestinationDesc;
 use reticulum::hash::AddressHash;
 use std::collections::HashMap;
@@ -38,6 +39,14 @@ use std::time::Instant;
 /// Prevents memory exhaustion from announce flooding.
 const MAX_KNOWN_DESTINATIONS: usize = 100;
 
+/// Destinations shown per page by the `list` command.
+pub const LIST_PAGE_SIZE: usize = 20;
+
+/// Longest message text accepted by `msg` and `broadcast`, in bytes.
+///
+/// The payload of a single encrypted link packet on a 500-byte MTU.
+pub const MAX_MESSAGE_TEXT_LEN: usize = 431;
+
 /// Number of characters to show from hash for display.
 /// 8 hex chars = 4 bytes = ~1 in 4 billion collision probability.
 const DISPLAY_HASH_CHARS: usize = 8;
@@ -53,6 +62,11 @@ fn format_hash_short(hash: &AddressHash) -> String {
 /// A known destination discovered via announce.
 #[derive(Clone)]
 pub struct KnownDestination {
+    /// Short ID shown by `list` and accepted by `msg`.
+    ///
+    /// Assigned in discovery order and never reused, so it stays valid
+    /// when other destinations are evicted.
+    pub id: usize,
     /// The destination's address hash.
     pub hash: AddressHash,
     /// Full destination descriptor (needed for creating links).
@@ -66,6 +80,7 @@ pub struct KnownDestination {
 impl std::fmt::Debug for KnownDestination {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("KnownDestination")
+            .field("id", &self.id)
             .field("hash", &self.hash)
             .field("last_seen", &self.last_seen)
             .field("display_name", &self.display_name)
@@ -74,10 +89,11 @@ impl std::fmt::Debug for KnownDestination {
 }
 
 impl KnownDestination {
-    /// Create a new known destination.
-    pub fn new(hash: AddressHash, descriptor: DestinationDesc) -> Self {
+    /// Create a new known destination with the given short ID.
+    pub fn new(id: usize, hash: AddressHash, descriptor: DestinationDesc) -> Self {
         let display_name = format_hash_short(&hash);
         Self {
+            id,
             hash,
             descriptor,
             last_seen: Instant::now(),
@@ -91,14 +107,25 @@ impl KnownDestination {
     }
 }
 
+/// Outcome of [`ChatState::add_destination`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AddResult {
+    /// Whether the destination was not known before.
+    pub was_new: bool,
+    /// Destination evicted to make room, if the cache was full.
+    pub evicted: Option<AddressHash>,
+}
+
 /// Manages known destinations and provides command parsing.
 pub struct ChatState {
     /// Our own identity hash (for display).
     pub identity_hash: String,
-    /// Known destinations indexed by short ID (0, 1, 2...).
+    /// Known destinations, sorted by short ID.
     destinations: Vec<KnownDestination>,
-    /// Map from address hash to index for quick lookup.
+    /// Map from address hash to position in `destinations`.
     hash_to_index: HashMap<AddressHash, usize>,
+    /// Short ID for the next new destination.
+    next_id: usize,
     /// When the node started.
     start_time: Instant,
 }
@@ -110,51 +137,65 @@ impl ChatState {
             identity_hash,
             destinations: Vec::new(),
             hash_to_index: HashMap::new(),
+            next_id: 0,
             start_time: Instant::now(),
         }
     }
 
     /// Add or update a known destination.
     ///
-    /// Returns `true` if this is a new destination, `false` if updated existing.
-    /// When the cache is full, evicts the least recently seen destination.
-    pub fn add_destination(&mut self, hash: AddressHash, descriptor: DestinationDesc) -> bool {
+    /// Reports whether this is a new destination (as opposed to a refresh of
+    /// an existing one) and which destination was evicted, if any. When the
+    /// cache is full, evicts the least recently seen destination.
+    pub fn add_destination(&mut self, hash: AddressHash, descriptor: DestinationDesc) -> AddResult {
         if let Some(&idx) = self.hash_to_index.get(&hash) {
             // Update existing - refresh last_seen time
             self.destinations[idx].last_seen = Instant::now();
-            false
+            AddResult {
+                was_new: false,
+                evicted: None,
+            }
         } else {
             // Need to add new entry
-            if self.destinations.len() >= MAX_KNOWN_DESTINATIONS {
+            let evicted = if self.destinations.len() >= MAX_KNOWN_DESTINATIONS {
                 // Cache full - evict oldest (LRU)
-                self.evict_oldest();
-            }
+                self.evict_oldest()
+            } else {
+                None
+            };
 
-            // Add new entry
+            // Add new entry; IDs only grow, so the list stays sorted
             let idx = self.destinations.len();
             self.destinations
-                .push(KnownDestination::new(hash, descriptor));
+                .push(KnownDestination::new(self.next_id, hash, descriptor));
+            self.next_id += 1;
             self.hash_to_index.insert(hash, idx);
             info!(
                 "[chat] New destination discovered: {}",
                 self.destinations[idx].display_name
             );
-            true
+            AddResult {
+                was_new: true,
+                evicted,
+            }
         }
     }
 
-    /// Evict the least recently seen destination using swap-remove.
+    /// Evict the least recently seen destination.
     ///
     /// Note: Finding the oldest entry is O(n) where n = destination count.
     /// This is acceptable for MAX_KNOWN_DESTINATIONS=100 on ESP32 (<1μs at 240MHz).
     /// A doubly-linked list would give O(1) but adds complexity. Consider if
     /// the limit increases significantly.
     ///
-    /// The swap-remove maintains O(1) for the actual removal by swapping the
-    /// oldest entry with the last, then popping from the end.
-    fn evict_oldest(&mut self) {
+    /// The removal shifts later entries down (also O(n)) rather than
+    /// swap-removing, so the list stays sorted by ID. Short IDs are unaffected
+    /// either way, since they're stored rather than derived from positions.
+    ///
+    /// Returns the evicted hash, or `None` if there was nothing to evict.
+    fn evict_oldest(&mut self) -> Option<AddressHash> {
         if self.destinations.is_empty() {
-            return;
+            return None;
         }
 
         // Find index of oldest entry (minimum last_seen)
@@ -166,42 +207,59 @@ impl ChatState {
             .map(|(i, _)| i)
             .unwrap();
 
-        let oldest_hash = self.destinations[oldest_idx].hash;
-        let last_idx = self.destinations.len() - 1;
+        let oldest_hash = self.destinations.remove(oldest_idx).hash;
+        self.hash_to_index.remove(&oldest_hash);
 
-        if oldest_idx != last_idx {
-            // Swap oldest with last, update the moved entry's index
-            let last_hash = self.destinations[last_idx].hash;
-            self.destinations.swap(oldest_idx, last_idx);
-            self.hash_to_index.insert(last_hash, oldest_idx);
+        // Entries after the removed one moved down by one
+        for (idx, dest) in self.destinations.iter().enumerate().skip(oldest_idx) {
+            self.hash_to_index.insert(dest.hash, idx);
         }
 
-        // Remove the last entry (which now contains the oldest data)
-        self.destinations.pop();
-        self.hash_to_index.remove(&oldest_hash);
-
         info!(
             "[chat] Evicted oldest destination: {}",
             format_hash_short(&oldest_hash)
         );
+        Some(oldest_hash)
     }
 
-    /// Get a destination by index or hash prefix.
+    /// Get a destination by short ID or hash prefix.
     pub fn get_destination(&self, id: &str) -> Option<&KnownDestination> {
-        // Try as index first
-        if let Ok(idx) = id.parse::<usize>() {
-            return self.destinations.get(idx);
+        // Try as short ID first
+        if let Ok(id) = id.parse::<usize>() {
+            return self
+                .destinations
+                .binary_search_by_key(&id, |d| d.id)
+                .ok()
+                .map(|idx| &self.destinations[idx]);
         }
 
         // Try as hash prefix
-        let id_lower = id.to_lowercase();
+        let id_lower = id.to_ascii_lowercase();
         self.destinations
             .iter()
             .find(|d| d.display_name.starts_with(&id_lower))
     }
 
-    /// Get all known destinations.
-    pub fn all_destinations(&self) -> &[KnownDestination] {
+    /// Get a destination by its full address hash.
+    pub fn destination_by_hash(&self, hash: &AddressHash) -> Option<&KnownDestination> {
+        self.destinations.get(*self.hash_to_index.get(hash)?)
+    }
+
+    /// Get all known destinations, most recently seen first.
+    ///
+    /// Gives commands like `broadcast` a deterministic order.
+    pub fn known_destinations_sorted_by_recent(&self) -> Vec<&KnownDestination> {
+        let mut destinations: Vec<_> = self.destinations.iter().collect();
+        destinations.sort_by_key(|d| std::cmp::Reverse(d.last_seen));
+        destinations
+    }
+
+    /// Get all known destinations in storage order (by short ID).
+    ///
+    /// This is discovery order, not recency; use
+    /// [`known_destinations_sorted_by_recent`](Self::known_destinations_sorted_by_recent)
+    /// when it matters.
+    pub fn all_destinations_unordered(&self) -> &[KnownDestination] {
         &self.destinations
     }
 
@@ -210,21 +268,55 @@ impl ChatState {
         self.start_time.elapsed().as_secs()
     }
 
-    /// Format the list of known destinations.
-    pub fn format_list(&self) -> String {
-        if self.destinations.is_empty() {
+    /// Format the first page of known destinations.
+    ///
+    /// `link_statuses` holds the state of any link to each destination, so the
+    /// list shows whether a message would be sent directly or queued.
+    pub fn format_list(&self, link_statuses: &HashMap<AddressHash, LinkStatus>) -> String {
+        self.format_list_page(link_statuses, 0, LIST_PAGE_SIZE)
+    }
+
+    /// Format one page of known destinations (`page` counts from 0).
+    ///
+    /// Entries show their short ID, so `msg <index>` works from any page.
+    /// When the list doesn't fit on one page a `(showing 21-40 of 85)` line
+    /// is appended.
+    pub fn format_list_page(
+        &self,
+        link_statuses: &HashMap<AddressHash, LinkStatus>,
+        page: usize,
+        page_size: usize,
+    ) -> String {
+        let total = self.destinations.len();
+        if total == 0 {
             return "No known destinations. Wait for announces...".to_string();
         }
 
+        let page_size = page_size.max(1);
+        let start = page.saturating_mul(page_size);
+        if start >= total {
+            return format!(
+                "No destinations on page {} ({} known, {} page(s))",
+                page + 1,
+                total,
+                total.div_ceil(page_size)
+            );
+        }
+        let end = (start + page_size).min(total);
+
         let mut output = String::from("Known destinations:\n");
-        for (idx, dest) in self.destinations.iter().enumerate() {
+        for dest in &self.destinations[start..end] {
             output.push_str(&format!(
-                "  [{}] {} (seen {}s ago)\n",
-                idx,
+                "  [{}] {} (seen {}s ago) [{}]\n",
+                dest.id,
                 dest.display_name,
-                dest.seconds_ago()
+                dest.seconds_ago(),
+                link_status_label(link_statuses.get(&dest.hash))
             ));
         }
+        if total > page_size {
+            output.push_str(&format!("(showing {}-{} of {})\n", start + 1, end, total));
+        }
         output
     }
 
@@ -239,6 +331,18 @@ impl ChatState {
     }
 }
 
+/// Short label for a link status in the destination list.
+fn link_status_label(status: Option<&LinkStatus>) -> &'static str {
+    match status {
+        None => "none",
+        Some(LinkStatus::Pending) => "PENDING",
+        Some(LinkStatus::Handshake) => "HANDSHAKE",
+        Some(LinkStatus::Active) => "ACTIVE",
+        Some(LinkStatus::Stale) => "STALE",
+        Some(LinkStatus::Closed) => "CLOSED",
+    }
+}
+
 /// Parsed chat command.
 #[derive(Debug)]
 pub enum ChatCommand {
@@ -246,8 +350,14 @@ pub enum ChatCommand {
     Message { dest_id: String, text: String },
     /// Broadcast to all known destinations.
     Broadcast { text: String },
-    /// List known destinations.
+    /// Establish a link without sending a message.
+    Connect { dest_id: String },
+    /// Close the link to a destination.
+    Disconnect { dest_id: String },
+    /// List the first page of known destinations.
     List,
+    /// List a page of known destinations (counting from 0; `list 2` is page 1).
+    ListPage { page: usize },
     /// Show node status.
     Status,
     /// Show help.
@@ -268,7 +378,9 @@ impl ChatCommand {
         let cmd = parts.next().unwrap_or("");
         let args = parts.next().unwrap_or("").trim();
 
-        match cmd.to_lowercase().as_str() {
+        // Command names are ASCII; `to_ascii_lowercase` skips the Unicode case
+        // tables that `to_lowercase` would link into the firmware
+        match cmd.to_ascii_lowercase().as_str() {
             "msg" | "m" | "send" => {
                 let mut msg_parts = args.splitn(2, ' ');
                 let dest_id = msg_parts.next().unwrap_or("").to_string();
@@ -288,7 +400,26 @@ impl ChatCommand {
                     }
                 }
             }
-            "list" | "ls" | "l" => ChatCommand::List,
+            "connect" | "c" => match args.split_whitespace().next() {
+                Some(dest_id) => ChatCommand::Connect {
+                    dest_id: dest_id.to_string(),
+                },
+                None => ChatCommand::Unknown("Usage: connect <dest_id>".to_string()),
+            },
+            "disconnect" | "dc" => match args.split_whitespace().next() {
+                Some(dest_id) => ChatCommand::Disconnect {
+                    dest_id: dest_id.to_string(),
+                },
+                None => ChatCommand::Unknown("Usage: disconnect <dest_id>".to_string()),
+            },
+            "list" | "ls" | "l" => match args.split_whitespace().next() {
+                None => ChatCommand::List,
+                // Pages are numbered from 1 for the user
+                Some(page) => match page.parse::<usize>() {
+                    Ok(page) if page > 0 => ChatCommand::ListPage { page: page - 1 },
+                    _ => ChatCommand::Unknown("Usage: list [page]".to_string()),
+                },
+            },
             "status" | "stat" | "s" => ChatCommand::Status,
             "help" | "h" | "?" => ChatCommand::Help,
             _ => ChatCommand::Unknown(format!(
@@ -299,21 +430,63 @@ impl ChatCommand {
     }
 }
 
+/// Reason outgoing message text was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageTextError {
+    /// Text is longer than [`MAX_MESSAGE_TEXT_LEN`] bytes.
+    TooLong { len: usize, max: usize },
+    /// Text contains a control character other than newline.
+    ControlCharacter,
+}
+
+impl std::fmt::Display for MessageTextError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TooLong { len, max } => {
+                write!(f, "message too long: {} bytes (max {})", len, max)
+            }
+            Self::ControlCharacter => write!(f, "message contains control characters"),
+        }
+    }
+}
+
+impl std::error::Error for MessageTextError {}
+
+/// Check message text before it is sent or queued.
+///
+/// Control characters (other than newline) would corrupt the receiver's
+/// terminal when [`format_incoming_message`] prints them.
+pub fn validate_message_text(text: &str) -> Result<(), MessageTextError> {
+    if text.len() > MAX_MESSAGE_TEXT_LEN {
+        return Err(MessageTextError::TooLong {
+            len: text.len(),
+            max: MAX_MESSAGE_TEXT_LEN,
+        });
+    }
+    if text.chars().any(|c| c.is_control() && c != '\n') {
+        return Err(MessageTextError::ControlCharacter);
+    }
+    Ok(())
+}
+
 /// Help text for available commands.
 pub const HELP_TEXT: &str = r#"
 Available commands:
   msg <id> <text>    Send message to destination (by index or hash prefix)
   broadcast <text>   Send message to all known destinations
-  list               Show known destinations
+  connect <id>       Establish a link without sending a message
+  disconnect <id>    Close the link to a destination
+  list [page]        Show known destinations (20 per page)
   status             Show node status
   help               Show this help
 
-Shortcuts: m=msg, b=broadcast, l=list, s=status, h=help
+Shortcuts: m=msg, b=broadcast, c=connect, dc=disconnect, l=list, s=status, h=help
 
 Examples:
   msg 0 Hello!       Send "Hello!" to destination [0]
   msg a1b2 Hi        Send "Hi" to destination starting with "a1b2"
   broadcast Anyone?  Send to all known destinations
+  connect 0          Pre-warm a link to destination [0]
 "#;
 
 /// Format an incoming message for display.
@@ -382,6 +555,26 @@ mod tests {
         }
     }
 
+    #[esp32_test]
+    fn test_parse_connect_disconnect() {
+        match ChatCommand::parse("connect a1b2") {
+            ChatCommand::Connect { dest_id } => assert_eq!(dest_id, "a1b2"),
+            _ => panic!("Expected Connect command"),
+        }
+        match ChatCommand::parse("dc 0") {
+            ChatCommand::Disconnect { dest_id } => assert_eq!(dest_id, "0"),
+            _ => panic!("Expected Disconnect command"),
+        }
+        assert!(matches!(
+            ChatCommand::parse("connect"),
+            ChatCommand::Unknown(_)
+        ));
+        assert!(matches!(
+            ChatCommand::parse("disconnect"),
+            ChatCommand::Unknown(_)
+        ));
+    }
+
     #[esp32_test]
     fn test_parse_list() {
         assert!(matches!(ChatCommand::parse("list"), ChatCommand::List));
@@ -389,6 +582,26 @@ mod tests {
         assert!(matches!(ChatCommand::parse("l"), ChatCommand::List));
     }
 
+    #[esp32_test]
+    fn test_parse_list_page() {
+        assert!(matches!(
+            ChatCommand::parse("list 2"),
+            ChatCommand::ListPage { page: 1 }
+        ));
+        assert!(matches!(
+            ChatCommand::parse("l 1"),
+            ChatCommand::ListPage { page: 0 }
+        ));
+        assert!(matches!(
+            ChatCommand::parse("list 0"),
+            ChatCommand::Unknown(_)
+        ));
+        assert!(matches!(
+            ChatCommand::parse("list two"),
+            ChatCommand::Unknown(_)
+        ));
+    }
+
     #[esp32_test]
     fn test_parse_status() {
         assert!(matches!(ChatCommand::parse("status"), ChatCommand::Status));
@@ -401,6 +614,39 @@ mod tests {
         assert!(matches!(ChatCommand::parse("?"), ChatCommand::Help));
     }
 
+    #[esp32_test]
+    fn test_validate_message_text() {
+        assert!(validate_message_text("Hello, wörld!").is_ok());
+        assert!(validate_message_text("line one\nline two").is_ok());
+        assert!(validate_message_text(&"a".repeat(MAX_MESSAGE_TEXT_LEN)).is_ok());
+
+        assert_eq!(
+            validate_message_text(&"a".repeat(MAX_MESSAGE_TEXT_LEN + 1)),
+            Err(MessageTextError::TooLong {
+                len: MAX_MESSAGE_TEXT_LEN + 1,
+                max: MAX_MESSAGE_TEXT_LEN
+            })
+        );
+        assert_eq!(
+            validate_message_text("\x1b[2Jgotcha"),
+            Err(MessageTextError::ControlCharacter)
+        );
+        assert_eq!(
+            validate_message_text("tab\there"),
+            Err(MessageTextError::ControlCharacter)
+        );
+    }
+
+    #[esp32_test]
+    fn test_parse_case_insensitive() {
+        assert!(matches!(ChatCommand::parse("LIST"), ChatCommand::List));
+        assert!(matches!(ChatCommand::parse("Help"), ChatCommand::Help));
+        assert!(matches!(
+            ChatCommand::parse("BC hello"),
+            ChatCommand::Broadcast { .. }
+        ));
+    }
+
     #[esp32_test]
     fn test_parse_unknown() {
         assert!(matches!(ChatCommand::parse("foo"), ChatCommand::Unknown(_)));
@@ -424,8 +670,10 @@ mod tests {
     #[esp32_test]
     fn test_chat_state_empty() {
         let state = ChatState::new("test_identity".to_string());
-        assert_eq!(state.all_destinations().len(), 0);
-        assert!(state.format_list().contains("No known destinations"));
+        assert_eq!(state.all_destinations_unordered().len(), 0);
+        assert!(state
+            .format_list(&HashMap::new())
+            .contains("No known destinations"));
         assert!(state.format_status().contains("test_identity"));
     }
 
@@ -437,11 +685,51 @@ mod tests {
         let desc = test_descriptor(1);
         state.add_destination(hash, desc);
 
-        assert_eq!(state.all_destinations().len(), 1);
-        let list = state.format_list();
+        assert_eq!(state.all_destinations_unordered().len(), 1);
+        let list = state.format_list(&HashMap::new());
         assert!(list.contains("[0]"));
         // Hash should start with "01" (first byte is 1)
         assert!(list.contains("01"));
+        assert!(list.contains("[none]"));
+    }
+
+    #[esp32_test]
+    fn test_format_list_link_status() {
+        let mut state = ChatState::new("test".to_string());
+        state.add_destination(test_hash(1), test_descriptor(1));
+        state.add_destination(test_hash(2), test_descriptor(2));
+        state.add_destination(test_hash(3), test_descriptor(3));
+
+        let mut statuses = HashMap::new();
+        statuses.insert(test_hash(1), LinkStatus::Active);
+        statuses.insert(test_hash(2), LinkStatus::Pending);
+
+        let list = state.format_list(&statuses);
+        let lines: Vec<_> = list.lines().skip(1).collect();
+        assert!(lines[0].starts_with("  [0]") && lines[0].ends_with("[ACTIVE]"));
+        assert!(lines[1].starts_with("  [1]") && lines[1].ends_with("[PENDING]"));
+        assert!(lines[2].starts_with("  [2]") && lines[2].ends_with("[none]"));
+    }
+
+    #[esp32_test]
+    fn test_format_list_pagination() {
+        let mut state = ChatState::new("test".to_string());
+        for i in 0..45 {
+            state.add_destination(test_hash(i), test_descriptor(i));
+        }
+        let statuses = HashMap::new();
+
+        let first = state.format_list(&statuses);
+        assert_eq!(first.lines().count(), 1 + LIST_PAGE_SIZE + 1);
+        assert!(first.ends_with("(showing 1-20 of 45)\n"));
+
+        let last = state.format_list_page(&statuses, 2, LIST_PAGE_SIZE);
+        assert!(last.contains("  [40]") && last.contains("  [44]"));
+        assert!(!last.contains("  [39]"));
+        assert!(last.ends_with("(showing 41-45 of 45)\n"));
+
+        let past_end = state.format_list_page(&statuses, 3, LIST_PAGE_SIZE);
+        assert!(past_end.contains("No destinations on page 4 (45 known, 3 page(s))"));
     }
 
     #[esp32_test]
@@ -479,6 +767,24 @@ mod tests {
         assert!(state.get_destination("ff").is_none());
     }
 
+    #[esp32_test]
+    fn test_destination_by_hash() {
+        let mut state = ChatState::new("test".to_string());
+        assert!(state.destination_by_hash(&test_hash(1)).is_none());
+
+        for i in 0..MAX_KNOWN_DESTINATIONS {
+            state.add_destination(test_hash(i as u8), test_descriptor(i as u8));
+        }
+        state.add_destination(test_hash(255), test_descriptor(255));
+
+        // Lookups stay correct after eviction moves entries around
+        assert!(state.destination_by_hash(&test_hash(0)).is_none());
+        for i in (1..MAX_KNOWN_DESTINATIONS).chain([255]) {
+            let hash = test_hash(i as u8);
+            assert_eq!(state.destination_by_hash(&hash).unwrap().hash, hash);
+        }
+    }
+
     #[esp32_test]
     fn test_chat_state_lru_eviction() {
         let mut state = ChatState::new("test".to_string());
@@ -487,36 +793,104 @@ mod tests {
         for i in 0..MAX_KNOWN_DESTINATIONS {
             state.add_destination(test_hash(i as u8), test_descriptor(i as u8));
         }
-        assert_eq!(state.all_destinations().len(), MAX_KNOWN_DESTINATIONS);
+        assert_eq!(
+            state.all_destinations_unordered().len(),
+            MAX_KNOWN_DESTINATIONS
+        );
 
         // Add one more - should evict the oldest
         let new_hash = test_hash(255);
-        state.add_destination(new_hash, test_descriptor(255));
+        let result = state.add_destination(new_hash, test_descriptor(255));
+        assert!(result.was_new);
+        assert_eq!(result.evicted, Some(test_hash(0)));
 
         // Should still be at max capacity
-        assert_eq!(state.all_destinations().len(), MAX_KNOWN_DESTINATIONS);
+        assert_eq!(
+            state.all_destinations_unordered().len(),
+            MAX_KNOWN_DESTINATIONS
+        );
 
         // The new entry should exist
-        assert!(state.all_destinations().iter().any(|d| d.hash == new_hash));
+        assert!(state
+            .all_destinations_unordered()
+            .iter()
+            .any(|d| d.hash == new_hash));
 
         // The first entry (hash 0) should have been evicted
         let first_hash = test_hash(0);
         assert!(!state
-            .all_destinations()
+            .all_destinations_unordered()
             .iter()
             .any(|d| d.hash == first_hash));
     }
 
+    #[esp32_test]
+    fn test_get_destination_by_index_after_eviction() {
+        let mut state = ChatState::new("test".to_string());
+        for i in 0..MAX_KNOWN_DESTINATIONS {
+            state.add_destination(test_hash(i as u8), test_descriptor(i as u8));
+        }
+        let fifth = state.get_destination("5").unwrap().hash;
+        let last_id = (MAX_KNOWN_DESTINATIONS - 1).to_string();
+        let last = state.get_destination(&last_id).unwrap().hash;
+
+        // Evicts the entry with ID 0
+        state.add_destination(test_hash(255), test_descriptor(255));
+
+        assert!(state.get_destination("0").is_none());
+        assert_eq!(state.get_destination("5").unwrap().hash, fifth);
+        assert_eq!(state.get_destination(&last_id).unwrap().hash, last);
+        let new_id = MAX_KNOWN_DESTINATIONS.to_string();
+        assert_eq!(state.get_destination(&new_id).unwrap().hash, test_hash(255));
+
+        // The list shows the same IDs that msg accepts
+        let list = state.format_list_page(&HashMap::new(), 0, MAX_KNOWN_DESTINATIONS);
+        assert!(!list.contains("  [0] "));
+        assert!(list.contains("  [5] "));
+        assert!(list.contains(&format!("  [{}] ", new_id)));
+    }
+
     #[esp32_test]
     fn test_chat_state_update_existing() {
         let mut state = ChatState::new("test".to_string());
 
         let hash = test_hash(1);
-        state.add_destination(hash, test_descriptor(1));
+        let result = state.add_destination(hash, test_descriptor(1));
+        assert_eq!(
+            result,
+            AddResult {
+                was_new: true,
+                evicted: None
+            }
+        );
 
         // Add again with same hash - should update, not duplicate
-        state.add_destination(hash, test_descriptor(1));
+        let result = state.add_destination(hash, test_descriptor(1));
+        assert!(!result.was_new);
+        assert_eq!(result.evicted, None);
+
+        assert_eq!(state.all_destinations_unordered().len(), 1);
+    }
 
-        assert_eq!(state.all_destinations().len(), 1);
+    #[esp32_test]
+    fn test_known_destinations_sorted_by_recent() {
+        let mut state = ChatState::new("test".to_string());
+        for i in 1..=3 {
+            state.add_destination(test_hash(i), test_descriptor(i));
+        }
+
+        // Offsets from a common base (adding avoids Instant underflow shortly
+        // after boot on ESP32)
+        let base = Instant::now();
+        for (dest, offset) in state.destinations.iter_mut().zip([0, 20, 10]) {
+            dest.last_seen = base + std::time::Duration::from_secs(offset);
+        }
+
+        let order: Vec<_> = state
+            .known_destinations_sorted_by_recent()
+            .iter()
+            .map(|d| d.hash)
+            .collect();
+        assert_eq!(order, vec![test_hash(2), test_hash(3), test_hash(1)]);
     }
 }
@@ -25,8 +25,8 @@ mod ble_service;
 
 // Re-export WiFi configuration types (platform-independent)
 pub use wifi::{
-    ConfigCommand, ConfigError, WifiConfig, WifiStatus, MAX_PASSWORD_LEN, MAX_SSID_LEN,
-    MIN_PASSWORD_LEN,
+    ConfigCommand, ConfigError, EapConfig, EapMethod, WifiConfig, WifiConfigList, WifiStatus,
+    WpaType, MAX_PASSWORD_LEN, MAX_SSID_LEN, MAX_WIFI_NETWORKS, MIN_PASSWORD_LEN,
 };
 
 // Re-export BLE service (ESP32 only)
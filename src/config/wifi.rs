@@ -17,6 +17,8 @@
 
 use std::borrow::Cow;
 use std::fmt;
+use std::net::IpAddr;
+use unicode_normalization::UnicodeNormalization;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
 /// Maximum SSID length per IEEE 802.11 standard.
@@ -28,35 +30,141 @@ pub const MAX_PASSWORD_LEN: usize = 64;
 /// Minimum password length for WPA2.
 pub const MIN_PASSWORD_LEN: usize = 8;
 
+/// Maximum number of networks in a [`WifiConfigList`].
+pub const MAX_WIFI_NETWORKS: usize = 8;
+
+/// Current [`WifiConfig::to_bytes`] format version.
+///
+/// Version 2 appended an XOR checksum byte to version 1.
+const FORMAT_VERSION: u8 = 2;
+
+/// High bit marking the first byte as a format version.
+///
+/// Legacy configs start with the SSID length (at most [`MAX_SSID_LEN`]), so
+/// the bit is never set there.
+const FORMAT_VERSION_FLAG: u8 = 0x80;
+
+/// EAP method used to authenticate on a WPA2-Enterprise network.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EapMethod {
+    /// Protected EAP with MSCHAPv2 inside the TLS tunnel (common on corporate networks).
+    #[default]
+    Peap,
+    /// EAP-TTLS with MSCHAPv2 as the inner method (common on university networks).
+    Ttls,
+}
+
+/// WPA2-Enterprise (802.1X) credentials.
+///
+/// The password is automatically zeroed from memory when this struct is dropped.
+#[derive(Debug, Clone, PartialEq, Eq, Zeroize, ZeroizeOnDrop)]
+pub struct EapConfig {
+    /// Outer identity, sent in the clear before the TLS tunnel is up
+    /// (often `anonymous@realm`).
+    pub identity: String,
+    /// Username, sent inside the TLS tunnel.
+    pub username: String,
+    /// Password, sent inside the TLS tunnel.
+    pub password: String,
+    /// PEM CA certificate used to verify the authentication server.
+    /// `None` accepts any server certificate.
+    pub ca_cert_pem: Option<String>,
+    /// EAP method.
+    #[zeroize(skip)]
+    pub eap_method: EapMethod,
+}
+
+impl EapConfig {
+    /// Create enterprise credentials without server certificate verification.
+    pub fn new(
+        eap_method: EapMethod,
+        identity: impl Into<String>,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Self {
+        Self {
+            identity: identity.into(),
+            username: username.into(),
+            password: password.into(),
+            ca_cert_pem: None,
+            eap_method,
+        }
+    }
+
+    /// Verify the authentication server against a PEM CA certificate.
+    pub fn with_ca_cert(mut self, ca_cert_pem: impl Into<String>) -> Self {
+        self.ca_cert_pem = Some(ca_cert_pem.into());
+        self
+    }
+
+    /// Validate the credentials.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.identity.is_empty() {
+            return Err(ConfigError::EapIdentityEmpty);
+        }
+        if self.username.is_empty() {
+            return Err(ConfigError::EapUsernameEmpty);
+        }
+        Ok(())
+    }
+}
+
 /// WiFi credentials for connecting to an access point.
 ///
 /// The password is automatically zeroed from memory when this struct is dropped.
 #[derive(Debug, Clone, PartialEq, Eq, Zeroize, ZeroizeOnDrop)]
 pub struct WifiConfig {
-    /// Network SSID (1-32 bytes).
+    /// Network SSID (1-32 bytes), NFC-normalized by the constructors.
     pub ssid: String,
-    /// Network password (8-64 bytes for WPA2, empty for open networks).
+    /// Network password (8-64 bytes for WPA2, empty for open and enterprise networks).
     pub password: String,
+    /// Whether the network hides its SSID (requires active probing).
+    pub hidden_ssid: bool,
+    /// WPA2-Enterprise credentials, used instead of `password` when set.
+    pub enterprise: Option<EapConfig>,
 }
 
 impl WifiConfig {
     /// Create a new WiFi configuration.
     ///
+    /// The SSID is normalized to Unicode NFC, so the stored SSID may differ
+    /// from the input (e.g. a decomposed "café" becomes the precomposed form).
+    ///
     /// Returns an error if SSID or password are invalid.
     pub fn new(ssid: impl Into<String>, password: impl Into<String>) -> Result<Self, ConfigError> {
         let config = Self {
-            ssid: ssid.into(),
+            ssid: normalize_ssid(ssid.into()),
             password: password.into(),
+            hidden_ssid: false,
+            enterprise: None,
         };
         config.validate()?;
         Ok(config)
     }
 
     /// Create a configuration for an open network (no password).
+    ///
+    /// The SSID is normalized like in [`new`](Self::new).
     pub fn open(ssid: impl Into<String>) -> Result<Self, ConfigError> {
         let config = Self {
-            ssid: ssid.into(),
+            ssid: normalize_ssid(ssid.into()),
+            password: String::new(),
+            hidden_ssid: false,
+            enterprise: None,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Create a configuration for a WPA2-Enterprise network.
+    ///
+    /// The SSID is normalized like in [`new`](Self::new).
+    pub fn new_enterprise(ssid: impl Into<String>, eap: EapConfig) -> Result<Self, ConfigError> {
+        let config = Self {
+            ssid: normalize_ssid(ssid.into()),
             password: String::new(),
+            hidden_ssid: false,
+            enterprise: Some(eap),
         };
         config.validate()?;
         Ok(config)
@@ -89,30 +197,95 @@ impl WifiConfig {
             });
         }
 
+        if let Some(eap) = &self.enterprise {
+            eap.validate()?;
+        }
+
         Ok(())
     }
 
-    /// Check if this is an open network (no password).
+    /// Mark the network as hidden (or not).
+    ///
+    /// Hidden networks don't broadcast their SSID, so the station must send
+    /// probe requests containing it instead of scanning passively.
+    pub fn with_hidden(mut self, hidden: bool) -> Self {
+        self.hidden_ssid = hidden;
+        self
+    }
+
+    /// Check if this is an open network (no password or enterprise credentials).
     pub fn is_open(&self) -> bool {
-        self.password.is_empty()
+        self.password.is_empty() && self.enterprise.is_none()
+    }
+
+    /// Check if this is a WPA2-Enterprise network.
+    pub fn is_enterprise(&self) -> bool {
+        self.enterprise.is_some()
     }
 
     /// Serialize to bytes for NVS storage.
     ///
-    /// Format: `[ssid_len:1][ssid:N][password_len:1][password:M]`
+    /// Format: `[version:1][ssid_len:1][ssid:N][password_len:1][password:M][hidden:1][checksum:1]`
+    ///
+    /// The version byte has its high bit set so it
+    /// can never be mistaken for the SSID length that starts the legacy
+    /// unversioned format. The checksum is the XOR of all preceding bytes,
+    /// catching NVS corruption after a hard reset during a write.
+    ///
+    /// Enterprise credentials are not part of the format; check
+    /// [`is_enterprise`](Self::is_enterprise) before persisting.
     pub fn to_bytes(&self) -> Vec<u8> {
-        let mut bytes = Vec::with_capacity(2 + self.ssid.len() + self.password.len());
+        let mut bytes = Vec::with_capacity(5 + self.ssid.len() + self.password.len());
+        bytes.push(FORMAT_VERSION_FLAG | FORMAT_VERSION);
         bytes.push(self.ssid.len() as u8);
         bytes.extend_from_slice(self.ssid.as_bytes());
         bytes.push(self.password.len() as u8);
         bytes.extend_from_slice(self.password.as_bytes());
+        bytes.push(self.hidden_ssid as u8);
+        bytes.push(xor_checksum(&bytes));
         bytes
     }
 
     /// Deserialize from bytes.
+    ///
+    /// Accepts the current versioned format, version 1 (no checksum), and the
+    /// legacy unversioned one (first byte is the SSID length, at most
+    /// [`MAX_SSID_LEN`]). Returns [`ConfigError::ChecksumMismatch`] if the
+    /// checksum doesn't match, and [`ConfigError::UnsupportedVersion`] for a
+    /// version written by newer firmware.
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, ConfigError> {
-        if bytes.is_empty() {
+        let Some(&first) = bytes.first() else {
             return Err(ConfigError::InvalidFormat("empty data".into()));
+        };
+
+        if first & FORMAT_VERSION_FLAG == 0 {
+            return Self::parse_fields(bytes, false);
+        }
+
+        match first & !FORMAT_VERSION_FLAG {
+            1 => Self::parse_fields(&bytes[1..], true),
+            2 => {
+                let Some((&found, data)) = bytes.split_last() else {
+                    unreachable!("bytes has at least the version byte");
+                };
+                let expected = xor_checksum(data);
+                if found != expected {
+                    return Err(ConfigError::ChecksumMismatch { expected, found });
+                }
+                Self::parse_fields(&data[1..], true)
+            }
+            version => Err(ConfigError::UnsupportedVersion(version)),
+        }
+    }
+
+    /// Parse `[ssid_len][ssid][password_len][password][hidden]`.
+    ///
+    /// The trailing `hidden` byte is optional in the legacy format: configs
+    /// stored before it was added end after the password and load with
+    /// `hidden_ssid = false`.
+    fn parse_fields(bytes: &[u8], hidden_required: bool) -> Result<Self, ConfigError> {
+        if bytes.is_empty() {
+            return Err(ConfigError::InvalidFormat("truncated data".into()));
         }
 
         let ssid_len = bytes[0] as usize;
@@ -152,7 +325,163 @@ impl WifiConfig {
             String::from_utf8(bytes[password_start..password_start + password_len].to_vec())
                 .map_err(|_| ConfigError::InvalidFormat("invalid password UTF-8".into()))?;
 
-        Self::new(ssid, password)
+        let hidden_ssid = match bytes.get(password_start + password_len) {
+            None if hidden_required => {
+                return Err(ConfigError::InvalidFormat("missing hidden flag".into()))
+            }
+            None | Some(0) => false,
+            Some(1) => true,
+            Some(_) => return Err(ConfigError::InvalidFormat("invalid hidden flag".into())),
+        };
+
+        Ok(Self::new(ssid, password)?.with_hidden(hidden_ssid))
+    }
+}
+
+/// XOR of all bytes, the checksum appended by [`WifiConfig::to_bytes`].
+fn xor_checksum(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0u8, |acc, b| acc ^ b)
+}
+
+/// Known WiFi networks, each with a connection priority (higher is preferred).
+///
+/// Lets a device that moves between sites, or sits in range of two known
+/// networks, pick the preferred one from a scan. Holds at most
+/// [`MAX_WIFI_NETWORKS`] networks, one per SSID.
+///
+/// # Example
+///
+/// ```
+/// use reticulum_rs_esp32::config::{WifiConfig, WifiConfigList};
+///
+/// let mut networks = WifiConfigList::new();
+/// networks.add_network(1, WifiConfig::new("Office", "password123").unwrap()).unwrap();
+/// networks.add_network(5, WifiConfig::new("Home", "password456").unwrap()).unwrap();
+///
+/// let best = networks.best_available(&["Office", "Home", "Cafe"]).unwrap();
+/// assert_eq!(best.ssid, "Home");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WifiConfigList {
+    networks: Vec<(u8, WifiConfig)>,
+}
+
+impl WifiConfigList {
+    /// Create an empty list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a network, replacing any existing entry with the same SSID.
+    ///
+    /// Returns [`ConfigError::TooManyNetworks`] if the list is full.
+    pub fn add_network(&mut self, priority: u8, config: WifiConfig) -> Result<(), ConfigError> {
+        if let Some(entry) = self
+            .networks
+            .iter_mut()
+            .find(|(_, c)| c.ssid == config.ssid)
+        {
+            *entry = (priority, config);
+            return Ok(());
+        }
+        if self.networks.len() >= MAX_WIFI_NETWORKS {
+            return Err(ConfigError::TooManyNetworks {
+                max: MAX_WIFI_NETWORKS,
+            });
+        }
+        self.networks.push((priority, config));
+        Ok(())
+    }
+
+    /// Remove the network with the given SSID, returning it if present.
+    pub fn remove_network(&mut self, ssid: &str) -> Option<WifiConfig> {
+        let index = self.networks.iter().position(|(_, c)| c.ssid == ssid)?;
+        Some(self.networks.remove(index).1)
+    }
+
+    /// Iterate over `(priority, config)` pairs in insertion order.
+    pub fn list_networks(&self) -> impl Iterator<Item = (u8, &WifiConfig)> {
+        self.networks
+            .iter()
+            .map(|(priority, config)| (*priority, config))
+    }
+
+    /// Get the highest-priority network whose SSID appears in a scan.
+    ///
+    /// Ties go to the network added first. Scanned SSIDs are compared as
+    /// given, so pass them through the same NFC normalization as stored ones
+    /// if the scan may report decomposed characters.
+    pub fn best_available(&self, ssids: &[&str]) -> Option<&WifiConfig> {
+        self.networks
+            .iter()
+            .filter(|(_, config)| ssids.contains(&config.ssid.as_str()))
+            .min_by_key(|(priority, _)| std::cmp::Reverse(*priority))
+            .map(|(_, config)| config)
+    }
+
+    /// Get the number of networks.
+    pub fn len(&self) -> usize {
+        self.networks.len()
+    }
+
+    /// Check if the list is empty.
+    pub fn is_empty(&self) -> bool {
+        self.networks.is_empty()
+    }
+}
+
+/// Normalize an SSID to Unicode NFC, reusing the input if it already is.
+fn normalize_ssid(ssid: String) -> String {
+    if unicode_normalization::is_nfc(&ssid) {
+        ssid
+    } else {
+        ssid.nfc().collect()
+    }
+}
+
+/// Security mode of the connected network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WpaType {
+    /// No encryption.
+    Open,
+    /// WEP (broken; offers no real protection).
+    Wep,
+    /// WPA (TKIP).
+    Wpa,
+    /// WPA2 (CCMP).
+    Wpa2,
+    /// WPA3 (SAE).
+    Wpa3,
+}
+
+impl WpaType {
+    /// Name used in BLE status strings.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Open => "OPEN",
+            Self::Wep => "WEP",
+            Self::Wpa => "WPA",
+            Self::Wpa2 => "WPA2",
+            Self::Wpa3 => "WPA3",
+        }
+    }
+
+    /// Parse a name produced by [`as_str`](Self::as_str).
+    pub fn from_str_name(s: &str) -> Option<Self> {
+        match s {
+            "OPEN" => Some(Self::Open),
+            "WEP" => Some(Self::Wep),
+            "WPA" => Some(Self::Wpa),
+            "WPA2" => Some(Self::Wpa2),
+            "WPA3" => Some(Self::Wpa3),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for WpaType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
     }
 }
 
@@ -163,8 +492,14 @@ pub enum WifiStatus {
     Unconfigured,
     /// Attempting to connect to the network.
     Connecting,
-    /// Successfully connected with the given IP address.
-    Connected { ip: String },
+    /// Successfully connected with the given IP address and security mode.
+    ///
+    /// The security mode is `None` when unknown, e.g. in a status string from
+    /// firmware that predates it.
+    Connected {
+        ip: String,
+        wpa_type: Option<WpaType>,
+    },
     /// Connection failed with the given reason.
     Failed { reason: String },
 }
@@ -177,12 +512,21 @@ impl WifiStatus {
         match self {
             Self::Unconfigured => "unconfigured".into(),
             Self::Connecting => "connecting".into(),
-            Self::Connected { ip } => format!("connected:{}", ip).into(),
+            Self::Connected {
+                ip,
+                wpa_type: Some(wpa_type),
+            } => format!("connected:{}:{}", ip, wpa_type).into(),
+            Self::Connected { ip, wpa_type: None } => format!("connected:{}", ip).into(),
             Self::Failed { reason } => format!("failed:{}", reason).into(),
         }
     }
 
     /// Parse status from a BLE string.
+    ///
+    /// The security mode is the last `:`-separated field of a `connected`
+    /// status, so IPv6 addresses parse correctly. The legacy
+    /// `connected:<ip>` form without it is still accepted, with an unknown
+    /// security mode.
     pub fn from_ble_string(s: &str) -> Result<Self, ConfigError> {
         if s == "unconfigured" {
             return Ok(Self::Unconfigured);
@@ -190,8 +534,23 @@ impl WifiStatus {
         if s == "connecting" {
             return Ok(Self::Connecting);
         }
-        if let Some(ip) = s.strip_prefix("connected:") {
-            return Ok(Self::Connected { ip: ip.to_string() });
+        if let Some(rest) = s.strip_prefix("connected:") {
+            if rest.parse::<IpAddr>().is_ok() {
+                return Ok(Self::Connected {
+                    ip: rest.to_string(),
+                    wpa_type: None,
+                });
+            }
+            let (ip, wpa_type) = rest
+                .rsplit_once(':')
+                .and_then(|(ip, name)| Some((ip, WpaType::from_str_name(name)?)))
+                .ok_or_else(|| {
+                    ConfigError::InvalidFormat(format!("missing security mode: {}", s))
+                })?;
+            return Ok(Self::Connected {
+                ip: ip.to_string(),
+                wpa_type: Some(wpa_type),
+            });
         }
         if let Some(reason) = s.strip_prefix("failed:") {
             return Ok(Self::Failed {
@@ -234,7 +593,7 @@ impl std::str::FromStr for ConfigCommand {
     type Err = ConfigError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.trim().to_lowercase().as_str() {
+        match s.trim().to_ascii_lowercase().as_str() {
             "connect" => Ok(Self::Connect),
             "disconnect" => Ok(Self::Disconnect),
             "clear" => Ok(Self::Clear),
@@ -264,6 +623,16 @@ pub enum ConfigError {
     InvalidFormat(String),
     /// Unknown command string.
     UnknownCommand(String),
+    /// Stored config uses a format version this firmware doesn't understand.
+    UnsupportedVersion(u8),
+    /// A [`WifiConfigList`] already holds the maximum number of networks.
+    TooManyNetworks { max: usize },
+    /// Stored config is corrupted: its checksum byte doesn't match.
+    ChecksumMismatch { expected: u8, found: u8 },
+    /// WPA2-Enterprise outer identity is empty.
+    EapIdentityEmpty,
+    /// WPA2-Enterprise username is empty.
+    EapUsernameEmpty,
 }
 
 impl fmt::Display for ConfigError {
@@ -281,6 +650,15 @@ impl fmt::Display for ConfigError {
             }
             Self::InvalidFormat(msg) => write!(f, "invalid format: {}", msg),
             Self::UnknownCommand(cmd) => write!(f, "unknown command: {}", cmd),
+            Self::UnsupportedVersion(v) => write!(f, "unsupported config format version: {}", v),
+            Self::TooManyNetworks { max } => write!(f, "too many networks (max {})", max),
+            Self::ChecksumMismatch { expected, found } => write!(
+                f,
+                "config checksum mismatch: expected {:#04x}, found {:#04x}",
+                expected, found
+            ),
+            Self::EapIdentityEmpty => write!(f, "EAP identity cannot be empty"),
+            Self::EapUsernameEmpty => write!(f, "EAP username cannot be empty"),
         }
     }
 }
@@ -307,9 +685,38 @@ mod tests {
     fn test_open_network() {
         let config = WifiConfig::open("OpenNetwork").unwrap();
         assert!(config.is_open());
+        assert!(!config.hidden_ssid);
         assert!(config.validate().is_ok());
     }
 
+    #[esp32_test]
+    fn test_enterprise_network() {
+        let eap = EapConfig::new(EapMethod::Ttls, "anonymous@uni.edu", "student", "secret")
+            .with_ca_cert("-----BEGIN CERTIFICATE-----");
+        let config = WifiConfig::new_enterprise("eduroam", eap.clone()).unwrap();
+        assert!(config.is_enterprise());
+        assert!(!config.is_open());
+        assert!(config.password.is_empty());
+        assert_eq!(config.enterprise, Some(eap));
+
+        assert!(!WifiConfig::open("OpenNetwork").unwrap().is_enterprise());
+    }
+
+    #[esp32_test]
+    fn test_enterprise_requires_identity_and_username() {
+        let no_identity = EapConfig::new(EapMethod::Peap, "", "user", "secret");
+        assert_eq!(
+            WifiConfig::new_enterprise("corp", no_identity),
+            Err(ConfigError::EapIdentityEmpty)
+        );
+
+        let no_username = EapConfig::new(EapMethod::Peap, "anonymous", "", "secret");
+        assert_eq!(
+            WifiConfig::new_enterprise("corp", no_username),
+            Err(ConfigError::EapUsernameEmpty)
+        );
+    }
+
     #[esp32_test]
     fn test_empty_ssid() {
         let result = WifiConfig::new("", "password123");
@@ -356,6 +763,72 @@ mod tests {
         assert!(config.validate().is_ok());
     }
 
+    #[esp32_test]
+    fn test_ssid_normalized_to_nfc() {
+        let nfc = WifiConfig::new("caf\u{e9}.network", "password123").unwrap();
+        let nfd = WifiConfig::new("cafe\u{301}.network", "password123").unwrap();
+        assert_eq!(nfc, nfd);
+        assert_eq!(nfd.ssid, "caf\u{e9}.network");
+
+        let open = WifiConfig::open("cafe\u{301}").unwrap();
+        assert_eq!(open.ssid, "caf\u{e9}");
+    }
+
+    // ==================== WifiConfigList Tests ====================
+
+    #[esp32_test]
+    fn test_config_list_best_available() {
+        let mut list = WifiConfigList::new();
+        list.add_network(1, WifiConfig::new("Office", "password123").unwrap())
+            .unwrap();
+        list.add_network(5, WifiConfig::open("Home").unwrap())
+            .unwrap();
+        list.add_network(5, WifiConfig::open("Backup").unwrap())
+            .unwrap();
+
+        assert_eq!(
+            list.best_available(&["Office", "Home"]).unwrap().ssid,
+            "Home"
+        );
+        assert_eq!(list.best_available(&["Office"]).unwrap().ssid, "Office");
+        // Equal priority: the earlier entry wins
+        assert_eq!(
+            list.best_available(&["Backup", "Home"]).unwrap().ssid,
+            "Home"
+        );
+        assert!(list.best_available(&["Cafe"]).is_none());
+        assert!(list.best_available(&[]).is_none());
+    }
+
+    #[esp32_test]
+    fn test_config_list_add_remove() {
+        let mut list = WifiConfigList::new();
+        list.add_network(1, WifiConfig::open("Home").unwrap())
+            .unwrap();
+        // Same SSID replaces the entry and its priority
+        list.add_network(9, WifiConfig::new("Home", "password123").unwrap())
+            .unwrap();
+        assert_eq!(list.len(), 1);
+        let (priority, config) = list.list_networks().next().unwrap();
+        assert_eq!(priority, 9);
+        assert!(!config.is_open());
+
+        for i in 1..MAX_WIFI_NETWORKS {
+            list.add_network(0, WifiConfig::open(format!("Net{}", i)).unwrap())
+                .unwrap();
+        }
+        assert_eq!(
+            list.add_network(0, WifiConfig::open("Extra").unwrap()),
+            Err(ConfigError::TooManyNetworks {
+                max: MAX_WIFI_NETWORKS
+            })
+        );
+
+        assert_eq!(list.remove_network("Home").unwrap().ssid, "Home");
+        assert!(list.remove_network("Home").is_none());
+        assert_eq!(list.len(), MAX_WIFI_NETWORKS - 1);
+    }
+
     // ==================== Serialization Tests ====================
 
     #[esp32_test]
@@ -390,7 +863,7 @@ mod tests {
     #[esp32_test]
     fn test_deserialize_ssid_too_long() {
         // Craft malicious input: ssid_len = 255 (exceeds MAX_SSID_LEN of 32)
-        let mut bytes = vec![255u8]; // ssid_len = 255
+        let mut bytes = vec![FORMAT_VERSION_FLAG | 1, 255]; // ssid_len = 255
         bytes.extend_from_slice(&[b'x'; 255]); // fake ssid data
         bytes.push(8); // password_len
         bytes.extend_from_slice(b"password");
@@ -402,7 +875,7 @@ mod tests {
     #[esp32_test]
     fn test_deserialize_password_too_long() {
         // Craft malicious input: password_len = 255 (exceeds MAX_PASSWORD_LEN of 64)
-        let mut bytes = vec![4u8]; // ssid_len = 4
+        let mut bytes = vec![FORMAT_VERSION_FLAG | 1, 4]; // ssid_len = 4
         bytes.extend_from_slice(b"test"); // ssid
         bytes.push(255); // password_len = 255
         bytes.extend_from_slice(&[b'x'; 255]); // fake password data
@@ -422,6 +895,104 @@ mod tests {
         assert_eq!(config, restored);
     }
 
+    #[esp32_test]
+    fn test_hidden_ssid_roundtrip() {
+        let config = WifiConfig::new("Hidden", "password123")
+            .unwrap()
+            .with_hidden(true);
+        assert!(config.hidden_ssid);
+        let restored = WifiConfig::from_bytes(&config.to_bytes()).unwrap();
+        assert!(restored.hidden_ssid);
+        assert_eq!(config, restored);
+    }
+
+    #[esp32_test]
+    fn test_deserialize_legacy_format_without_hidden_flag() {
+        // Pre-hidden_ssid format: [ssid_len][ssid][password_len][password]
+        let mut bytes = vec![4u8];
+        bytes.extend_from_slice(b"test");
+        bytes.push(8);
+        bytes.extend_from_slice(b"password");
+
+        let config = WifiConfig::from_bytes(&bytes).unwrap();
+        assert_eq!(config.ssid, "test");
+        assert!(!config.hidden_ssid);
+    }
+
+    #[esp32_test]
+    fn test_serialize_writes_version_byte() {
+        let config = WifiConfig::new("test", "password")
+            .unwrap()
+            .with_hidden(true);
+        let bytes = config.to_bytes();
+        assert_eq!(bytes[0], FORMAT_VERSION_FLAG | FORMAT_VERSION);
+        assert_eq!(bytes[1], 4);
+
+        // Legacy layout of the same config is the versioned one minus the
+        // version and checksum bytes
+        let legacy = WifiConfig::from_bytes(&bytes[1..bytes.len() - 1]).unwrap();
+        assert_eq!(legacy, config);
+    }
+
+    #[esp32_test]
+    fn test_deserialize_unsupported_version() {
+        let mut bytes = WifiConfig::new("test", "password").unwrap().to_bytes();
+        bytes[0] = FORMAT_VERSION_FLAG | 3;
+        assert_eq!(
+            WifiConfig::from_bytes(&bytes),
+            Err(ConfigError::UnsupportedVersion(3))
+        );
+
+        // Versioned format requires the hidden flag
+        let mut truncated = WifiConfig::open("test").unwrap().to_bytes();
+        truncated[0] = FORMAT_VERSION_FLAG | 1;
+        truncated.pop(); // checksum
+        truncated.pop(); // hidden flag
+        assert!(matches!(
+            WifiConfig::from_bytes(&truncated),
+            Err(ConfigError::InvalidFormat(_))
+        ));
+    }
+
+    #[esp32_test]
+    fn test_deserialize_version_1_without_checksum() {
+        let config = WifiConfig::new("test", "password")
+            .unwrap()
+            .with_hidden(true);
+        let mut bytes = config.to_bytes();
+        bytes[0] = FORMAT_VERSION_FLAG | 1;
+        bytes.pop();
+        assert_eq!(WifiConfig::from_bytes(&bytes).unwrap(), config);
+    }
+
+    #[esp32_test]
+    fn test_deserialize_checksum_mismatch() {
+        let bytes = WifiConfig::new("test", "password").unwrap().to_bytes();
+        let checksum = *bytes.last().unwrap();
+
+        // A single flipped bit in the SSID is caught
+        let mut corrupted = bytes.clone();
+        corrupted[2] ^= 0x04;
+        assert_eq!(
+            WifiConfig::from_bytes(&corrupted),
+            Err(ConfigError::ChecksumMismatch {
+                expected: checksum ^ 0x04,
+                found: checksum
+            })
+        );
+
+        // So is a corrupted checksum byte
+        let mut corrupted = bytes;
+        *corrupted.last_mut().unwrap() ^= 0x01;
+        assert_eq!(
+            WifiConfig::from_bytes(&corrupted),
+            Err(ConfigError::ChecksumMismatch {
+                expected: checksum,
+                found: checksum ^ 0x01
+            })
+        );
+    }
+
     // ==================== WifiStatus Tests ====================
 
     #[esp32_test]
@@ -442,14 +1013,60 @@ mod tests {
     fn test_status_connected() {
         let status = WifiStatus::Connected {
             ip: "192.168.1.100".to_string(),
+            wpa_type: Some(WpaType::Wpa2),
         };
-        assert_eq!(status.to_ble_string(), "connected:192.168.1.100");
+        assert_eq!(status.to_ble_string(), "connected:192.168.1.100:WPA2");
         assert_eq!(
-            WifiStatus::from_ble_string("connected:192.168.1.100").unwrap(),
+            WifiStatus::from_ble_string("connected:192.168.1.100:WPA2").unwrap(),
             status
         );
     }
 
+    #[esp32_test]
+    fn test_status_connected_wpa_types() {
+        for wpa_type in [
+            WpaType::Open,
+            WpaType::Wep,
+            WpaType::Wpa,
+            WpaType::Wpa2,
+            WpaType::Wpa3,
+        ] {
+            let status = WifiStatus::Connected {
+                ip: "fe80::1".to_string(),
+                wpa_type: Some(wpa_type),
+            };
+            assert_eq!(
+                WifiStatus::from_ble_string(&status.to_ble_string()).unwrap(),
+                status
+            );
+        }
+
+        // Unknown security modes and malformed addresses are rejected
+        for s in ["connected:10.0.0.1:WPA4", "connected:not-an-ip"] {
+            assert!(matches!(
+                WifiStatus::from_ble_string(s),
+                Err(ConfigError::InvalidFormat(_))
+            ));
+        }
+    }
+
+    #[esp32_test]
+    fn test_status_connected_legacy() {
+        // Firmware before the security suffix sent a bare IP address
+        for ip in ["192.168.1.100", "fe80::1"] {
+            let s = format!("connected:{}", ip);
+            let status = WifiStatus::from_ble_string(&s).unwrap();
+            assert_eq!(
+                status,
+                WifiStatus::Connected {
+                    ip: ip.to_string(),
+                    wpa_type: None,
+                }
+            );
+            assert_eq!(status.to_ble_string(), s);
+        }
+    }
+
     #[esp32_test]
     fn test_status_failed() {
         let status = WifiStatus::Failed {
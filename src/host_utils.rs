@@ -4,7 +4,8 @@
 //! from the host machine. Only available when not building for ESP32.
 
 use log::debug;
-use std::path::Path;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
 use std::process::{Child, Command, Stdio};
 
 /// Maximum lines to process before aborting (protects against infinite output).
@@ -200,37 +201,81 @@ pub fn start_monitor(port: &str) -> Result<std::process::Child, FlashError> {
         .map_err(|e| FlashError::CommandFailed(e.to_string()))
 }
 
+/// QEMU binary for ESP32 emulation.
+const QEMU_BINARY: &str = "qemu-system-xtensa";
+
+/// Environment variable pointing at the QEMU binary, bypassing the search.
+pub const QEMU_PATH_ENV: &str = "QEMU_PATH";
+
+/// QEMU was not found; lists every location that was searched.
+#[derive(Debug)]
+pub struct QemuNotFound {
+    /// Searched locations, in search order.
+    pub searched: Vec<String>,
+}
+
+impl std::fmt::Display for QemuNotFound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} not found. Searched:", QEMU_BINARY)?;
+        for location in &self.searched {
+            writeln!(f, "  {}", location)?;
+        }
+        write!(
+            f,
+            "Set {} to the binary, or install from: https://github.com/espressif/qemu/releases",
+            QEMU_PATH_ENV
+        )
+    }
+}
+
+impl std::error::Error for QemuNotFound {}
+
 /// Find QEMU for ESP32 emulation.
 ///
-/// Searches in Espressif tools directory and PATH.
-pub fn find_qemu() -> Option<std::path::PathBuf> {
+/// Uses `QEMU_PATH` as is if set. Otherwise searches `PATH`, then the
+/// Espressif tools directory (newest version first).
+pub fn find_qemu() -> Result<PathBuf, QemuNotFound> {
+    if let Some(path) = std::env::var_os(QEMU_PATH_ENV).filter(|p| !p.is_empty()) {
+        return Ok(PathBuf::from(path));
+    }
+    let mut searched = vec![format!("${} (not set)", QEMU_PATH_ENV)];
+
+    let path_var = std::env::var_os("PATH").unwrap_or_default();
+    if let Some(path) = find_in_paths(QEMU_BINARY, &path_var) {
+        return Ok(path);
+    }
+    searched.extend(std::env::split_paths(&path_var).map(|dir| dir.display().to_string()));
+
     // Try to find QEMU in Espressif tools directory (any version)
     if let Ok(home) = std::env::var("HOME") {
         let pattern = format!(
-            "{}/.espressif/tools/qemu-xtensa/*/qemu/bin/qemu-system-xtensa",
-            home
+            "{}/.espressif/tools/qemu-xtensa/*/qemu/bin/{}",
+            home, QEMU_BINARY
         );
         if let Ok(paths) = glob::glob(&pattern) {
             // Get the most recent version by sorting paths (version numbers sort naturally)
             let mut candidates: Vec<_> = paths.flatten().collect();
             candidates.sort();
             if let Some(path) = candidates.pop() {
-                return Some(path);
+                return Ok(path);
             }
         }
+        searched.push(pattern);
     }
 
-    // Fallback: check if QEMU is in PATH
-    if let Ok(output) = Command::new("which").arg("qemu-system-xtensa").output() {
-        if output.status.success() {
-            let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
-            if !path.is_empty() {
-                return Some(std::path::PathBuf::from(path));
-            }
-        }
-    }
+    Err(QemuNotFound { searched })
+}
+
+/// Find an executable named `name` in the directories listed in `PATH`.
+pub fn find_in_path(name: &str) -> Option<PathBuf> {
+    find_in_paths(name, &std::env::var_os("PATH")?)
+}
 
-    None
+/// Find `name` in a `PATH`-style list of directories.
+fn find_in_paths(name: &str, paths: &OsStr) -> Option<PathBuf> {
+    std::env::split_paths(paths)
+        .map(|dir| dir.join(name))
+        .find(|candidate| candidate.is_file())
 }
 
 /// Monitor serial output with a custom handler.
@@ -306,9 +351,7 @@ where
             }
         }
 
-        // Convert to string, replacing invalid UTF-8 with replacement char.
-        // Strip \r characters (ESP32 uses \r\n line endings).
-        let line = String::from_utf8_lossy(&buf).trim().replace('\r', "");
+        let line = normalize_line(&buf, line_count == 0);
 
         // Skip empty lines and garbage
         if line.is_empty() || line.chars().all(|c| c == '\u{FFFD}' || c.is_control()) {
@@ -323,6 +366,115 @@ where
     }
 }
 
+/// Convert a raw serial line to a string for matching.
+///
+/// Invalid UTF-8 is replaced with the replacement character, and `\r` is
+/// stripped (some boards use `\r\n` line endings). Some boards also send a
+/// UTF-8 BOM at the start of output, which is stripped from the first
+/// non-empty line when `first` is set.
+fn normalize_line(raw: &[u8], first: bool) -> String {
+    let line = String::from_utf8_lossy(raw);
+    let line = if first {
+        line.trim_start().trim_start_matches('\u{FEFF}')
+    } else {
+        &line
+    };
+    line.trim().replace('\r', "")
+}
+
+/// Outcome of a single test, parsed from the libtest output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestCaseResult {
+    /// Full test path, e.g. `ble::fragmentation::tests::test_roundtrip`.
+    pub name: String,
+    /// Whether the test reported `ok`.
+    pub passed: bool,
+    /// First line of the failure output, if the test failed and printed one.
+    pub message: Option<String>,
+}
+
+/// Per-test results collected from [`monitor_output`] lines, so a run can be
+/// inspected without re-parsing the output.
+#[derive(Debug, Default)]
+pub struct TestResults {
+    results: Vec<TestCaseResult>,
+    /// Test whose `---- <name> stdout ----` failure section is being read.
+    failure_section: Option<String>,
+}
+
+impl TestResults {
+    /// Record a line of libtest output.
+    ///
+    /// Picks up `test <name> ... ok` / `... FAILED` result lines and the
+    /// first line of each failed test's captured output. Ignored tests are
+    /// not recorded.
+    pub fn record_line(&mut self, line: &str) {
+        if let Some(rest) = line.strip_prefix("test ") {
+            if let Some((name, outcome)) = rest.split_once(" ... ") {
+                let passed = match outcome.trim() {
+                    "ok" => true,
+                    "FAILED" => false,
+                    _ => return,
+                };
+                self.results.push(TestCaseResult {
+                    name: name.to_string(),
+                    passed,
+                    message: None,
+                });
+            }
+            return;
+        }
+
+        if let Some(name) = line
+            .strip_prefix("---- ")
+            .and_then(|rest| rest.strip_suffix(" stdout ----"))
+        {
+            self.failure_section = Some(name.to_string());
+            return;
+        }
+
+        let line = line.trim();
+        if line.is_empty() || line.starts_with("thread '") {
+            return;
+        }
+        if let Some(name) = self.failure_section.take() {
+            if let Some(result) = self
+                .results
+                .iter_mut()
+                .find(|r| r.name == name && r.message.is_none())
+            {
+                result.message = Some(line.to_string());
+            }
+        }
+    }
+
+    /// All tests that reported a result, in the order they finished.
+    pub fn results(&self) -> &[TestCaseResult] {
+        &self.results
+    }
+
+    /// Print the pass rate and the failed tests with their messages.
+    pub fn print_summary(&self) {
+        let results = self.results();
+        if results.is_empty() {
+            return;
+        }
+        let passed = results.iter().filter(|r| r.passed).count();
+        print!(
+            "Passed {}/{} ({:.1}%)\r\n",
+            passed,
+            results.len(),
+            passed as f64 * 100.0 / results.len() as f64
+        );
+        for failed in results.iter().filter(|r| !r.passed) {
+            match &failed.message {
+                Some(message) => print!("  FAILED {}: {}\r\n", failed.name, message),
+                None => print!("  FAILED {}\r\n", failed.name),
+            }
+        }
+    }
+}
+
 /// Flash a binary and monitor output with a custom handler.
 ///
 /// Flashes the binary, then monitors serial output. Each line is passed to
@@ -394,3 +546,164 @@ impl From<String> for FlashError {
         FlashError::CommandFailed(s)
     }
 }
+
+/// libtest options that take a separate value, e.g. `--skip slow`.
+const TEST_ARGS_WITH_VALUE: &[&str] = &[
+    "--color",
+    "--format",
+    "--logfile",
+    "--shuffle-seed",
+    "--skip",
+    "--test-threads",
+    "-Z",
+];
+
+/// Extract the filter from test arguments: the value of `--filter`, or else
+/// the first bare argument (as `cargo test <pattern>` would use it).
+///
+/// Values of options like `--skip slow` are not bare arguments.
+pub fn filter_from_test_args(args: &str) -> Option<String> {
+    let mut words = args.split_whitespace();
+    let mut bare = None;
+    while let Some(word) = words.next() {
+        if word == "--filter" {
+            return words.next().map(str::to_string);
+        }
+        if TEST_ARGS_WITH_VALUE.contains(&word) {
+            words.next();
+        } else if bare.is_none() && !word.starts_with('-') {
+            bare = Some(word);
+        }
+    }
+    bare.map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reticulum_rs_esp32_macros::esp32_test;
+
+    #[esp32_test]
+    fn test_normalize_line() {
+        assert_eq!(
+            normalize_line(b"test result: ok\r\n", false),
+            "test result: ok"
+        );
+        assert_eq!(normalize_line(b"a\rb\n", false), "ab");
+        assert_eq!(
+            normalize_line("\u{FEFF}test result: ok\r\n".as_bytes(), true),
+            "test result: ok"
+        );
+        // A BOM is only expected at the start of output
+        assert_eq!(normalize_line("\u{FEFF}x\n".as_bytes(), false), "\u{FEFF}x");
+    }
+
+    #[esp32_test]
+    fn test_find_in_paths() {
+        let dir = std::env::temp_dir().join(format!("find_in_paths_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("bin")).unwrap();
+        std::fs::write(dir.join("bin").join("fake-qemu"), b"").unwrap();
+
+        let paths = std::env::join_paths([dir.join("empty"), dir.join("bin")]).unwrap();
+        assert_eq!(
+            find_in_paths("fake-qemu", &paths),
+            Some(dir.join("bin").join("fake-qemu"))
+        );
+        assert_eq!(find_in_paths("missing", &paths), None);
+        // Directories don't count as executables
+        assert_eq!(
+            find_in_paths("bin", &std::env::join_paths([&dir]).unwrap()),
+            None
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[esp32_test]
+    fn test_filter_from_test_args() {
+        assert_eq!(filter_from_test_args(""), None);
+        assert_eq!(filter_from_test_args("csma"), Some("csma".to_string()));
+        assert_eq!(
+            filter_from_test_args("--nocapture csma"),
+            Some("csma".to_string())
+        );
+        // --filter wins over an earlier bare argument
+        assert_eq!(
+            filter_from_test_args("csma --filter airtime"),
+            Some("airtime".to_string())
+        );
+        assert_eq!(filter_from_test_args("--filter"), None);
+    }
+
+    #[esp32_test]
+    fn test_filter_from_test_args_skips_option_values() {
+        assert_eq!(filter_from_test_args("--skip slow"), None);
+        assert_eq!(
+            filter_from_test_args("--test-threads 1 --skip slow csma"),
+            Some("csma".to_string())
+        );
+        assert_eq!(
+            filter_from_test_args("--format=terse csma"),
+            Some("csma".to_string())
+        );
+    }
+
+    fn record(lines: &[&str]) -> TestResults {
+        let mut results = TestResults::default();
+        for line in lines {
+            results.record_line(line);
+        }
+        results
+    }
+
+    #[esp32_test]
+    fn test_record_line_results() {
+        let results = record(&[
+            "running 3 tests",
+            "test lora::csma::tests::test_a ... ok",
+            "test lora::csma::tests::test_b ... FAILED",
+            "test lora::csma::tests::test_c ... ignored",
+            "test result: FAILED. 1 passed; 1 failed; 1 ignored",
+        ]);
+
+        assert_eq!(
+            results.results(),
+            &[
+                TestCaseResult {
+                    name: "lora::csma::tests::test_a".to_string(),
+                    passed: true,
+                    message: None,
+                },
+                TestCaseResult {
+                    name: "lora::csma::tests::test_b".to_string(),
+                    passed: false,
+                    message: None,
+                },
+            ]
+        );
+    }
+
+    #[esp32_test]
+    fn test_record_line_failure_message() {
+        let results = record(&[
+            "test lora::csma::tests::test_a ... ok",
+            "test lora::csma::tests::test_b ... FAILED",
+            "",
+            "failures:",
+            "",
+            "---- lora::csma::tests::test_b stdout ----",
+            "thread 'lora::csma::tests::test_b' panicked at src/lora/csma.rs:10:5:",
+            "assertion `left == right` failed",
+            "  left: 1",
+            " right: 2",
+        ]);
+
+        let results = results.results();
+        assert_eq!(results[0].message, None);
+        assert!(!results[1].passed);
+        assert_eq!(
+            results[1].message.as_deref(),
+            Some("assertion `left == right` failed")
+        );
+    }
+}
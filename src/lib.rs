@@ -17,6 +17,7 @@ pub mod persistence;
 #[cfg(not(feature = "esp32"))]
 pub mod persistence_host;
 pub mod routing;
+pub mod shutdown;
 pub mod testnet;
 pub mod wifi;
 
@@ -27,16 +28,19 @@ pub mod node;
 // Re-export commonly used items
 pub use announce::{AnnounceCache, AnnounceCacheConfig, AnnounceEntry};
 pub use ble::{Fragment, FragmentError, Fragmenter, Reassembler};
-pub use chat::{ChatCommand, ChatState, KnownDestination, HELP_TEXT};
-pub use config::{ConfigCommand, ConfigError, WifiConfig, WifiStatus};
-pub use lora::{calculate_airtime_ms, calculate_airtime_us, DutyCycleLimiter, LoRaParams};
+pub use chat::{AddResult, ChatCommand, ChatState, KnownDestination, MessageTextError, HELP_TEXT};
+pub use config::{ConfigCommand, ConfigError, WifiConfig, WifiStatus, WpaType};
+pub use lora::{
+    calculate_airtime_ms, calculate_airtime_us, DutyCycleLimiter, LoRaParamError, LoRaParams,
+};
 pub use message_queue::{QueuedMessage, MAX_QUEUED_MESSAGES_PER_DEST, QUEUE_MESSAGE_TTL};
 pub use network::{NetworkError, NetworkProvider, NodeStats, StatsServer, DEFAULT_STATS_PORT};
 pub use routing::{InterfaceType, PathEntry, PathTable, PathTableConfig, RoutingMetrics};
+pub use shutdown::{ShutdownManager, DEFAULT_SHUTDOWN_TIMEOUT};
 pub use testnet::{TestnetServer, TestnetTransport, TransportError, DEFAULT_SERVER, SERVERS};
 
 #[cfg(not(feature = "esp32"))]
-pub use node::{IncomingMessage, LinkActivationEvent, Node, NodeError};
+pub use node::{ConnectionStats, IncomingMessage, LinkActivationEvent, Node, NodeError};
 
 #[cfg(feature = "esp32")]
 pub use network::WifiNetwork;
@@ -14,10 +14,44 @@
 //! println!("50-byte packet takes {} us ({:.2} ms)", airtime, airtime as f64 / 1000.0);
 //! ```
 
+use super::config::{BANDWIDTH_HZ, CODING_RATE, LORA_MTU, PREAMBLE_LENGTH, SPREADING_FACTOR};
+
+/// LoRa bandwidths supported by the SX1262, in Hz.
+const VALID_BANDWIDTHS_HZ: [u32; 10] = [
+    7_800, 10_400, 15_600, 20_800, 31_250, 41_700, 62_500, 125_000, 250_000, 500_000,
+];
+
+/// A [`LoRaParams`] field out of the range the SX1262 supports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoRaParamError {
+    /// Spreading factor outside 6-12.
+    InvalidSpreadingFactor(u8),
+    /// Bandwidth isn't one of the SX1262 LoRa bandwidths.
+    InvalidBandwidth(u32),
+    /// Coding rate denominator outside 5-8 (4/5 to 4/8).
+    InvalidCodingRate(u8),
+}
+
+impl std::fmt::Display for LoRaParamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidSpreadingFactor(sf) => {
+                write!(f, "invalid spreading factor {} (expected 6-12)", sf)
+            }
+            Self::InvalidBandwidth(hz) => write!(f, "unsupported bandwidth {} Hz", hz),
+            Self::InvalidCodingRate(cr) => {
+                write!(f, "invalid coding rate 4/{} (expected 4/5 to 4/8)", cr)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LoRaParamError {}
+
 /// LoRa modulation parameters for airtime calculation.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct LoRaParams {
-    /// Spreading factor (7-12)
+    /// Spreading factor (6-12)
     pub spreading_factor: u8,
     /// Bandwidth in Hz (typically 125000, 250000, or 500000)
     pub bandwidth_hz: u32,
@@ -27,6 +61,11 @@ pub struct LoRaParams {
     pub preamble_symbols: u8,
     /// Whether explicit header mode is used
     pub explicit_header: bool,
+    /// Fixed payload length for implicit header mode.
+    ///
+    /// `Some(n)` forces implicit header mode (overriding `explicit_header`)
+    /// with every packet `n` bytes long, as used for fixed-length probes.
+    pub implicit_header_len: Option<u8>,
     /// Whether CRC is enabled
     pub crc_enabled: bool,
 }
@@ -35,17 +74,56 @@ impl Default for LoRaParams {
     /// Default parameters matching Reticulum/RNode defaults.
     fn default() -> Self {
         Self {
-            spreading_factor: 7,
-            bandwidth_hz: 125_000,
-            coding_rate: 5, // 4/5
-            preamble_symbols: 8,
+            spreading_factor: SPREADING_FACTOR,
+            bandwidth_hz: BANDWIDTH_HZ,
+            coding_rate: CODING_RATE,
+            preamble_symbols: PREAMBLE_LENGTH as u8,
             explicit_header: true,
+            implicit_header_len: None,
             crc_enabled: true,
         }
     }
 }
 
 impl LoRaParams {
+    /// Check that the modulation parameters are ones the SX1262 supports.
+    ///
+    /// The fields are public, so out-of-range values (e.g. SF0, which makes
+    /// [`calculate_airtime_us`] meaningless) are only caught here.
+    pub fn is_valid(&self) -> Result<(), LoRaParamError> {
+        if !(6..=12).contains(&self.spreading_factor) {
+            return Err(LoRaParamError::InvalidSpreadingFactor(
+                self.spreading_factor,
+            ));
+        }
+        if !VALID_BANDWIDTHS_HZ.contains(&self.bandwidth_hz) {
+            return Err(LoRaParamError::InvalidBandwidth(self.bandwidth_hz));
+        }
+        if !(5..=8).contains(&self.coding_rate) {
+            return Err(LoRaParamError::InvalidCodingRate(self.coding_rate));
+        }
+        Ok(())
+    }
+
+    /// Check if implicit header mode is used (no length field on air).
+    pub fn is_implicit_header(&self) -> bool {
+        !self.explicit_header || self.implicit_header_len.is_some()
+    }
+
+    /// Header type and payload length bytes for SX1262 `SetPacketParams`.
+    ///
+    /// In implicit header mode with a fixed length, that length is used
+    /// regardless of `payload_len`: the receiver has no length field to read,
+    /// so both sides must agree on it up front.
+    pub fn header_params(&self, payload_len: u8) -> (u8, u8) {
+        let header_type = if self.is_implicit_header() {
+            0x01
+        } else {
+            0x00
+        };
+        (header_type, self.implicit_header_len.unwrap_or(payload_len))
+    }
+
     /// Check if low data rate optimization should be enabled.
     ///
     /// Required when symbol time exceeds 16ms (SF11/SF12 at 125kHz).
@@ -65,6 +143,23 @@ impl LoRaParams {
         }
         (1u64 << sf) * 1_000_000 / bw
     }
+
+    /// Estimate how many `payload_len`-byte packets fit in an airtime budget.
+    ///
+    /// Saturates at `usize::MAX` (e.g. for a zero-airtime configuration).
+    pub fn packets_in_budget(&self, budget_us: u64, payload_len: usize) -> usize {
+        let airtime_us = calculate_airtime_us(payload_len, self);
+        if airtime_us == 0 {
+            return usize::MAX;
+        }
+        usize::try_from(budget_us / airtime_us).unwrap_or(usize::MAX)
+    }
+
+    /// Estimate how many maximum-size ([`LORA_MTU`]) packets fit in an
+    /// airtime budget, e.g. the remaining duty cycle headroom.
+    pub fn airtime_budget_packets(&self, budget_us: u64) -> usize {
+        self.packets_in_budget(budget_us, LORA_MTU)
+    }
 }
 
 /// Calculate LoRa packet airtime in microseconds.
@@ -100,7 +195,11 @@ pub fn calculate_airtime_us(payload_bytes: usize, params: &LoRaParams) -> u64 {
     } else {
         0.0
     };
-    let h = if params.explicit_header { 0.0 } else { 1.0 };
+    let h = if params.is_implicit_header() {
+        1.0
+    } else {
+        0.0
+    };
     let crc_bits = if params.crc_enabled { 16.0 } else { 0.0 };
 
     // Numerator: 8*PL - 4*SF + 28 + 16*CRC - 20*H
@@ -109,6 +208,10 @@ pub fn calculate_airtime_us(payload_bytes: usize, params: &LoRaParams) -> u64 {
     let numerator = 8.0 * pl - 4.0 * sf + 28.0 + crc_bits - 20.0 * h;
 
     // Denominator: 4 * (SF - 2*DE)
+    // With LDRO each symbol carries two fewer bits, so DE=1 shrinks the
+    // denominator and increases the payload symbol count (SX1262 Table 6-1).
+    // The datasheet writes the header term as `+ 8 + 20*H` with H=1 for
+    // explicit header; `+ 28 - 20*H` with H=1 for implicit is equivalent.
     let denominator = 4.0 * (sf - 2.0 * de);
 
     // Payload symbols = 8 + max(ceil(numerator/denominator) * CR, 0)
@@ -152,9 +255,98 @@ mod tests {
         assert_eq!(params.coding_rate, 5);
         assert_eq!(params.preamble_symbols, 8);
         assert!(params.explicit_header);
+        assert_eq!(params.implicit_header_len, None);
         assert!(params.crc_enabled);
     }
 
+    #[esp32_test]
+    fn test_is_valid() {
+        assert_eq!(LoRaParams::default().is_valid(), Ok(()));
+
+        for (sf, expected) in [(5, false), (6, true), (12, true), (13, false), (0, false)] {
+            let params = LoRaParams {
+                spreading_factor: sf,
+                ..Default::default()
+            };
+            assert_eq!(params.is_valid().is_ok(), expected, "SF{}", sf);
+        }
+        assert_eq!(
+            LoRaParams {
+                spreading_factor: 0,
+                ..Default::default()
+            }
+            .is_valid(),
+            Err(LoRaParamError::InvalidSpreadingFactor(0))
+        );
+
+        for bw in VALID_BANDWIDTHS_HZ {
+            let params = LoRaParams {
+                bandwidth_hz: bw,
+                ..Default::default()
+            };
+            assert_eq!(params.is_valid(), Ok(()));
+        }
+        assert_eq!(
+            LoRaParams {
+                bandwidth_hz: 100_000,
+                ..Default::default()
+            }
+            .is_valid(),
+            Err(LoRaParamError::InvalidBandwidth(100_000))
+        );
+
+        for (cr, expected) in [(4, false), (5, true), (8, true), (10, false)] {
+            let params = LoRaParams {
+                coding_rate: cr,
+                ..Default::default()
+            };
+            assert_eq!(params.is_valid().is_ok(), expected, "CR 4/{}", cr);
+        }
+    }
+
+    #[esp32_test]
+    fn test_header_params_implicit_uses_fixed_length() {
+        let explicit = LoRaParams::default();
+        assert!(!explicit.is_implicit_header());
+        assert_eq!(explicit.header_params(42), (0x00, 42));
+
+        let implicit = LoRaParams {
+            implicit_header_len: Some(16),
+            ..Default::default()
+        };
+        assert!(implicit.is_implicit_header());
+        // Runtime payload length is ignored in favor of the fixed length
+        assert_eq!(implicit.header_params(42), (0x01, 16));
+        assert_eq!(implicit.header_params(0), (0x01, 16));
+
+        // Implicit header saves the 20-bit header on air
+        assert!(calculate_airtime_us(16, &implicit) < calculate_airtime_us(16, &explicit));
+    }
+
+    #[esp32_test]
+    fn test_implicit_header_fragment_parses_from_fixed_length_frame() {
+        use crate::ble::Fragment;
+
+        let params = LoRaParams {
+            implicit_header_len: Some(16),
+            ..Default::default()
+        };
+        let fragment = Fragment::new(7, 0x01, (0..14).collect());
+        let bytes = fragment.to_bytes();
+        assert_eq!(bytes.len(), 16);
+
+        // The receiver has no length byte on air and reads the fixed length
+        // out of its RX buffer, whatever length the sender passed in
+        let mut rx_buffer = [0xAA; LORA_MTU];
+        rx_buffer[..bytes.len()].copy_from_slice(&bytes);
+        for payload_len in [0, 16, u8::MAX] {
+            let (header_type, length) = params.header_params(payload_len);
+            assert_eq!(header_type, 0x01);
+            let frame = &rx_buffer[..usize::from(length)];
+            assert_eq!(Fragment::from_bytes(frame).unwrap(), fragment);
+        }
+    }
+
     #[esp32_test]
     fn test_symbol_duration_sf7_125khz() {
         let params = LoRaParams {
@@ -386,6 +578,90 @@ mod tests {
         assert_eq!(calculate_airtime_us(50, &params), 0);
     }
 
+    #[esp32_test]
+    fn test_airtime_regression_anchor_sf7_bw125() {
+        // 12-byte packet, SF7/BW125/CR4-5, explicit header, CRC, 8 preamble symbols.
+        // Semtech LoRa Calculator: 41.22 ms
+        // Preamble: 12.25 sym, payload: 8 + ceil(112/28)*5 = 28 sym
+        // (12.25 + 28) * 1024 us = 41216 us
+        let params = LoRaParams::default();
+        assert_eq!(calculate_airtime_us(12, &params), 41_216);
+    }
+
+    #[esp32_test]
+    fn test_airtime_sweep_matches_datasheet() {
+        // Expected payload symbols for a 12-byte packet, CR4/5, explicit header, CRC.
+        // Numerator 8*12 - 4*SF + 28 + 16 = 140 - 4*SF. LDRO (DE=1) applies when
+        // symbol time exceeds 16 ms: SF11/SF12 at BW125 and SF12 at BW250.
+        let cases: [(u8, u32, bool, u64); 18] = [
+            (7, 125_000, false, 28),
+            (8, 125_000, false, 28),
+            (9, 125_000, false, 23),
+            (10, 125_000, false, 23),
+            (11, 125_000, true, 23),
+            (12, 125_000, true, 23),
+            (7, 250_000, false, 28),
+            (8, 250_000, false, 28),
+            (9, 250_000, false, 23),
+            (10, 250_000, false, 23),
+            (11, 250_000, false, 23),
+            (12, 250_000, true, 23),
+            (7, 500_000, false, 28),
+            (8, 500_000, false, 28),
+            (9, 500_000, false, 23),
+            (10, 500_000, false, 23),
+            (11, 500_000, false, 23),
+            (12, 500_000, false, 18),
+        ];
+
+        for (sf, bw, ldro, payload_symbols) in cases {
+            let params = LoRaParams {
+                spreading_factor: sf,
+                bandwidth_hz: bw,
+                ..Default::default()
+            };
+            assert_eq!(
+                params.low_data_rate_optimize(),
+                ldro,
+                "LDRO mismatch at SF{} BW{}",
+                sf,
+                bw
+            );
+
+            // (12.25 + payload_symbols) * 2^SF / BW, scaled by 4 to stay integral
+            let expected = (49 + 4 * payload_symbols) * (1u64 << sf) * 1_000_000 / (4 * bw as u64);
+            let actual = calculate_airtime_us(12, &params);
+            assert!(
+                actual.abs_diff(expected) <= 1,
+                "SF{} BW{}: expected {} us, got {} us",
+                sf,
+                bw,
+                expected,
+                actual
+            );
+        }
+    }
+
+    #[esp32_test]
+    fn test_packets_in_budget() {
+        let params = LoRaParams::default();
+        let airtime = calculate_airtime_us(50, &params);
+
+        assert_eq!(params.packets_in_budget(0, 50), 0);
+        assert_eq!(params.packets_in_budget(airtime - 1, 50), 0);
+        assert_eq!(params.packets_in_budget(airtime * 3, 50), 3);
+        assert_eq!(
+            params.airtime_budget_packets(calculate_airtime_us(LORA_MTU, &params) * 2),
+            2
+        );
+
+        let zero_bw = LoRaParams {
+            bandwidth_hz: 0,
+            ..Default::default()
+        };
+        assert_eq!(zero_bw.packets_in_budget(1_000, 50), usize::MAX);
+    }
+
     #[esp32_test]
     fn test_ms_conversion() {
         let params = LoRaParams::default();
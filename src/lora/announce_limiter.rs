@@ -0,0 +1,209 @@
+//! Rate limiting for announce rebroadcasts.
+//!
+//! A gateway can receive a burst of announces over WiFi far faster than LoRa
+//! can carry them. Forwarding all of them immediately exhausts the duty cycle
+//! budget and most transmissions fail. [`AnnounceLimiter`] smooths bursts
+//! with a token bucket and holds excess announces in a small queue, dropping
+//! the oldest when it overflows (newer announces carry fresher paths).
+
+use super::{calculate_airtime_us, LoRaParams, LORA_MTU};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Announces that may be sent back to back.
+pub const ANNOUNCE_BURST: u32 = 5;
+
+/// Announces held while waiting for a token.
+pub const ANNOUNCE_QUEUE_LEN: usize = 8;
+
+/// Token bucket with an overflow queue for announce rebroadcasts.
+///
+/// # Example
+///
+/// ```
+/// use reticulum_rs_esp32::lora::{AnnounceLimiter, LoRaParams, ANNOUNCE_BURST};
+///
+/// let mut limiter = AnnounceLimiter::<Vec<u8>>::for_params(&LoRaParams::default());
+/// for _ in 0..ANNOUNCE_BURST {
+///     assert!(limiter.try_take());
+/// }
+/// assert!(!limiter.try_take());
+///
+/// // Out of tokens: hold the announce until one refills
+/// assert_eq!(limiter.enqueue(vec![0x01]), None);
+/// assert_eq!(limiter.queued(), 1);
+/// ```
+#[derive(Debug)]
+pub struct AnnounceLimiter<T> {
+    /// Time to earn one token.
+    refill_interval: Duration,
+    /// Available tokens, in microseconds of refill time.
+    level_us: u64,
+    last_refill: Instant,
+    queue: VecDeque<T>,
+}
+
+impl<T> AnnounceLimiter<T> {
+    /// Create a limiter that earns one token per `refill_interval`.
+    ///
+    /// Starts with a full bucket of [`ANNOUNCE_BURST`] tokens.
+    pub fn new(refill_interval: Duration) -> Self {
+        Self::new_at(refill_interval, Instant::now())
+    }
+
+    /// Create a limiter that earns one token per maximum-size packet airtime.
+    ///
+    /// With a fixed implicit header length, that length is the largest packet.
+    pub fn for_params(params: &LoRaParams) -> Self {
+        let max_len = params.implicit_header_len.map_or(LORA_MTU, usize::from);
+        Self::new(Duration::from_micros(calculate_airtime_us(max_len, params)))
+    }
+
+    fn new_at(refill_interval: Duration, now: Instant) -> Self {
+        let mut limiter = Self {
+            refill_interval,
+            level_us: 0,
+            last_refill: now,
+            queue: VecDeque::with_capacity(ANNOUNCE_QUEUE_LEN),
+        };
+        limiter.level_us = limiter.capacity_us();
+        limiter
+    }
+
+    /// Take a token if one is available.
+    pub fn try_take(&mut self) -> bool {
+        self.try_take_at(Instant::now())
+    }
+
+    fn try_take_at(&mut self, now: Instant) -> bool {
+        self.refill(now);
+        let cost = self.token_us();
+        if self.level_us >= cost {
+            self.level_us -= cost;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Queue an announce until a token is available.
+    ///
+    /// Returns the oldest queued announce if it had to be dropped to make
+    /// room.
+    pub fn enqueue(&mut self, item: T) -> Option<T> {
+        let dropped = if self.queue.len() >= ANNOUNCE_QUEUE_LEN {
+            self.queue.pop_front()
+        } else {
+            None
+        };
+        self.queue.push_back(item);
+        dropped
+    }
+
+    /// Take the oldest queued announce if a token is available.
+    pub fn pop_ready(&mut self) -> Option<T> {
+        self.pop_ready_at(Instant::now())
+    }
+
+    fn pop_ready_at(&mut self, now: Instant) -> Option<T> {
+        if self.queue.is_empty() || !self.try_take_at(now) {
+            return None;
+        }
+        self.queue.pop_front()
+    }
+
+    /// Get the number of queued announces.
+    pub fn queued(&self) -> usize {
+        self.queue.len()
+    }
+
+    fn token_us(&self) -> u64 {
+        (self.refill_interval.as_micros() as u64).max(1)
+    }
+
+    fn capacity_us(&self) -> u64 {
+        self.token_us().saturating_mul(ANNOUNCE_BURST as u64)
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        self.level_us = self
+            .level_us
+            .saturating_add(elapsed.as_micros() as u64)
+            .min(self.capacity_us());
+        self.last_refill = now;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use reticulum_rs_esp32_macros::esp32_test;
+
+    const INTERVAL: Duration = Duration::from_millis(100);
+
+    #[esp32_test]
+    fn test_burst_then_refill() {
+        let start = Instant::now();
+        let mut limiter = AnnounceLimiter::<u8>::new_at(INTERVAL, start);
+
+        for _ in 0..ANNOUNCE_BURST {
+            assert!(limiter.try_take_at(start));
+        }
+        assert!(!limiter.try_take_at(start));
+        assert!(!limiter.try_take_at(start + INTERVAL / 2));
+        assert!(limiter.try_take_at(start + INTERVAL));
+        assert!(!limiter.try_take_at(start + INTERVAL));
+
+        // Idle time never banks more than a full burst
+        let later = start + INTERVAL * 100;
+        for _ in 0..ANNOUNCE_BURST {
+            assert!(limiter.try_take_at(later));
+        }
+        assert!(!limiter.try_take_at(later));
+    }
+
+    #[esp32_test]
+    fn test_queue_drops_oldest_and_drains() {
+        let start = Instant::now();
+        let mut limiter = AnnounceLimiter::new_at(INTERVAL, start);
+        for _ in 0..ANNOUNCE_BURST {
+            assert!(limiter.try_take_at(start));
+        }
+
+        for i in 0..ANNOUNCE_QUEUE_LEN as u8 {
+            assert_eq!(limiter.enqueue(i), None);
+        }
+        assert_eq!(limiter.enqueue(100), Some(0));
+        assert_eq!(limiter.queued(), ANNOUNCE_QUEUE_LEN);
+
+        // Drains in order, one per token
+        assert_eq!(limiter.pop_ready_at(start), None);
+        assert_eq!(limiter.pop_ready_at(start + INTERVAL), Some(1));
+        assert_eq!(limiter.pop_ready_at(start + INTERVAL), None);
+        assert_eq!(limiter.pop_ready_at(start + INTERVAL * 2), Some(2));
+        assert_eq!(limiter.queued(), ANNOUNCE_QUEUE_LEN - 2);
+    }
+
+    #[esp32_test]
+    fn test_for_params_follows_link() {
+        let default = LoRaParams::default();
+        let interval =
+            |params: &LoRaParams| AnnounceLimiter::<u8>::for_params(params).refill_interval;
+
+        // A narrower channel takes longer per packet, so tokens refill slower
+        let narrow = LoRaParams {
+            bandwidth_hz: default.bandwidth_hz / 2,
+            ..default
+        };
+        assert!(interval(&narrow) > interval(&default));
+
+        // Fixed-length frames are shorter than the MTU
+        let implicit = LoRaParams {
+            implicit_header_len: Some(16),
+            ..default
+        };
+        assert!(interval(&implicit) < interval(&default));
+    }
+}
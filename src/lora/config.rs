@@ -7,6 +7,72 @@
 use super::DutyCycleLimiter;
 use std::time::Duration;
 
+/// A LoRa channel the radio can be tuned to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoRaChannel {
+    /// Center frequency in Hz.
+    pub frequency_hz: u32,
+    /// Bandwidth in Hz (125, 250 or 500 kHz).
+    pub bandwidth_hz: u32,
+    /// Human-readable name for logs and the serial console.
+    pub label: &'static str,
+}
+
+impl LoRaChannel {
+    /// Create a channel with the standard [`BANDWIDTH_HZ`].
+    pub const fn new(frequency_hz: u32, label: &'static str) -> Self {
+        Self {
+            frequency_hz,
+            bandwidth_hz: BANDWIDTH_HZ,
+            label,
+        }
+    }
+}
+
+/// EU868: the three mandatory LoRaWAN channels, then five more at 200 kHz spacing.
+const EU868_CHANNELS: [LoRaChannel; 8] = [
+    LoRaChannel::new(868_100_000, "868.1"),
+    LoRaChannel::new(868_300_000, "868.3"),
+    LoRaChannel::new(868_500_000, "868.5"),
+    LoRaChannel::new(867_100_000, "867.1"),
+    LoRaChannel::new(867_300_000, "867.3"),
+    LoRaChannel::new(867_500_000, "867.5"),
+    LoRaChannel::new(867_700_000, "867.7"),
+    LoRaChannel::new(867_900_000, "867.9"),
+];
+
+/// US915: the default frequency, then LoRaWAN sub-band 2.
+const US915_CHANNELS: [LoRaChannel; 9] = [
+    LoRaChannel::new(915_000_000, "915.0"),
+    LoRaChannel::new(903_900_000, "903.9"),
+    LoRaChannel::new(904_100_000, "904.1"),
+    LoRaChannel::new(904_300_000, "904.3"),
+    LoRaChannel::new(904_500_000, "904.5"),
+    LoRaChannel::new(904_700_000, "904.7"),
+    LoRaChannel::new(904_900_000, "904.9"),
+    LoRaChannel::new(905_100_000, "905.1"),
+    LoRaChannel::new(905_300_000, "905.3"),
+];
+
+/// AU915: the default frequency, then LoRaWAN sub-band 2.
+const AU915_CHANNELS: [LoRaChannel; 9] = [
+    LoRaChannel::new(915_000_000, "915.0"),
+    LoRaChannel::new(916_800_000, "916.8"),
+    LoRaChannel::new(917_000_000, "917.0"),
+    LoRaChannel::new(917_200_000, "917.2"),
+    LoRaChannel::new(917_400_000, "917.4"),
+    LoRaChannel::new(917_600_000, "917.6"),
+    LoRaChannel::new(917_800_000, "917.8"),
+    LoRaChannel::new(918_000_000, "918.0"),
+    LoRaChannel::new(918_200_000, "918.2"),
+];
+
+/// AS923: the two mandatory LoRaWAN channels.
+const AS923_CHANNELS: [LoRaChannel; 2] = [
+    LoRaChannel::new(923_200_000, "923.2"),
+    LoRaChannel::new(923_400_000, "923.4"),
+];
+
 /// Frequency band region.
 ///
 /// Determines the operating frequency and duty cycle limits for regulatory compliance.
@@ -33,6 +99,19 @@ impl Region {
         }
     }
 
+    /// Get the channels available in this region.
+    ///
+    /// The first channel is always [`frequency`](Self::frequency), so nodes
+    /// that never switch channel stay compatible with older firmware.
+    pub fn channels(self) -> &'static [LoRaChannel] {
+        match self {
+            Self::Eu868 => &EU868_CHANNELS,
+            Self::Us915 => &US915_CHANNELS,
+            Self::Au915 => &AU915_CHANNELS,
+            Self::As923 => &AS923_CHANNELS,
+        }
+    }
+
     /// Get the duty cycle limit for this region (percentage).
     pub fn duty_cycle_percent(self) -> f32 {
         match self {
@@ -89,6 +168,20 @@ pub const PREAMBLE_LENGTH: u16 = 8;
 /// Sync word (0x12 for private Reticulum network).
 pub const SYNC_WORD: u8 = 0x12;
 
+/// Sync word used by public LoRaWAN networks.
+pub const LORAWAN_SYNC_WORD: u8 = 0x34;
+
+/// Convert a one-byte sync word to the SX126x two-byte register value.
+///
+/// SX126x radios spread each nibble over a register byte, with the low
+/// nibble of each byte fixed at 0x4 (e.g. 0x12 becomes 0x1424), so they
+/// interoperate with one-byte sync words on SX127x radios.
+pub fn sync_word_register(word: u8) -> u16 {
+    let msb = (word & 0xF0) | 0x04;
+    let lsb = ((word & 0x0F) << 4) | 0x04;
+    u16::from_be_bytes([msb, lsb])
+}
+
 /// Reticulum MTU for LoRa interface.
 pub const LORA_MTU: usize = 500;
 
@@ -109,12 +202,30 @@ mod tests {
         assert_eq!(Region::As923.frequency(), 923_200_000);
     }
 
+    #[esp32_test]
+    fn test_region_channels() {
+        for region in [Region::Eu868, Region::Us915, Region::Au915, Region::As923] {
+            let channels = region.channels();
+            assert_eq!(channels[0].frequency_hz, region.frequency());
+            assert!(channels.iter().all(|ch| ch.bandwidth_hz == BANDWIDTH_HZ));
+        }
+        assert_eq!(Region::Eu868.channels().len(), 8);
+        assert_eq!(Region::Eu868.channels()[3].label, "867.1");
+    }
+
     #[esp32_test]
     fn test_region_duty_cycle() {
         assert_eq!(Region::Eu868.duty_cycle_percent(), 1.0);
         assert_eq!(Region::Us915.duty_cycle_percent(), 10.0);
     }
 
+    #[esp32_test]
+    fn test_sync_word_register() {
+        // SX126x reset defaults for private and public networks
+        assert_eq!(sync_word_register(SYNC_WORD), 0x1424);
+        assert_eq!(sync_word_register(LORAWAN_SYNC_WORD), 0x3444);
+    }
+
     #[esp32_test]
     fn test_region_duty_cycle_limiter() {
         let limiter = Region::Eu868.duty_cycle_limiter();
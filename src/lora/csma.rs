@@ -11,6 +11,9 @@
 //! 4. On retry, use exponential backoff (doubles each attempt)
 //! 5. Give up after max retries exceeded
 //!
+//! With [`SensingMode::Cad`], steps 1-3 use the radio's Channel Activity
+//! Detection result instead of an RSSI threshold.
+//!
 //! # Example
 //!
 //! ```
@@ -28,6 +31,29 @@
 //!     CsmaResult::GiveUp => println!("Max retries exceeded"),
 //! }
 //! ```
+//!
+//! # Randomness
+//!
+//! [`Csma::new`] uses a small LCG that needs no allocation, which is fine for
+//! jitter but produces a predictable sequence. [`Csma::with_rng`] takes any
+//! [`RngCore`] instead (e.g. `rand_core::OsRng`, backed by the hardware RNG
+//! on ESP32) so backoff times can't be predicted by other nodes.
+
+use rand_core::RngCore;
+use std::sync::{Arc, Mutex};
+
+/// How the channel is sensed before transmitting.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SensingMode {
+    /// Compare instantaneous RSSI against
+    /// [`rssi_threshold_dbm`](CsmaConfig::rssi_threshold_dbm).
+    #[default]
+    Rssi,
+    /// Use the radio's Channel Activity Detection, which correlates against
+    /// LoRa preamble symbols and catches transmissions too weak or short for
+    /// an RSSI sample. Use [`Csma::try_access_cad`] with this mode.
+    Cad,
+}
 
 /// Configuration for CSMA/CA behavior.
 #[derive(Debug, Clone, Copy)]
@@ -44,6 +70,9 @@ pub struct CsmaConfig {
 
     /// Maximum backoff time in milliseconds (caps exponential growth).
     pub max_backoff_ms: u32,
+
+    /// How the radio senses the channel.
+    pub sensing_mode: SensingMode,
 }
 
 impl Default for CsmaConfig {
@@ -53,6 +82,7 @@ impl Default for CsmaConfig {
             max_retries: 5,
             min_backoff_ms: 10,
             max_backoff_ms: 500,
+            sensing_mode: SensingMode::Rssi,
         }
     }
 }
@@ -66,6 +96,41 @@ impl CsmaConfig {
         }
     }
 
+    /// Config for dense (e.g. urban) deployments with many nodes in range.
+    ///
+    /// - Threshold -75 dBm: distant nodes keep the noise floor around -90 dBm
+    ///   most of the time, so a low threshold would rarely see a clear channel.
+    ///   Only strong nearby transmissions count as busy.
+    /// - 8 retries: contention is expected, so give up later.
+    /// - 20-1000 ms backoff: spreads many contending nodes further apart and
+    ///   covers a full SF7 packet at the upper end.
+    pub fn for_dense_network() -> Self {
+        Self {
+            rssi_threshold_dbm: -75,
+            max_retries: 8,
+            min_backoff_ms: 20,
+            max_backoff_ms: 1000,
+            ..Default::default()
+        }
+    }
+
+    /// Config for sparse (e.g. rural) deployments where contention is rare.
+    ///
+    /// - Threshold -90 dBm (the default): the quiet noise floor leaves room
+    ///   to detect weak distant transmissions.
+    /// - 3 retries: a busy channel is most likely a single long transmission,
+    ///   or interference that retrying won't fix.
+    /// - 5-100 ms backoff: few nodes compete, so short waits keep latency low.
+    pub fn for_sparse_network() -> Self {
+        Self {
+            rssi_threshold_dbm: -90,
+            max_retries: 3,
+            min_backoff_ms: 5,
+            max_backoff_ms: 100,
+            ..Default::default()
+        }
+    }
+
     /// Validate configuration values.
     pub fn validate(&self) -> Result<(), CsmaError> {
         if self.min_backoff_ms == 0 {
@@ -134,12 +199,28 @@ impl std::error::Error for CsmaError {}
 ///
 /// Tracks retry count and generates random backoff times. The state is reset
 /// after a successful transmission.
+///
+/// `Clone` copies the retry counter and the PRNG state, so a clone replays the
+/// same backoff sequence as the original. Reseed the clone with [`seed`](Self::seed)
+/// if it is meant to be an independent instance rather than a snapshot.
+/// A clone of an instance created with [`with_rng`](Self::with_rng) shares
+/// the external RNG, so its backoff sequence is not replayed.
+#[derive(Clone)]
 pub struct Csma {
     config: CsmaConfig,
     retries: u8,
-    /// Simple LCG PRNG state for backoff randomization.
+    /// Random source for backoff randomization.
+    rng: CsmaRng,
+}
+
+/// Random source used for backoff jitter.
+#[derive(Clone)]
+enum CsmaRng {
+    /// Simple LCG PRNG state.
     /// Using a simple PRNG to avoid dependencies and keep it host-testable.
-    rng_state: u32,
+    Lcg(u32),
+    /// Caller-provided RNG (shared between clones).
+    External(Arc<Mutex<dyn RngCore + Send>>),
 }
 
 impl Default for Csma {
@@ -154,17 +235,36 @@ impl Csma {
         Self {
             config,
             retries: 0,
-            // Initialize with a non-zero seed (will be overwritten by seed())
-            rng_state: 0x12345678,
+            // Non-zero fixed seed; reseed with seed() for a different sequence
+            rng: CsmaRng::Lcg(0x12345678),
         }
     }
 
+    /// Create a CSMA instance that draws backoff jitter from `rng`.
+    ///
+    /// Returns an error if the configuration is invalid.
+    pub fn with_rng<R: RngCore + Send + 'static>(
+        config: CsmaConfig,
+        rng: R,
+    ) -> Result<Self, CsmaError> {
+        config.validate()?;
+        Ok(Self {
+            config,
+            retries: 0,
+            rng: CsmaRng::External(Arc::new(Mutex::new(rng))),
+        })
+    }
+
     /// Seed the random number generator.
     ///
-    /// On ESP32, use hardware RNG for the seed. For testing, use a fixed seed.
+    /// Use a fixed seed for reproducible backoff in tests; the radio uses
+    /// [`with_rng`](Self::with_rng) with the hardware RNG instead.
+    /// Has no effect on an instance created with [`with_rng`](Self::with_rng).
     pub fn seed(&mut self, seed: u32) {
-        // Ensure non-zero state
-        self.rng_state = if seed == 0 { 1 } else { seed };
+        if let CsmaRng::Lcg(state) = &mut self.rng {
+            // Ensure non-zero state
+            *state = if seed == 0 { 1 } else { seed };
+        }
     }
 
     /// Check if channel is clear based on RSSI reading.
@@ -189,7 +289,20 @@ impl Csma {
     /// * `CsmaResult::Wait { ms }` - Channel busy, wait before retrying
     /// * `CsmaResult::GiveUp` - Max retries exceeded, drop the packet
     pub fn try_access(&mut self, rssi_dbm: i16) -> CsmaResult {
-        if self.is_channel_clear(rssi_dbm) {
+        self.access(self.is_channel_clear(rssi_dbm))
+    }
+
+    /// Attempt to access the channel based on a CAD result.
+    ///
+    /// Counterpart of [`try_access`](Self::try_access) for
+    /// [`SensingMode::Cad`], with the same retry and backoff behavior.
+    pub fn try_access_cad(&mut self, channel_active: bool) -> CsmaResult {
+        self.access(!channel_active)
+    }
+
+    /// Transmit if clear, otherwise back off or give up.
+    fn access(&mut self, clear: bool) -> CsmaResult {
+        if clear {
             CsmaResult::Transmit
         } else if self.retries >= self.config.max_retries {
             CsmaResult::GiveUp
@@ -207,6 +320,14 @@ impl Csma {
         self.retries = 0;
     }
 
+    /// Capture the current state so it can be restored after an aborted attempt.
+    ///
+    /// Useful for "try-once" semantics: snapshot before a CSMA cycle and
+    /// assign the snapshot back on abort instead of calling [`reset`](Self::reset).
+    pub fn snapshot(&self) -> Csma {
+        self.clone()
+    }
+
     /// Get current retry count.
     pub fn retries(&self) -> u8 {
         self.retries
@@ -217,6 +338,24 @@ impl Csma {
         &self.config
     }
 
+    /// Replace the configuration, keeping the random source.
+    ///
+    /// Resets the retry counter. Returns an error, leaving the current
+    /// configuration in place, if `config` is invalid.
+    pub fn set_config(&mut self, config: CsmaConfig) -> Result<(), CsmaError> {
+        config.validate()?;
+        self.config = config;
+        self.retries = 0;
+        Ok(())
+    }
+
+    /// Retry state and configuration summary, same as the `Debug` output.
+    ///
+    /// Example: `Csma { retries: 2/5, threshold: -90 dBm, backoff_range: [10, 500] ms }`
+    pub fn debug_summary(&self) -> String {
+        format!("{:?}", self)
+    }
+
     /// Calculate random backoff time with exponential growth.
     ///
     /// Backoff window doubles with each retry:
@@ -242,15 +381,35 @@ impl Csma {
         self.config.min_backoff_ms + (random % range)
     }
 
-    /// Simple LCG random number generator.
+    /// Next random value from the configured source.
     ///
-    /// Parameters from Numerical Recipes (good enough for backoff jitter).
+    /// LCG parameters from Numerical Recipes (good enough for backoff jitter).
     fn next_random(&mut self) -> u32 {
-        self.rng_state = self
-            .rng_state
-            .wrapping_mul(1664525)
-            .wrapping_add(1013904223);
-        self.rng_state
+        match &mut self.rng {
+            CsmaRng::Lcg(state) => {
+                *state = state.wrapping_mul(1664525).wrapping_add(1013904223);
+                *state
+            }
+            CsmaRng::External(rng) => rng
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .next_u32(),
+        }
+    }
+}
+
+/// Shows retry state and configuration, but not the RNG state.
+impl std::fmt::Debug for Csma {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Csma {{ retries: {}/{}, threshold: {} dBm, backoff_range: [{}, {}] ms }}",
+            self.retries,
+            self.config.max_retries,
+            self.config.rssi_threshold_dbm,
+            self.config.min_backoff_ms,
+            self.config.max_backoff_ms
+        )
     }
 }
 
@@ -274,6 +433,21 @@ mod tests {
         assert!(config.validate().is_ok());
     }
 
+    #[esp32_test]
+    fn test_deployment_presets() {
+        let dense = CsmaConfig::for_dense_network();
+        assert_eq!(dense.rssi_threshold_dbm, -75);
+        assert_eq!(dense.max_retries, 8);
+        assert_eq!((dense.min_backoff_ms, dense.max_backoff_ms), (20, 1000));
+        assert!(dense.validate().is_ok());
+
+        let sparse = CsmaConfig::for_sparse_network();
+        assert_eq!(sparse.rssi_threshold_dbm, -90);
+        assert_eq!(sparse.max_retries, 3);
+        assert_eq!((sparse.min_backoff_ms, sparse.max_backoff_ms), (5, 100));
+        assert!(sparse.validate().is_ok());
+    }
+
     #[esp32_test]
     fn test_config_validation_zero_min_backoff() {
         let config = CsmaConfig {
@@ -536,6 +710,42 @@ mod tests {
         );
     }
 
+    #[esp32_test]
+    fn test_try_access_cad() {
+        let config = CsmaConfig {
+            sensing_mode: SensingMode::Cad,
+            max_retries: 1,
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+        let mut csma = Csma::new(config);
+        assert_eq!(csma.config().sensing_mode, SensingMode::Cad);
+
+        assert_eq!(csma.try_access_cad(false), CsmaResult::Transmit);
+        assert!(matches!(csma.try_access_cad(true), CsmaResult::Wait { .. }));
+        assert_eq!(csma.try_access_cad(true), CsmaResult::GiveUp);
+    }
+
+    #[esp32_test]
+    fn test_set_config() {
+        let mut csma = Csma::default();
+        csma.seed(12345);
+        let _ = csma.try_access(-50);
+        assert_eq!(csma.retries(), 1);
+
+        let invalid = CsmaConfig {
+            min_backoff_ms: 0,
+            ..Default::default()
+        };
+        assert!(csma.set_config(invalid).is_err());
+        assert_eq!(csma.config().min_backoff_ms, 10);
+        assert_eq!(csma.retries(), 1);
+
+        csma.set_config(CsmaConfig::for_dense_network()).unwrap();
+        assert_eq!(csma.config().rssi_threshold_dbm, -75);
+        assert_eq!(csma.retries(), 0);
+    }
+
     #[esp32_test]
     fn test_custom_threshold() {
         let config = CsmaConfig::with_threshold(-80);
@@ -583,4 +793,78 @@ mod tests {
             assert_eq!(r1, r2);
         }
     }
+
+    /// RNG that always returns the same value, to prove it is used.
+    struct FixedRng(u32);
+
+    impl RngCore for FixedRng {
+        fn next_u32(&mut self) -> u32 {
+            self.0
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            self.0 as u64
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            dest.fill(0);
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    #[esp32_test]
+    fn test_with_rng_uses_external_rng() {
+        let mut csma = Csma::with_rng(CsmaConfig::default(), FixedRng(5)).unwrap();
+        // Seeding is ignored for an external RNG
+        csma.seed(12345);
+
+        // Window is [10, 20) on the first retry, [10, 40) on the second
+        assert_eq!(csma.try_access(-50), CsmaResult::Wait { ms: 15 });
+        assert_eq!(csma.try_access(-50), CsmaResult::Wait { ms: 15 });
+
+        let invalid = CsmaConfig {
+            min_backoff_ms: 0,
+            ..Default::default()
+        };
+        assert!(matches!(
+            Csma::with_rng(invalid, FixedRng(0)),
+            Err(CsmaError::InvalidConfig(_))
+        ));
+    }
+
+    #[esp32_test]
+    fn test_snapshot_restores_state() {
+        let mut csma = Csma::default();
+        csma.seed(42);
+        let _ = csma.try_access(-50);
+        assert_eq!(csma.retries(), 1);
+
+        let snapshot = csma.snapshot();
+        let expected = csma.snapshot().try_access(-50);
+
+        // Abort a retry sequence, then restore
+        let _ = csma.try_access(-50);
+        let _ = csma.try_access(-50);
+        csma = snapshot;
+
+        assert_eq!(csma.retries(), 1);
+        // PRNG state is restored too, so the next backoff matches
+        assert_eq!(csma.try_access(-50), expected);
+    }
+
+    #[esp32_test]
+    fn test_debug_hides_rng_state() {
+        let mut csma = Csma::default();
+        csma.seed(42);
+        let _ = csma.try_access(-50);
+        let _ = csma.try_access(-50);
+
+        let expected = "Csma { retries: 2/5, threshold: -90 dBm, backoff_range: [10, 500] ms }";
+        assert_eq!(format!("{:?}", csma), expected);
+        assert_eq!(csma.debug_summary(), expected);
+    }
 }
@@ -22,8 +22,32 @@
 //! }
 //! ```
 
+use super::airtime::{calculate_airtime_us, LoRaParams};
 use std::time::{Duration, Instant};
 
+/// How a [`DutyCycleLimiter`] treats transmissions over its budget.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LimitMode {
+    /// Regulatory limit: over-budget transmissions are rejected outright.
+    #[default]
+    Hard,
+    /// Application-level limit: over-budget transmissions are throttled,
+    /// telling the caller how long to wait before retrying.
+    Soft,
+}
+
+/// Outcome of [`DutyCycleLimiter::consume`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsumeResult {
+    /// Budget was consumed; transmit now.
+    Allowed,
+    /// Not enough budget yet (soft mode only); retry after this long.
+    Throttled(Duration),
+    /// Not enough budget, and no retry hint (hard mode, or the airtime
+    /// exceeds the full budget and can never fit).
+    Rejected,
+}
+
 /// Duty cycle limiter using token bucket algorithm.
 ///
 /// This limiter ensures LoRa transmissions comply with regulatory duty cycle
@@ -31,6 +55,15 @@ use std::time::{Duration, Instant};
 ///
 /// The token bucket algorithm allows bursty traffic while maintaining the
 /// average duty cycle over the configured time window.
+///
+/// # Thread Safety
+///
+/// Every method that touches the budget takes `&mut self`, so the borrow
+/// checker already rules out concurrent access: a refill and a consume can
+/// never interleave. The limiter is `Send` and lives inside `LoRaRadio`, which
+/// tasks share behind a mutex. Code that needs a limiter on its own across
+/// tasks should wrap it in `Mutex<DutyCycleLimiter>` rather than making the
+/// fields atomic, since `try_consume` must refill and subtract as one step.
 pub struct DutyCycleLimiter {
     /// Maximum budget in microseconds (total allowed airtime per window)
     budget_us: u64,
@@ -40,6 +73,8 @@ pub struct DutyCycleLimiter {
     last_refill: Instant,
     /// Window duration for duty cycle calculation
     window: Duration,
+    /// How over-budget transmissions are reported by `consume`
+    mode: LimitMode,
 }
 
 impl DutyCycleLimiter {
@@ -69,9 +104,21 @@ impl DutyCycleLimiter {
             remaining_us: budget_us,
             last_refill: Instant::now(),
             window,
+            mode: LimitMode::Hard,
         }
     }
 
+    /// Set how over-budget transmissions are reported (default [`LimitMode::Hard`]).
+    pub fn with_mode(mut self, mode: LimitMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Get the limit mode.
+    pub fn mode(&self) -> LimitMode {
+        self.mode
+    }
+
     /// Attempt to consume airtime budget.
     ///
     /// Returns `true` if transmission is allowed (budget was consumed),
@@ -90,6 +137,54 @@ impl DutyCycleLimiter {
         }
     }
 
+    /// Attempt to consume airtime budget, reporting how to proceed if not.
+    ///
+    /// Like [`try_consume`](Self::try_consume), but in [`LimitMode::Soft`]
+    /// an over-budget request returns [`ConsumeResult::Throttled`] with the
+    /// time until the budget will cover it, so the caller can queue and
+    /// retry instead of dropping the packet.
+    pub fn consume(&mut self, airtime_us: u64) -> ConsumeResult {
+        if self.try_consume(airtime_us) {
+            return ConsumeResult::Allowed;
+        }
+        match (self.mode, self.time_until_budget(airtime_us)) {
+            (LimitMode::Soft, Some(wait)) => ConsumeResult::Throttled(wait),
+            _ => ConsumeResult::Rejected,
+        }
+    }
+
+    /// Time until the budget will cover `needed_us` of airtime.
+    ///
+    /// Returns `Some(Duration::ZERO)` if it already does, and `None` if
+    /// `needed_us` exceeds the full budget and can never be covered.
+    pub fn time_until_budget(&self, needed_us: u64) -> Option<Duration> {
+        let window_us = self.window.as_micros();
+        if needed_us > self.budget_us || window_us == 0 {
+            return None;
+        }
+        let deficit_us = needed_us.saturating_sub(self.available()) as u128;
+        let wait_us = (deficit_us * window_us).div_ceil(self.budget_us.max(1) as u128);
+        Some(Duration::from_micros(wait_us as u64))
+    }
+
+    /// Correct a reservation made with [`try_consume`](Self::try_consume)
+    /// once the measured on-air time is known.
+    ///
+    /// Budget is deducted from the calculated airtime before transmitting;
+    /// this charges the overrun if the radio was on air longer, or refunds
+    /// the difference if it was shorter.
+    pub fn record_actual_airtime(&mut self, reserved_us: u64, actual_us: u64) {
+        self.refill();
+        if actual_us > reserved_us {
+            self.remaining_us = self.remaining_us.saturating_sub(actual_us - reserved_us);
+        } else {
+            self.remaining_us = self
+                .remaining_us
+                .saturating_add(reserved_us - actual_us)
+                .min(self.budget_us);
+        }
+    }
+
     /// Get remaining budget in microseconds.
     pub fn remaining(&mut self) -> u64 {
         self.refill();
@@ -110,6 +205,37 @@ impl DutyCycleLimiter {
         self.budget_us
     }
 
+    /// Get the airtime in microseconds that accrues over an upcoming window.
+    ///
+    /// This is the duty-cycle share of `window` (e.g., 1% of 10 minutes is
+    /// 6 seconds), capped at the maximum budget since the bucket can never
+    /// hold more than that.
+    pub fn budget_for_duration(&self, window: Duration) -> u64 {
+        let window_us = self.window.as_micros();
+        if window_us == 0 {
+            return 0;
+        }
+        let accrued = self.budget_us as u128 * window.as_micros() / window_us;
+        accrued.min(self.budget_us as u128) as u64
+    }
+
+    /// Check whether a packet could be transmitted right now.
+    ///
+    /// Computes the packet's time-on-air and compares it against the remaining
+    /// budget plus whatever has accrued since the last refill. Does not consume
+    /// any budget; use [`try_consume`](Self::try_consume) to actually transmit.
+    pub fn can_transmit(&self, payload_len: usize, params: &LoRaParams) -> bool {
+        calculate_airtime_us(payload_len, params) <= self.available()
+    }
+
+    /// Remaining budget plus whatever has accrued since the last refill.
+    fn available(&self) -> u64 {
+        let elapsed = Instant::now().saturating_duration_since(self.last_refill);
+        self.remaining_us
+            .saturating_add(self.budget_for_duration(elapsed))
+            .min(self.budget_us)
+    }
+
     /// Refill budget based on elapsed time.
     fn refill(&mut self) {
         let now = Instant::now();
@@ -132,6 +258,32 @@ impl DutyCycleLimiter {
     }
 }
 
+/// Time until a drained budget refills to `target_us`.
+///
+/// Uses the same continuous refill as [`DutyCycleLimiter`]: the full budget
+/// (`duty_cycle_pct` of `window_secs`) accrues evenly over the window.
+/// Returns zero if `budget_us` already covers the target, and
+/// `Duration::MAX` if the target exceeds the full budget and can never be
+/// reached. Useful for "next transmission possible in ..." messages.
+pub fn time_until_budget_refills(
+    budget_us: u64,
+    target_us: u64,
+    duty_cycle_pct: f32,
+    window_secs: u64,
+) -> Duration {
+    if budget_us >= target_us {
+        return Duration::ZERO;
+    }
+    let window_us = window_secs as u128 * 1_000_000;
+    let capacity_us = (window_us as f64 * duty_cycle_pct as f64 / 100.0) as u128;
+    if capacity_us == 0 || target_us as u128 > capacity_us {
+        return Duration::MAX;
+    }
+    let deficit_us = (target_us - budget_us) as u128;
+    let wait_us = (deficit_us * window_us).div_ceil(capacity_us);
+    Duration::from_micros(wait_us as u64)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -188,6 +340,32 @@ mod tests {
         assert!(limiter.remaining() < budget / 100);
     }
 
+    #[esp32_test]
+    fn test_record_actual_airtime() {
+        let mut limiter = DutyCycleLimiter::new(1.0, Duration::from_secs(3600));
+        let budget = limiter.budget();
+
+        // Overrun of 50ms is charged on top of the reservation
+        assert!(limiter.try_consume(1_000_000));
+        limiter.record_actual_airtime(1_000_000, 1_050_000);
+        let remaining = limiter.remaining();
+        assert!(remaining <= budget - 1_050_000 + 10_000); // Allow 10ms of refill
+        assert!(remaining >= budget - 1_050_000);
+
+        // A shorter transmission refunds the difference, capped at the budget
+        limiter.record_actual_airtime(1_000_000, 0);
+        assert!(limiter.remaining() >= budget - 50_000);
+        limiter.record_actual_airtime(budget, 0);
+        assert_eq!(limiter.remaining(), budget);
+        limiter.record_actual_airtime(u64::MAX, 0);
+        assert_eq!(limiter.remaining(), budget);
+
+        // Overrun past an empty budget saturates instead of underflowing
+        assert!(limiter.try_consume(budget));
+        limiter.record_actual_airtime(0, u64::MAX);
+        assert!(limiter.remaining() < budget / 100);
+    }
+
     #[esp32_test]
     fn test_remaining_percent() {
         let mut limiter = DutyCycleLimiter::new(1.0, Duration::from_secs(3600));
@@ -236,4 +414,124 @@ mod tests {
         // Should be at or near zero (might have rounding remainder)
         assert!(limiter.remaining() < chunk);
     }
+
+    #[esp32_test]
+    fn test_shared_behind_mutex_never_overspends() {
+        use std::sync::{Arc, Mutex};
+
+        let limiter = Arc::new(Mutex::new(DutyCycleLimiter::new(
+            1.0,
+            Duration::from_secs(3600),
+        )));
+        let budget = limiter.lock().unwrap().budget();
+        let chunk = budget / 100;
+
+        // Four threads race for twice the available budget
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let limiter = limiter.clone();
+                std::thread::spawn(move || {
+                    (0..50)
+                        .filter(|_| limiter.lock().unwrap().try_consume(chunk))
+                        .count()
+                })
+            })
+            .collect();
+        let granted: usize = handles.into_iter().map(|h| h.join().unwrap()).sum();
+
+        // Refill during the test is far below one chunk (1% of 36s per second)
+        assert_eq!(granted, 100);
+    }
+
+    #[esp32_test]
+    fn test_budget_for_duration() {
+        let limiter = DutyCycleLimiter::new(1.0, Duration::from_secs(3600));
+
+        // 1% of 10 minutes = 6 seconds
+        assert_eq!(
+            limiter.budget_for_duration(Duration::from_secs(600)),
+            6_000_000
+        );
+        assert_eq!(limiter.budget_for_duration(Duration::ZERO), 0);
+
+        // Longer than the window is capped at the full budget
+        assert_eq!(
+            limiter.budget_for_duration(Duration::from_secs(7200)),
+            limiter.budget()
+        );
+    }
+
+    #[esp32_test]
+    fn test_can_transmit() {
+        let params = LoRaParams::default();
+        let mut limiter = DutyCycleLimiter::new(1.0, Duration::from_secs(3600));
+        assert!(limiter.can_transmit(100, &params));
+
+        // Checking must not consume budget
+        let budget = limiter.budget();
+        assert!(limiter.remaining() >= budget - 10_000);
+
+        // Drain the budget; a max-size packet no longer fits
+        assert!(limiter.try_consume(budget));
+        assert!(!limiter.can_transmit(500, &params));
+
+        // Zero budget never allows transmission
+        let limiter = DutyCycleLimiter::new(0.0, Duration::from_secs(3600));
+        assert!(!limiter.can_transmit(1, &params));
+    }
+
+    #[esp32_test]
+    fn test_time_until_budget_refills() {
+        // 1% of 100s = 1s of airtime, refilling at 10ms per second
+        assert_eq!(
+            time_until_budget_refills(50_000, 10_000, 1.0, 100),
+            Duration::ZERO
+        );
+        assert_eq!(
+            time_until_budget_refills(0, 10_000, 1.0, 100),
+            Duration::from_secs(1)
+        );
+        assert_eq!(
+            time_until_budget_refills(5_000, 10_000, 1.0, 100),
+            Duration::from_millis(500)
+        );
+
+        // Target beyond the full budget, or no budget at all
+        assert_eq!(
+            time_until_budget_refills(0, 2_000_000, 1.0, 100),
+            Duration::MAX
+        );
+        assert_eq!(time_until_budget_refills(0, 1, 0.0, 100), Duration::MAX);
+    }
+
+    #[esp32_test]
+    fn test_time_until_budget() {
+        // 1% of 100s = 1s of airtime, refilling at 10ms per second
+        let mut limiter = DutyCycleLimiter::new(1.0, Duration::from_secs(100));
+        assert_eq!(limiter.time_until_budget(500_000), Some(Duration::ZERO));
+        assert_eq!(limiter.time_until_budget(1_000_001), None);
+
+        assert!(limiter.try_consume(limiter.budget()));
+        let wait = limiter.time_until_budget(10_000).unwrap();
+        // About 1s, minus whatever accrued since draining
+        assert!(wait > Duration::from_millis(900) && wait <= Duration::from_secs(1));
+    }
+
+    #[esp32_test]
+    fn test_consume_hard_vs_soft() {
+        let mut hard = DutyCycleLimiter::new(1.0, Duration::from_secs(100));
+        assert_eq!(hard.mode(), LimitMode::Hard);
+        assert_eq!(hard.consume(1_000_000), ConsumeResult::Allowed);
+        assert_eq!(hard.consume(10_000), ConsumeResult::Rejected);
+
+        let mut soft =
+            DutyCycleLimiter::new(1.0, Duration::from_secs(100)).with_mode(LimitMode::Soft);
+        assert_eq!(soft.consume(1_000_000), ConsumeResult::Allowed);
+        assert!(matches!(
+            soft.consume(10_000),
+            ConsumeResult::Throttled(wait) if wait > Duration::ZERO
+        ));
+        // Larger than the full budget: waiting won't help
+        assert_eq!(soft.consume(2_000_000), ConsumeResult::Rejected);
+    }
 }
@@ -11,35 +11,70 @@
 //! This worker prioritizes TX over RX since we control when to transmit but
 //! cannot control when packets arrive.
 //!
-//! # Blocking Bridge Pattern
+//! # Announce Rate Limiting
 //!
-//! The SX1262 radio driver uses blocking SPI calls. To avoid blocking the
-//! async runtime, each radio operation runs in `tokio::task::spawn_blocking`.
+//! Announces are rebroadcast through an [`AnnounceLimiter`]. A burst beyond
+//! what the token bucket allows is queued and drained by the worker loop as
+//! tokens refill, instead of failing with duty cycle errors. Tokens refill
+//! at the airtime of a maximum-size packet on the radio's channel and header
+//! mode, read from [`LoRaRadio::lora_params`] when the worker starts.
+//!
+//! # Idle Sleep
+//!
+//! With [`LoRaInterface::set_idle_sleep`], the radio is put to sleep once no
+//! packet has been sent or received for the idle timeout. It then sleeps for
+//! [`SLEEP_WINDOW_MS`] at a time and wakes for one receive window in between,
+//! so packets arriving while asleep are missed and outgoing packets wait for
+//! the end of the sleep window. Disabled by default.
+//!
+//! # Receive Filter
+//!
+//! A gateway hears every packet in range. [`LoRaInterface::set_receive_filter`]
+//! installs a callback that sees each received packet before it is parsed;
+//! rejected packets never reach the transport's crypto pipeline. They still
+//! count as received, and are also counted as `lora_filtered_rx` in the stats.
+//!
+//! # Radio Thread
+//!
+//! The SX1262 radio driver uses blocking SPI calls, and its DIO1 interrupt
+//! must be subscribed and re-enabled from one thread. The radio is moved onto
+//! a [`RadioThread`] and the worker awaits its replies, so the async runtime
+//! is never blocked and the driver never migrates between threads.
+//!
+//! The worker queues transmissions with [`RadioThread::queue_transmit`] and
+//! doesn't wait for each one to be sent, which allows at most
+//! [`MAX_PENDING_TX`](super::MAX_PENDING_TX) packets in flight across all
+//! radio handles. Once that many are pending, the worker stops pulling from
+//! the transport's channel, so the backlog stays in the transport instead of
+//! growing behind the radio.
 //!
 //! # Usage
 //!
 //! ```ignore
 //! use reticulum_rs_esp32::lora::{LoRaRadio, LoRaInterface, Region};
 //!
-//! // Create and initialize the radio
-//! let mut radio = LoRaRadio::new(spi, sclk, mosi, miso, cs, reset, busy, dio1, Region::Eu868)?;
-//! radio.init()?;
+//! // Create the radio
+//! let radio = LoRaRadio::new(spi, sclk, mosi, miso, cs, reset, busy, dio1, Region::Eu868)?;
 //!
-//! // Wrap it in the interface adapter
-//! let lora_iface = LoRaInterface::new(radio);
+//! // Wrap it in the interface adapter (initializes it on the radio thread)
+//! let lora_iface = LoRaInterface::new(radio)?;
 //!
 //! // Register with transport
 //! transport.iface_manager().lock().await.spawn(lora_iface, LoRaInterface::spawn);
 //! ```
 
+use super::announce_limiter::AnnounceLimiter;
 use super::config::LORA_MTU;
-use super::radio::{LoRaRadio, ReceivedPacket};
+use super::radio::{LoRaRadio, RadioError, ReceivedPacket};
+use super::radio_thread::RadioThread;
+use crate::network::NodeStats;
 use log::{debug, error, info, warn};
 use reticulum::buffer::{InputBuffer, OutputBuffer};
 use reticulum::iface::{Interface, InterfaceContext, RxMessage};
-use reticulum::packet::Packet;
+use reticulum::packet::{Packet, PacketType};
 use reticulum::serde::Serialize;
-use std::time::Duration;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 /// LoRa receive timeout per poll (ms).
 ///
@@ -50,12 +85,13 @@ const RX_TIMEOUT_MS: u32 = 50;
 /// Delay after an error before retrying (ms).
 const ERROR_BACKOFF_MS: u64 = 100;
 
-/// Maximum consecutive task panics before shutting down the interface.
+/// How long the radio sleeps between receive windows once idle (ms).
+pub const SLEEP_WINDOW_MS: u64 = 1000;
+
+/// Callback deciding whether a received packet is passed to the transport.
 ///
-/// If spawn_blocking tasks panic repeatedly, something is seriously wrong
-/// (likely hardware issue or memory corruption). We shut down gracefully
-/// rather than continue in a potentially corrupted state.
-const MAX_CONSECUTIVE_PANICS: u32 = 3;
+/// Shared so a respawned worker keeps the filter.
+type ReceiveFilter = Arc<dyn Fn(&ReceivedPacket) -> bool + Send + Sync>;
 
 /// LoRa interface adapter for reticulum-rs transport.
 ///
@@ -64,49 +100,130 @@ const MAX_CONSECUTIVE_PANICS: u32 = 3;
 /// - Async polling for received packets
 /// - Transmitting packets from the transport layer
 /// - Error handling and logging
-pub struct LoRaInterface<'d> {
-    radio: LoRaRadio<'d>,
+pub struct LoRaInterface {
+    radio: RadioThread,
+    /// Idle time after which the radio sleeps between receive windows.
+    idle_sleep: Option<Duration>,
+    /// Packets for which this returns false are dropped before parsing.
+    receive_filter: Option<ReceiveFilter>,
+    /// Node statistics updated with received and filtered packets.
+    stats: Option<Arc<NodeStats>>,
 }
 
-impl<'d> LoRaInterface<'d> {
-    /// Create a new LoRa interface from an initialized radio.
+impl LoRaInterface {
+    /// Create a new LoRa interface, moving the radio onto its own thread.
     ///
-    /// The radio must be initialized before passing to this function.
-    pub fn new(radio: LoRaRadio<'d>) -> Self {
-        Self { radio }
+    /// The radio is initialized on that thread, so calling
+    /// [`LoRaRadio::init`] beforehand is not required.
+    pub fn new(radio: LoRaRadio<'static>) -> Result<Self, RadioError> {
+        Ok(Self {
+            radio: RadioThread::spawn(radio)?,
+            idle_sleep: None,
+            receive_filter: None,
+            stats: None,
+        })
     }
 
-    /// Get a reference to the underlying radio.
-    pub fn radio(&self) -> &LoRaRadio<'d> {
-        &self.radio
+    /// Sleep the radio between receive windows after `idle_timeout` without
+    /// traffic, or never with `None` (the default).
+    ///
+    /// Takes effect when the worker is spawned.
+    pub fn set_idle_sleep(&mut self, idle_timeout: Option<Duration>) {
+        self.idle_sleep = idle_timeout;
+    }
+
+    /// Drop received packets for which `filter` returns false.
+    ///
+    /// The filter runs before the packet is parsed, so it sees the raw bytes;
+    /// a typical filter checks the Reticulum header flags in the first byte.
+    /// Takes effect when the worker is spawned.
+    ///
+    /// ```ignore
+    /// // Ignore packets carrying an interface access code (IFAC flag)
+    /// lora_iface.set_receive_filter(|packet| {
+    ///     packet.data.first().is_some_and(|flags| flags & 0x80 == 0)
+    /// });
+    /// ```
+    pub fn set_receive_filter<F>(&mut self, filter: F)
+    where
+        F: Fn(&ReceivedPacket) -> bool + Send + Sync + 'static,
+    {
+        self.receive_filter = Some(Arc::new(filter));
     }
 
-    /// Get a mutable reference to the underlying radio.
-    pub fn radio_mut(&mut self) -> &mut LoRaRadio<'d> {
-        &mut self.radio
+    /// Attach node statistics to count received and filtered packets in.
+    ///
+    /// When the worker is spawned the stats are also handed to the radio
+    /// with [`LoRaRadio::set_stats`]. The radio records transmit airtime and
+    /// packets dropped for a failed CRC (`lora_crc_errors`), and marks LoRa
+    /// as configured and initialized for `/health`.
+    pub fn set_stats(&mut self, stats: Arc<NodeStats>) {
+        self.stats = Some(stats);
+    }
+
+    /// Get a handle to the radio thread.
+    ///
+    /// Driver settings such as the channel or sync word are changed through
+    /// [`RadioThread::with_radio`].
+    pub fn radio(&self) -> &RadioThread {
+        &self.radio
     }
 
     /// Spawn the LoRa interface worker task.
     ///
     /// This function runs the main interface loop that:
-    /// 1. Checks for outgoing packets from the transport
-    /// 2. Transmits any pending outgoing packets
+    /// 1. Checks for queued announces and outgoing packets from the transport
+    /// 2. Transmits any pending outgoing packets, deferring rate-limited announces
     /// 3. Polls for incoming packets from the radio
     /// 4. Forwards received packets to the transport
     ///
-    /// The loop runs until cancellation is signaled or too many panics occur.
-    pub async fn spawn(context: InterfaceContext<LoRaInterface<'d>>)
-    where
-        'd: 'static,
-    {
+    /// The loop runs until cancellation is signaled or the radio thread stops.
+    pub async fn spawn(context: InterfaceContext<LoRaInterface>) {
         let iface_address = context.channel.address;
         info!("LoRa interface started: {:?}", iface_address);
 
         // Split the channel to get ownership of sender/receiver
         let (rx_channel, mut tx_channel) = context.channel.split();
 
-        // Track consecutive panics to detect persistent hardware issues
-        let mut consecutive_panics: u32 = 0;
+        // Clone the handle once so radio calls never hold the interface lock
+        let (radio, idle_sleep, receive_filter, stats) = {
+            let mut iface = match context.inner.lock() {
+                Ok(guard) => guard,
+                Err(poisoned) => {
+                    warn!("LoRa interface mutex was poisoned, recovering");
+                    poisoned.into_inner()
+                }
+            };
+            (
+                iface.radio.clone(),
+                iface.idle_sleep,
+                iface.receive_filter.clone(),
+                iface.stats.clone(),
+            )
+        };
+
+        // Hand the stats to the radio and read the link it is configured for
+        let setup_stats = stats.clone();
+        let params = match radio
+            .with_radio(move |r| {
+                if let Some(stats) = setup_stats {
+                    r.set_stats(stats);
+                }
+                r.lora_params()
+            })
+            .await
+        {
+            Ok(params) => params,
+            Err(_) => {
+                error!("LoRa radio thread stopped, shutting down interface");
+                return;
+            }
+        };
+
+        // Smooths announce bursts at the rate the configured link can carry
+        // them; owned by the loop so draining needs no lock
+        let mut announce_limiter = AnnounceLimiter::for_params(&params);
+        let mut last_activity = Instant::now();
 
         loop {
             // Check for cancellation
@@ -115,60 +232,59 @@ impl<'d> LoRaInterface<'d> {
                 break;
             }
 
-            // Check for too many consecutive panics
-            if consecutive_panics >= MAX_CONSECUTIVE_PANICS {
-                error!(
-                    "LoRa interface shutting down after {} consecutive task panics - \
-                     possible hardware issue or memory corruption",
-                    consecutive_panics
-                );
-                break;
-            }
-
-            // Priority 1: Handle TX (we control when to transmit)
-            if let Ok(tx_msg) = tx_channel.try_recv() {
-                let packet = tx_msg.packet;
-                // Serialize packet to raw bytes for transmission
-                let mut buffer = [0u8; LORA_MTU];
-                let mut output = OutputBuffer::new(&mut buffer);
-                if let Err(e) = packet.serialize(&mut output) {
-                    warn!("Failed to serialize packet: {:?}", e);
-                    continue;
-                }
-                let data = output.as_slice().to_vec();
-                debug!("LoRa TX: {} bytes", data.len());
+            // Priority 1: Handle TX (we control when to transmit), starting
+            // with announces that were deferred for lack of tokens
+            let next_tx = match announce_limiter.pop_ready() {
+                Some(data) => Some(data),
+                None => match tx_channel.try_recv() {
+                    Ok(tx_msg) => {
+                        let packet = tx_msg.packet;
+                        // Serialize packet to raw bytes for transmission
+                        let mut buffer = [0u8; LORA_MTU];
+                        let mut output = OutputBuffer::new(&mut buffer);
+                        if let Err(e) = packet.serialize(&mut output) {
+                            warn!("Failed to serialize packet: {:?}", e);
+                            continue;
+                        }
+                        let data = output.as_slice().to_vec();
 
-                let inner = context.inner.clone();
-                let result = tokio::task::spawn_blocking(move || {
-                    // Handle poisoned mutex - recover by taking the inner value
-                    let mut iface = match inner.lock() {
-                        Ok(guard) => guard,
-                        Err(poisoned) => {
-                            warn!("LoRa interface mutex was poisoned, recovering");
-                            poisoned.into_inner()
+                        if packet.header.packet_type == PacketType::Announce
+                            && !announce_limiter.try_take()
+                        {
+                            if announce_limiter.enqueue(data).is_some() {
+                                warn!("LoRa announce queue full, dropped oldest announce");
+                            }
+                            debug!(
+                                "LoRa announce deferred ({} queued)",
+                                announce_limiter.queued()
+                            );
+                            continue;
                         }
-                    };
-                    iface.radio.transmit(&data)
-                })
-                .await;
-
-                match result {
-                    Ok(Ok(())) => {
-                        debug!("LoRa TX complete");
-                        consecutive_panics = 0; // Reset on success
+                        Some(data)
                     }
-                    Ok(Err(e)) => {
-                        warn!("LoRa TX error: {}", e);
-                        consecutive_panics = 0; // Radio responded, just an error
+                    Err(_) => None,
+                },
+            };
+
+            if let Some(data) = next_tx {
+                debug!("LoRa TX: {} bytes", data.len());
+
+                last_activity = Instant::now();
+                // Waits only while MAX_PENDING_TX packets are already queued
+                match radio.queue_transmit(data).await {
+                    Ok(pending) => {
+                        tokio::spawn(async move {
+                            match pending.wait().await {
+                                Ok(()) => debug!("LoRa TX complete"),
+                                Err(e) => warn!("LoRa TX error: {}", e),
+                            }
+                        });
                     }
-                    Err(e) => {
-                        consecutive_panics += 1;
-                        error!(
-                            "LoRa TX task panicked ({}/{}): {}",
-                            consecutive_panics, MAX_CONSECUTIVE_PANICS, e
-                        );
-                        tokio::time::sleep(Duration::from_millis(ERROR_BACKOFF_MS)).await;
+                    Err(RadioError::ThreadStopped) => {
+                        error!("LoRa radio thread stopped, shutting down interface");
+                        break;
                     }
+                    Err(e) => warn!("LoRa TX error: {}", e),
                 }
 
                 // Yield to allow other async tasks to run before checking for more TX
@@ -176,45 +292,48 @@ impl<'d> LoRaInterface<'d> {
                 continue;
             }
 
+            // Idle: sleep through a window, then wake for the next receive
+            if idle_sleep.is_some_and(|idle| last_activity.elapsed() >= idle) {
+                match sleep_window(&radio).await {
+                    Ok(()) => {}
+                    Err(RadioError::ThreadStopped) => {
+                        error!("LoRa radio thread stopped, shutting down interface");
+                        break;
+                    }
+                    Err(e) => warn!("LoRa sleep error: {}", e),
+                }
+            }
+
             // Priority 2: Poll for RX
-            let rx_result = {
-                let inner = context.inner.clone();
-                tokio::task::spawn_blocking(move || {
-                    // Handle poisoned mutex
-                    let mut iface = match inner.lock() {
-                        Ok(guard) => guard,
-                        Err(poisoned) => {
-                            warn!("LoRa interface mutex was poisoned, recovering");
-                            poisoned.into_inner()
+            match radio.receive(RX_TIMEOUT_MS).await {
+                Ok(Some(received)) => {
+                    last_activity = Instant::now();
+                    if let Some(stats) = &stats {
+                        stats.lora.record_rx();
+                    }
+                    let accepted = receive_filter
+                        .as_ref()
+                        .is_none_or(|filter| filter(&received));
+                    if !accepted {
+                        debug!("LoRa RX: {} bytes dropped by filter", received.data.len());
+                        if let Some(stats) = &stats {
+                            stats.lora_radio.record_filtered_rx();
                         }
-                    };
-                    iface.radio.receive(RX_TIMEOUT_MS)
-                })
-                .await
-            };
-
-            match rx_result {
-                Ok(Ok(Some(received))) => {
-                    consecutive_panics = 0; // Reset on success
+                        continue;
+                    }
                     if let Err(e) = handle_rx_packet(&rx_channel, iface_address, received).await {
                         warn!("Failed to forward RX packet: {}", e);
                     }
                 }
-                Ok(Ok(None)) => {
+                Ok(None) => {
                     // No packet received, normal operation
-                    consecutive_panics = 0; // Radio responded normally
                 }
-                Ok(Err(e)) => {
-                    warn!("LoRa RX error: {}", e);
-                    consecutive_panics = 0; // Radio responded, just an error
-                    tokio::time::sleep(Duration::from_millis(ERROR_BACKOFF_MS)).await;
+                Err(RadioError::ThreadStopped) => {
+                    error!("LoRa radio thread stopped, shutting down interface");
+                    break;
                 }
                 Err(e) => {
-                    consecutive_panics += 1;
-                    error!(
-                        "LoRa RX task panicked ({}/{}): {}",
-                        consecutive_panics, MAX_CONSECUTIVE_PANICS, e
-                    );
+                    warn!("LoRa RX error: {}", e);
                     tokio::time::sleep(Duration::from_millis(ERROR_BACKOFF_MS)).await;
                 }
             }
@@ -224,12 +343,19 @@ impl<'d> LoRaInterface<'d> {
     }
 }
 
-impl<'d> Interface for LoRaInterface<'d> {
+impl Interface for LoRaInterface {
     fn mtu() -> usize {
         LORA_MTU
     }
 }
 
+/// Sleep the radio for one [`SLEEP_WINDOW_MS`] window, then wake it.
+async fn sleep_window(radio: &RadioThread) -> Result<(), RadioError> {
+    radio.sleep().await?;
+    tokio::time::sleep(Duration::from_millis(SLEEP_WINDOW_MS)).await;
+    radio.wake().await
+}
+
 /// Handle a received packet by forwarding it to the transport.
 async fn handle_rx_packet(
     rx_channel: &reticulum::iface::InterfaceRxSender,
@@ -237,10 +363,11 @@ async fn handle_rx_packet(
     received: ReceivedPacket,
 ) -> Result<(), String> {
     debug!(
-        "LoRa RX: {} bytes, RSSI {} dBm, SNR {} dB",
+        "LoRa RX: {} bytes, RSSI {} dBm, SNR {} dB, {} MHz",
         received.data.len(),
         received.rssi,
-        received.snr
+        received.snr,
+        received.channel.label
     );
 
     // Validate packet size before parsing
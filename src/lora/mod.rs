@@ -5,10 +5,13 @@
 //! - [`duty_cycle`]: Duty cycle limiter for regulatory compliance
 //! - [`airtime`]: Time-on-air calculation for LoRa packets
 //! - [`csma`]: CSMA/CA collision avoidance for shared frequencies
+//! - [`announce_limiter`]: Rate limiting for announce rebroadcasts
 //! - [`radio`]: SX1262 radio driver (ESP32 only)
+//! - [`radio_thread`]: Dedicated thread that owns the radio (ESP32 only)
 //! - [`iface`]: Reticulum-rs transport interface adapter (ESP32 only)
 
 mod airtime;
+mod announce_limiter;
 mod config;
 mod csma;
 mod duty_cycle;
@@ -17,16 +20,21 @@ mod duty_cycle;
 mod iface;
 #[cfg(feature = "esp32")]
 mod radio;
+#[cfg(feature = "esp32")]
+mod radio_thread;
 
-pub use airtime::{calculate_airtime_ms, calculate_airtime_us, LoRaParams};
+pub use airtime::{calculate_airtime_ms, calculate_airtime_us, LoRaParamError, LoRaParams};
+pub use announce_limiter::{AnnounceLimiter, ANNOUNCE_BURST, ANNOUNCE_QUEUE_LEN};
 pub use config::{
-    Region, BANDWIDTH_HZ, CODING_RATE, LORA_MTU, LOW_DATA_RATE_OPTIMIZE, PREAMBLE_LENGTH,
-    SPREADING_FACTOR, SYNC_WORD, TX_POWER,
+    sync_word_register, LoRaChannel, Region, BANDWIDTH_HZ, CODING_RATE, LORAWAN_SYNC_WORD,
+    LORA_MTU, LOW_DATA_RATE_OPTIMIZE, PREAMBLE_LENGTH, SPREADING_FACTOR, SYNC_WORD, TX_POWER,
 };
-pub use csma::{Csma, CsmaConfig, CsmaError, CsmaResult};
-pub use duty_cycle::DutyCycleLimiter;
+pub use csma::{Csma, CsmaConfig, CsmaError, CsmaResult, SensingMode};
+pub use duty_cycle::{time_until_budget_refills, ConsumeResult, DutyCycleLimiter, LimitMode};
 
 #[cfg(feature = "esp32")]
-pub use iface::LoRaInterface;
+pub use iface::{LoRaInterface, SLEEP_WINDOW_MS};
+#[cfg(feature = "esp32")]
+pub use radio::{CadResult, LoRaRadio, RadioError, ReceivedPacket, SelfTestReport};
 #[cfg(feature = "esp32")]
-pub use radio::{LoRaRadio, RadioError, ReceivedPacket};
+pub use radio_thread::{PendingTx, RadioThread, MAX_PENDING_TX};
@@ -16,12 +16,13 @@
 //! | DIO1 | 1 | Interrupt |
 
 use super::config::{
-    Region, BANDWIDTH_HZ, LORA_MTU, LOW_DATA_RATE_OPTIMIZE, PREAMBLE_LENGTH, SPREADING_FACTOR,
-    TX_POWER,
+    sync_word_register, LoRaChannel, Region, LORA_MTU, LOW_DATA_RATE_OPTIMIZE, PREAMBLE_LENGTH,
+    SPREADING_FACTOR, SYNC_WORD, TX_POWER,
 };
-use super::csma::{Csma, CsmaConfig, CsmaResult};
+use super::csma::{Csma, CsmaConfig, CsmaError, CsmaResult, SensingMode};
 use super::{calculate_airtime_us, DutyCycleLimiter, LoRaParams};
-use esp_idf_hal::delay::FreeRtos;
+use crate::network::NodeStats;
+use esp_idf_hal::delay::{Ets, FreeRtos};
 use esp_idf_hal::gpio::{Gpio1, Gpio10, Gpio4, Gpio5, Input, InterruptType, Output, PinDriver};
 use esp_idf_hal::peripheral::Peripheral;
 use esp_idf_hal::spi::config::Config as SpiConfig;
@@ -32,8 +33,9 @@ use esp_idf_hal::units::FromValueType;
 use log::{debug, info, warn};
 use regiface::{Command, NoParameters, ToByteArray};
 use std::fmt;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use sx1262::{
     ClearIrqStatus, Device, DeviceSelect, DioIrqConfig, GetIrqStatus, GetPacketStatus,
     GetRxBufferStatus, IrqMask, PaConfig, PacketParams, PacketType, RampTime, RfFrequencyConfig,
@@ -48,6 +50,12 @@ use sx1262::{
 /// task calls `recv_front(timeout)` to wait.
 ///
 /// Uses capacity 2 to handle rapid interrupt bursts without losing signals.
+///
+/// A FreeRTOS task notification (`esp_idf_hal::task::notification::Notification`)
+/// would be lighter, but it is bound to the task that created it and is not
+/// `Send`. The signal is created in [`LoRaRadio::new`] on the caller's thread,
+/// before the radio moves to its `RadioThread`, so a notification created here
+/// would wake the wrong task.
 #[derive(Clone)]
 struct IrqSignal(Arc<Queue<()>>);
 
@@ -80,6 +88,18 @@ impl IrqSignal {
 /// Maximum time to wait for radio to become ready (ms).
 const BUSY_TIMEOUT_MS: u32 = 1000;
 
+/// BUSY poll interval while spinning (µs).
+const BUSY_POLL_US: u32 = 100;
+
+/// How long [`LoRaRadio::wait_busy`] spins before yielding to other tasks (µs).
+///
+/// Covers command processing and standby/TX/RX transitions; only calibration
+/// and wake-up from sleep take longer.
+const BUSY_SPIN_US: u64 = 2000;
+
+/// Extra hardware resets in `init` when the radio stays busy after a reset.
+const MAX_INIT_RETRIES: u32 = 3;
+
 /// Maximum time to wait for TX completion (seconds).
 const TX_TIMEOUT_SECS: u64 = 5;
 
@@ -87,12 +107,42 @@ const TX_TIMEOUT_SECS: u64 = 5;
 /// Per SX1262 community reports, 5ms is reliable for accurate RSSI readings.
 const RSSI_SETTLING_MS: u32 = 5;
 
+/// Time to wait for CAD_DONE (ms). A 2-symbol CAD at SF7/125 kHz takes ~2ms.
+const CAD_TIMEOUT_MS: u32 = 100;
+
+// SX1262 CAD parameters for SF7/125 kHz (Semtech AN1200.48, Table 42), see
+// `LoRaRadio::set_csma_config`:
+// 2 symbols, detection peak 22, detection minimum 10, CAD_ONLY exit mode
+// (return to STDBY_RC after CAD_DONE).
+const CAD_ON_2_SYMB: u8 = 0x01;
+const CAD_DET_PEAK: u8 = 22;
+const CAD_DET_MIN: u8 = 10;
+const CAD_EXIT_CAD_ONLY: u8 = 0x00;
+
+/// SetSleep config: warm start (register retention), no RTC wake-up.
+const SLEEP_WARM_START: u8 = 0x04;
+
+/// Time for the SX1262 to enter sleep after SetSleep (datasheet: 500 µs).
+const SLEEP_ENTRY_MS: u32 = 1;
+
 // SX1262 LoRa modulation parameter values (per datasheet Table 13-47, 13-48).
 // We use raw bytes because the sx1262 crate's LoRaBandwidth enum has incorrect values.
 const LORA_SF7: u8 = 0x07;
 const LORA_BW_125_KHZ: u8 = 0x04;
+const LORA_BW_250_KHZ: u8 = 0x05;
+const LORA_BW_500_KHZ: u8 = 0x06;
 const LORA_CR_4_5: u8 = 0x01;
 
+/// Map a bandwidth in Hz to its SX1262 register value.
+fn bandwidth_register(bandwidth_hz: u32) -> Option<u8> {
+    match bandwidth_hz {
+        125_000 => Some(LORA_BW_125_KHZ),
+        250_000 => Some(LORA_BW_250_KHZ),
+        500_000 => Some(LORA_BW_500_KHZ),
+        _ => None,
+    }
+}
+
 /// Raw LoRa modulation parameters (bypasses sx1262 crate's broken bandwidth enum).
 ///
 /// Format: [SF, BW, CR, LowDataRateOpt, 0, 0, 0, 0]
@@ -167,6 +217,301 @@ impl Command for GetRssiInst {
     }
 }
 
+/// Raw CAD parameters.
+///
+/// Format: [cadSymbolNum, cadDetPeak, cadDetMin, cadExitMode, cadTimeout(3)]
+#[derive(Debug, Clone)]
+struct RawCadParams([u8; 7]);
+
+impl ToByteArray for RawCadParams {
+    type Error = core::convert::Infallible;
+    type Array = [u8; 7];
+
+    fn to_bytes(self) -> Result<Self::Array, Self::Error> {
+        Ok(self.0)
+    }
+}
+
+/// Raw SetCadParams command (opcode 0x88).
+#[derive(Debug, Clone)]
+struct SetCadParams {
+    params: RawCadParams,
+}
+
+impl Command for SetCadParams {
+    type IdType = u8;
+    type CommandParameters = RawCadParams;
+    type ResponseParameters = NoParameters;
+
+    fn id() -> Self::IdType {
+        0x88
+    }
+
+    fn invoking_parameters(self) -> Self::CommandParameters {
+        self.params
+    }
+}
+
+/// Raw SetCad command (opcode 0xC5).
+#[derive(Debug, Clone, Default)]
+struct SetCad;
+
+impl Command for SetCad {
+    type IdType = u8;
+    type CommandParameters = NoParameters;
+    type ResponseParameters = NoParameters;
+
+    fn id() -> Self::IdType {
+        0xC5
+    }
+
+    fn invoking_parameters(self) -> Self::CommandParameters {
+        Default::default()
+    }
+}
+
+/// Raw SetSleep command (opcode 0x84).
+///
+/// The single parameter is the sleep config: bit 2 selects warm start
+/// (configuration retained), bit 0 enables the RTC wake-up timer.
+#[derive(Debug, Clone)]
+struct SetSleepRaw {
+    config: RawSleepConfig,
+}
+
+/// Raw sleep config byte for [`SetSleepRaw`].
+#[derive(Debug, Clone)]
+struct RawSleepConfig(u8);
+
+impl ToByteArray for RawSleepConfig {
+    type Error = core::convert::Infallible;
+    type Array = [u8; 1];
+
+    fn to_bytes(self) -> Result<Self::Array, Self::Error> {
+        Ok([self.0])
+    }
+}
+
+impl Command for SetSleepRaw {
+    type IdType = u8;
+    type CommandParameters = RawSleepConfig;
+    type ResponseParameters = NoParameters;
+
+    fn id() -> Self::IdType {
+        0x84
+    }
+
+    fn invoking_parameters(self) -> Self::CommandParameters {
+        self.config
+    }
+}
+
+/// Raw single-byte response (status byte + one value byte).
+#[derive(Debug, Clone, Default)]
+struct ByteResponse {
+    value: u8,
+}
+
+impl regiface::FromByteArray for ByteResponse {
+    type Error = core::convert::Infallible;
+    type Array = [u8; 2]; // status byte + value byte
+
+    fn from_bytes(bytes: Self::Array) -> Result<Self, Self::Error> {
+        Ok(Self { value: bytes[1] })
+    }
+}
+
+/// Raw GetPacketType command (opcode 0x11).
+#[derive(Debug, Clone, Default)]
+struct GetPacketTypeRaw;
+
+impl Command for GetPacketTypeRaw {
+    type IdType = u8;
+    type CommandParameters = NoParameters;
+    type ResponseParameters = ByteResponse;
+
+    fn id() -> Self::IdType {
+        0x11
+    }
+
+    fn invoking_parameters(self) -> Self::CommandParameters {
+        Default::default()
+    }
+}
+
+/// Raw status response (chip mode in bits 6:4).
+#[derive(Debug, Clone, Default)]
+struct StatusResponse {
+    status: u8,
+}
+
+impl regiface::FromByteArray for StatusResponse {
+    type Error = core::convert::Infallible;
+    type Array = [u8; 1];
+
+    fn from_bytes(bytes: Self::Array) -> Result<Self, Self::Error> {
+        Ok(Self { status: bytes[0] })
+    }
+}
+
+/// Raw GetStatus command (opcode 0xC0).
+#[derive(Debug, Clone, Default)]
+struct GetStatusRaw;
+
+impl Command for GetStatusRaw {
+    type IdType = u8;
+    type CommandParameters = NoParameters;
+    type ResponseParameters = StatusResponse;
+
+    fn id() -> Self::IdType {
+        0xC0
+    }
+
+    fn invoking_parameters(self) -> Self::CommandParameters {
+        Default::default()
+    }
+}
+
+/// Register address parameter for ReadRegister.
+#[derive(Debug, Clone)]
+struct RegisterAddress(u16);
+
+impl ToByteArray for RegisterAddress {
+    type Error = core::convert::Infallible;
+    type Array = [u8; 2];
+
+    fn to_bytes(self) -> Result<Self::Array, Self::Error> {
+        Ok(self.0.to_be_bytes())
+    }
+}
+
+/// Address and data for a 2-byte register write.
+#[derive(Debug, Clone)]
+struct RegisterWrite16 {
+    address: u16,
+    value: u16,
+}
+
+impl ToByteArray for RegisterWrite16 {
+    type Error = core::convert::Infallible;
+    type Array = [u8; 4];
+
+    fn to_bytes(self) -> Result<Self::Array, Self::Error> {
+        let [addr_hi, addr_lo] = self.address.to_be_bytes();
+        let [value_hi, value_lo] = self.value.to_be_bytes();
+        Ok([addr_hi, addr_lo, value_hi, value_lo])
+    }
+}
+
+/// Raw WriteRegister command (opcode 0x0D) writing 2 consecutive bytes.
+#[derive(Debug, Clone)]
+struct WriteRegister16 {
+    write: RegisterWrite16,
+}
+
+impl Command for WriteRegister16 {
+    type IdType = u8;
+    type CommandParameters = RegisterWrite16;
+    type ResponseParameters = NoParameters;
+
+    fn id() -> Self::IdType {
+        0x0D
+    }
+
+    fn invoking_parameters(self) -> Self::CommandParameters {
+        self.write
+    }
+}
+
+/// Raw 4-byte register read response.
+#[derive(Debug, Clone, Default)]
+struct Register32Response {
+    value: u32,
+}
+
+impl regiface::FromByteArray for Register32Response {
+    type Error = core::convert::Infallible;
+    type Array = [u8; 5]; // status byte + 4 data bytes
+
+    fn from_bytes(bytes: Self::Array) -> Result<Self, Self::Error> {
+        Ok(Self {
+            value: u32::from_be_bytes([bytes[1], bytes[2], bytes[3], bytes[4]]),
+        })
+    }
+}
+
+/// Raw ReadRegister command (opcode 0x1D) reading 4 consecutive bytes.
+#[derive(Debug, Clone)]
+struct ReadRegister32 {
+    address: RegisterAddress,
+}
+
+impl Command for ReadRegister32 {
+    type IdType = u8;
+    type CommandParameters = RegisterAddress;
+    type ResponseParameters = Register32Response;
+
+    fn id() -> Self::IdType {
+        0x1D
+    }
+
+    fn invoking_parameters(self) -> Self::CommandParameters {
+        self.address
+    }
+}
+
+/// Packet type value for LoRa (GetPacketType response).
+const PACKET_TYPE_LORA: u8 = 0x01;
+
+/// RF frequency register (4 bytes, big-endian PLL steps).
+const REG_RF_FREQUENCY: u16 = 0x088B;
+
+/// LoRa sync word register (2 bytes, see [`sync_word_register`]).
+const REG_LORA_SYNC_WORD: u16 = 0x0740;
+
+/// SX1262 crystal frequency used for PLL step conversion (Hz).
+const XTAL_FREQ_HZ: u64 = 32_000_000;
+
+/// Chip mode values from GetStatus bits 6:4.
+const CHIP_MODE_STBY_RC: u8 = 0x2;
+const CHIP_MODE_STBY_XOSC: u8 = 0x3;
+
+/// Convert a frequency in Hz to SX1262 PLL steps (freq * 2^25 / 32 MHz).
+fn frequency_to_pll_steps(frequency_hz: u32) -> u32 {
+    (((frequency_hz as u64) << 25) / XTAL_FREQ_HZ) as u32
+}
+
+/// Result of a Channel Activity Detection run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CadResult {
+    /// Whether a LoRa preamble was detected on the channel.
+    pub channel_active: bool,
+}
+
+/// Result of [`LoRaRadio::self_test`].
+///
+/// Each flag reports whether a register read back the value `init()`
+/// configured. A failure usually points at SPI wiring or a radio that
+/// silently reset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SelfTestReport {
+    /// Packet type register reads back LoRa.
+    pub packet_type_ok: bool,
+    /// RF frequency register matches the channel frequency.
+    pub frequency_ok: bool,
+    /// Chip reports a standby mode (RC or XOSC).
+    pub standby_ok: bool,
+    /// Instantaneous RSSI (dBm) sampled in RX mode, i.e. the noise floor.
+    pub raw_rssi_noise: i16,
+}
+
+impl SelfTestReport {
+    /// Whether all register checks passed.
+    pub fn all_ok(&self) -> bool {
+        self.packet_type_ok && self.frequency_ok && self.standby_ok
+    }
+}
+
 /// LoRa radio interface.
 pub struct LoRaRadio<'d> {
     device: Device<SpiDeviceDriver<'d, SpiDriver<'d>>>,
@@ -175,11 +520,23 @@ pub struct LoRaRadio<'d> {
     /// DIO1 pin for interrupt-driven RX/TX completion.
     dio1: PinDriver<'d, Gpio1, Input>,
     region: Region,
+    /// Channel the radio is tuned to, re-applied whenever the radio is reset.
+    channel: LoRaChannel,
     duty_cycle: DutyCycleLimiter,
     csma: Csma,
     initialized: bool,
+    /// Whether the radio is parked in continuous RX (listen-only) mode.
+    monitoring: bool,
+    /// Whether the radio is in sleep mode (see [`sleep`](Self::sleep)).
+    sleeping: bool,
+    /// LoRa sync word, re-applied whenever the radio is reset.
+    sync_word: u8,
+    /// Fixed packet length for implicit header mode, if enabled.
+    implicit_header_len: Option<u8>,
     /// Signal for interrupt-driven waiting (ISR signals when DIO1 fires).
     irq_signal: IrqSignal,
+    /// Node statistics updated with transmit airtime, if attached.
+    stats: Option<Arc<NodeStats>>,
 }
 
 impl<'d> LoRaRadio<'d> {
@@ -215,7 +572,9 @@ impl<'d> LoRaRadio<'d> {
         let dio1_pin = PinDriver::input(dio1).map_err(RadioError::Gpio)?;
 
         let duty_cycle = region.duty_cycle_limiter();
-        let csma = Csma::new(CsmaConfig::default());
+        // Hardware RNG (via getrandom) so other nodes can't predict our backoff
+        let csma = Csma::with_rng(CsmaConfig::default(), rand_core::OsRng)
+            .expect("default CSMA config should be valid");
         let irq_signal = IrqSignal::new();
 
         Ok(Self {
@@ -224,10 +583,16 @@ impl<'d> LoRaRadio<'d> {
             busy: busy_pin,
             dio1: dio1_pin,
             region,
+            channel: region.channels()[0],
             duty_cycle,
             csma,
             initialized: false,
+            monitoring: false,
+            sleeping: false,
+            sync_word: SYNC_WORD,
+            implicit_header_len: None,
             irq_signal,
+            stats: None,
         })
     }
 
@@ -237,10 +602,427 @@ impl<'d> LoRaRadio<'d> {
     pub fn init(&mut self) -> Result<(), RadioError> {
         info!("Initializing SX1262 radio for {:?}", self.region);
 
-        self.hardware_reset()?;
+        self.reset_until_ready()?;
+        self.configure()?;
+
+        // Set up interrupt-driven notification for DIO1
+        self.setup_dio1_interrupt()?;
+
+        self.initialized = true;
+        self.sleeping = false;
+        if let Some(stats) = &self.stats {
+            stats.lora_initialized.store(true, Ordering::Relaxed);
+        }
+        info!(
+            "SX1262 initialized: {} MHz, SF{}, {}kHz, {} dBm",
+            self.channel.label,
+            SPREADING_FACTOR,
+            self.channel.bandwidth_hz / 1000,
+            TX_POWER
+        );
+
+        Ok(())
+    }
+
+    /// Write the full LoRa configuration, leaving the radio in standby.
+    ///
+    /// Used after a reset and after waking from sleep.
+    fn configure(&mut self) -> Result<(), RadioError> {
+        // Set standby mode
+        self.device
+            .execute_command(SetStandby {
+                config: StandbyConfig::Rc,
+            })
+            .map_err(RadioError::Command)?;
+        self.wait_busy()?;
+
+        // Set packet type to LoRa
+        self.device
+            .execute_command(SetPacketType {
+                packet_type: PacketType::LoRa,
+            })
+            .map_err(RadioError::Command)?;
+        self.wait_busy()?;
+
+        // Set RF frequency and modulation parameters
+        self.write_channel()?;
+
+        // Set packet parameters (raw bytes for LoRa mode)
+        self.write_rx_packet_params()?;
+
+        // The reset restored the default sync word
+        self.write_sync_word()?;
+
+        // Configure PA for SX1262 (+22dBm capable)
+        self.device
+            .execute_command(SetPaConfig {
+                config: PaConfig {
+                    duty_cycle: 0x04,
+                    hp_max: 0x07,
+                    device_sel: DeviceSelect::Sx1262,
+                    pa_lut: 0x01,
+                },
+            })
+            .map_err(RadioError::Command)?;
+        self.wait_busy()?;
+
+        // Set TX parameters
+        self.device
+            .execute_command(SetTxParams {
+                params: TxParams {
+                    power: TX_POWER,
+                    ramp_time: RampTime::Micros200,
+                },
+            })
+            .map_err(RadioError::Command)?;
+        self.wait_busy()?;
+
+        // Configure DIO1 for TX done and RX done interrupts
+        self.configure_irq()
+    }
+
+    /// Configure IRQ settings.
+    fn configure_irq(&mut self) -> Result<(), RadioError> {
+        let dio1_mask = IrqMask::TX_DONE
+            | IrqMask::RX_DONE
+            | IrqMask::TIMEOUT
+            | IrqMask::CAD_DONE
+            | IrqMask::CAD_DETECTED;
+        // CRC_ERR is raised together with RX_DONE, so it only needs to be
+        // latched in the status register, not routed to DIO1
+        let irq_mask = dio1_mask | IrqMask::CRC_ERR;
+        self.device
+            .execute_command(SetDioIrqParams {
+                config: DioIrqConfig {
+                    irq_mask,
+                    dio1_mask,
+                    dio2_mask: IrqMask::empty(),
+                    dio3_mask: IrqMask::empty(),
+                },
+            })
+            .map_err(RadioError::Command)?;
+        self.wait_busy()?;
+        Ok(())
+    }
+
+    /// Set up DIO1 interrupt for efficient TX/RX completion detection.
+    ///
+    /// Instead of polling the radio every 1ms, we use a GPIO interrupt on DIO1
+    /// which the SX1262 pulses on TX_DONE, RX_DONE, TIMEOUT and CAD_DONE events.
+    fn setup_dio1_interrupt(&mut self) -> Result<(), RadioError> {
+        self.dio1
+            .set_interrupt_type(InterruptType::PosEdge)
+            .map_err(RadioError::Gpio)?;
+
+        let signal = self.irq_signal.clone();
+
+        // SAFETY: The signal is Arc-wrapped and Queue::send_back with timeout 0
+        // is documented as ISR-safe. The subscription is cleaned up in Drop.
+        unsafe {
+            self.dio1
+                .subscribe(move || signal.signal_from_isr())
+                .map_err(RadioError::Gpio)?;
+        }
+
+        self.dio1.enable_interrupt().map_err(RadioError::Gpio)?;
+
+        debug!("DIO1 interrupt configured for TX/RX completion");
+        Ok(())
+    }
+
+    /// Attach node statistics to record transmit airtime and CRC errors in.
+    ///
+    /// Every successful [`transmit`](Self::transmit) adds its airtime to
+    /// `stats.lora_radio`, exposed as `lora_airtime_us_total` on the stats endpoint.
+    /// Packets dropped by [`receive`](Self::receive) for a failed CRC are
+    /// counted as `lora_crc_errors`.
+    ///
+    /// Also marks LoRa as configured for `/health`, and as initialized once
+    /// [`init`](Self::init) succeeds.
+    pub fn set_stats(&mut self, stats: Arc<NodeStats>) {
+        stats.lora_configured.store(true, Ordering::Relaxed);
+        stats
+            .lora_initialized
+            .store(self.initialized, Ordering::Relaxed);
+        self.stats = Some(stats);
+    }
+
+    /// Get the channel the radio is tuned to.
+    pub fn channel(&self) -> LoRaChannel {
+        self.channel
+    }
+
+    /// Switch to another channel, e.g. one of [`Region::channels`].
+    ///
+    /// Defaults to the region's first channel. Before [`init`](Self::init)
+    /// or while asleep the channel is only stored, and it is re-applied after
+    /// every reset or wake-up.
+    /// Only 125, 250 and 500 kHz bandwidths are supported.
+    pub fn set_channel(&mut self, channel: &LoRaChannel) -> Result<(), RadioError> {
+        if bandwidth_register(channel.bandwidth_hz).is_none() {
+            return Err(RadioError::UnsupportedBandwidth(channel.bandwidth_hz));
+        }
+        if self.monitoring {
+            return Err(RadioError::InMonitorMode);
+        }
+        self.channel = *channel;
+        if self.initialized && !self.sleeping {
+            self.write_channel()?;
+        }
+        info!(
+            "LoRa channel set to {} MHz ({} kHz)",
+            channel.label,
+            channel.bandwidth_hz / 1000
+        );
+        Ok(())
+    }
+
+    /// Airtime and packet parameters for the current channel and header mode.
+    pub fn lora_params(&self) -> LoRaParams {
+        LoRaParams {
+            bandwidth_hz: self.channel.bandwidth_hz,
+            implicit_header_len: self.implicit_header_len,
+            ..LoRaParams::default()
+        }
+    }
+
+    /// Write the stored channel's frequency and bandwidth to the radio.
+    fn write_channel(&mut self) -> Result<(), RadioError> {
+        let bandwidth = bandwidth_register(self.channel.bandwidth_hz)
+            .ok_or(RadioError::UnsupportedBandwidth(self.channel.bandwidth_hz))?;
+
+        self.wait_busy()?;
+        self.device
+            .execute_command(SetRfFrequency {
+                config: RfFrequencyConfig {
+                    frequency: self.channel.frequency_hz,
+                },
+            })
+            .map_err(RadioError::Command)?;
+        self.wait_busy()?;
+
+        // Raw bytes because the sx1262 crate has an incorrect bandwidth enum
+        self.device
+            .execute_command(RawSetModulationParams {
+                params: RawLoRaModParams::new(
+                    LORA_SF7,
+                    bandwidth,
+                    LORA_CR_4_5,
+                    LOW_DATA_RATE_OPTIMIZE,
+                ),
+            })
+            .map_err(RadioError::Command)?;
+        self.wait_busy()
+    }
+
+    /// Get the LoRa sync word.
+    pub fn sync_word(&self) -> u8 {
+        self.sync_word
+    }
+
+    /// Set the LoRa sync word.
+    ///
+    /// Radios only receive packets with a matching sync word. Reticulum uses
+    /// [`SYNC_WORD`]; switching to [`LORAWAN_SYNC_WORD`](super::LORAWAN_SYNC_WORD)
+    /// lets a node observe public LoRaWAN traffic. Before [`init`](Self::init)
+    /// or while asleep the word is only stored, and it is re-applied after
+    /// every reset or wake-up.
+    pub fn set_sync_word(&mut self, word: u8) -> Result<(), RadioError> {
+        self.sync_word = word;
+        if self.initialized && !self.sleeping {
+            self.write_sync_word()?;
+        }
+        info!("LoRa sync word set to {:#04x}", word);
+        Ok(())
+    }
+
+    /// Get the fixed packet length used in implicit header mode, if enabled.
+    pub fn implicit_header_len(&self) -> Option<u8> {
+        self.implicit_header_len
+    }
+
+    /// Switch between explicit and implicit header mode.
+    ///
+    /// `Some(n)` selects implicit header mode with every packet exactly `n`
+    /// bytes long, for fixed-length probes; both ends must agree on `n`, since
+    /// no length field goes on air. `None` restores explicit headers. Like
+    /// [`set_sync_word`](Self::set_sync_word), the mode is only stored before
+    /// [`init`](Self::init) or while asleep, and re-applied on every reset.
+    pub fn set_implicit_header_len(&mut self, len: Option<u8>) -> Result<(), RadioError> {
+        if let Some(n) = len {
+            if n == 0 {
+                return Err(RadioError::EmptyPacket);
+            }
+        }
+        self.implicit_header_len = len;
+        if self.initialized && !self.sleeping {
+            self.write_rx_packet_params()?;
+        }
+        info!("LoRa implicit header length set to {:?}", len);
+        Ok(())
+    }
+
+    /// Write packet parameters for receiving to the radio.
+    ///
+    /// The length byte is the maximum payload in explicit header mode and the
+    /// fixed length in implicit header mode.
+    fn write_rx_packet_params(&mut self) -> Result<(), RadioError> {
+        let packet_params =
+            build_lora_packet_params(PREAMBLE_LENGTH, LORA_MTU as u8, &self.lora_params(), false);
         self.wait_busy()?;
+        self.device
+            .execute_command(SetPacketParams {
+                params: packet_params,
+            })
+            .map_err(RadioError::Command)?;
+        self.wait_busy()
+    }
+
+    /// Write the stored sync word to the radio.
+    fn write_sync_word(&mut self) -> Result<(), RadioError> {
+        self.wait_busy()?;
+        self.device
+            .execute_command(WriteRegister16 {
+                write: RegisterWrite16 {
+                    address: REG_LORA_SYNC_WORD,
+                    value: sync_word_register(self.sync_word),
+                },
+            })
+            .map_err(RadioError::Command)?;
+        self.wait_busy()
+    }
+
+    /// Hardware reset the radio.
+    fn hardware_reset(&mut self) -> Result<(), RadioError> {
+        debug!("Resetting radio");
+        self.reset.set_low().map_err(RadioError::Gpio)?;
+        FreeRtos::delay_ms(1);
+        self.reset.set_high().map_err(RadioError::Gpio)?;
+        FreeRtos::delay_ms(10);
+        Ok(())
+    }
+
+    /// Reset the radio and wait until it is ready.
+    ///
+    /// Some SX1262 units occasionally stay busy slightly longer than
+    /// [`BUSY_TIMEOUT_MS`] after a reset, so a timeout triggers another reset,
+    /// up to [`MAX_INIT_RETRIES`] times.
+    fn reset_until_ready(&mut self) -> Result<(), RadioError> {
+        let mut retries = 0;
+        loop {
+            self.hardware_reset()?;
+            match self.wait_busy() {
+                Err(RadioError::Timeout) if retries < MAX_INIT_RETRIES => {
+                    retries += 1;
+                    warn!(
+                        "Radio still busy after reset, retrying ({}/{})",
+                        retries, MAX_INIT_RETRIES
+                    );
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Wait for the radio to be ready (BUSY pin low).
+    ///
+    /// BUSY usually clears within tens of microseconds of a command, well
+    /// below the FreeRTOS tick (1-10 ms), so sleeping a tick per poll
+    /// added most of the latency. For the first [`BUSY_SPIN_US`] the pin is
+    /// polled every [`BUSY_POLL_US`] with a busy-wait, then the wait falls
+    /// back to one-tick sleeps so long waits (calibration, wake-up) don't hog
+    /// the CPU. A BUSY falling-edge interrupt would avoid the spin, but the
+    /// ISR and semaphore round trip costs about as much as the typical wait,
+    /// and the edge is missed if BUSY drops before the wait starts.
+    fn wait_busy(&self) -> Result<(), RadioError> {
+        let start = Instant::now();
+        let timeout = Duration::from_millis(BUSY_TIMEOUT_MS as u64);
+        let spin = Duration::from_micros(BUSY_SPIN_US);
+
+        while self.busy.is_high() {
+            let elapsed = start.elapsed();
+            if elapsed > timeout {
+                return Err(RadioError::Timeout);
+            }
+            if elapsed < spin {
+                Ets::delay_us(BUSY_POLL_US);
+            } else {
+                FreeRtos::delay_ms(1);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Read instantaneous RSSI from the radio.
+    ///
+    /// Returns RSSI in dBm. Used for CSMA/CA channel sensing.
+    fn get_rssi(&mut self) -> Result<i16, RadioError> {
+        self.wait_busy()?;
+        let response = self
+            .device
+            .execute_command(GetRssiInst)
+            .map_err(RadioError::Command)?;
+        // RSSI = -raw_value/2 dBm (per SX1262 datasheet)
+        Ok(-(response.rssi as i16) / 2)
+    }
+
+    /// Get the CSMA/CA configuration.
+    pub fn csma_config(&self) -> &CsmaConfig {
+        self.csma.config()
+    }
+
+    /// Replace the CSMA/CA configuration used before each transmission.
+    ///
+    /// The configuration is checked with [`CsmaConfig::validate`]; an invalid
+    /// one is rejected and the current one kept.
+    ///
+    /// With [`SensingMode::Cad`], the CAD symbol count and detection
+    /// thresholds are Semtech's recommendations for SF7 at 125 kHz, the
+    /// only modulation this driver configures. On a channel with a different
+    /// bandwidth CAD may miss transmissions or report false activity; prefer
+    /// RSSI sensing there.
+    pub fn set_csma_config(&mut self, config: CsmaConfig) -> Result<(), CsmaError> {
+        self.csma.set_config(config)?;
+        info!("LoRa CSMA config set: {:?}", config);
+        Ok(())
+    }
+
+    /// Sample the channel using the configured CSMA sensing mode.
+    fn sense_channel(&mut self) -> Result<CsmaResult, RadioError> {
+        match self.csma.config().sensing_mode {
+            SensingMode::Rssi => {
+                FreeRtos::delay_ms(RSSI_SETTLING_MS);
+                let rssi = self.get_rssi()?;
+                debug!("CSMA channel RSSI {} dBm", rssi);
+                Ok(self.csma.try_access(rssi))
+            }
+            SensingMode::Cad => {
+                self.start_cad()?;
+                let cad = self.wait_cad_done(CAD_TIMEOUT_MS)?;
+                debug!("CSMA channel activity detected: {}", cad.channel_active);
+                Ok(self.csma.try_access_cad(cad.channel_active))
+            }
+        }
+    }
+
+    /// Start Channel Activity Detection.
+    ///
+    /// The radio listens for a few symbols and raises CAD_DONE (plus
+    /// CAD_DETECTED if a LoRa preamble was seen), then returns to standby.
+    /// Call [`wait_cad_done`](Self::wait_cad_done) for the result.
+    pub fn start_cad(&mut self) -> Result<(), RadioError> {
+        if !self.initialized {
+            return Err(RadioError::NotInitialized);
+        }
+        if self.sleeping {
+            return Err(RadioError::Asleep);
+        }
+        if self.monitoring {
+            return Err(RadioError::InMonitorMode);
+        }
 
-        // Set standby mode
+        // CAD must be started from standby
         self.device
             .execute_command(SetStandby {
                 config: StandbyConfig::Rc,
@@ -248,171 +1030,251 @@ impl<'d> LoRaRadio<'d> {
             .map_err(RadioError::Command)?;
         self.wait_busy()?;
 
-        // Set packet type to LoRa
         self.device
-            .execute_command(SetPacketType {
-                packet_type: PacketType::LoRa,
+            .execute_command(SetCadParams {
+                params: RawCadParams([
+                    CAD_ON_2_SYMB,
+                    CAD_DET_PEAK,
+                    CAD_DET_MIN,
+                    CAD_EXIT_CAD_ONLY,
+                    0,
+                    0,
+                    0,
+                ]),
             })
             .map_err(RadioError::Command)?;
         self.wait_busy()?;
 
-        // Set RF frequency
         self.device
-            .execute_command(SetRfFrequency {
-                config: RfFrequencyConfig {
-                    frequency: self.region.frequency(),
-                },
-            })
+            .execute_command(SetCad)
             .map_err(RadioError::Command)?;
-        self.wait_busy()?;
+        Ok(())
+    }
 
-        // Set modulation parameters using raw bytes (sx1262 crate has incorrect bandwidth enum)
-        self.device
-            .execute_command(RawSetModulationParams {
-                params: RawLoRaModParams::new(
-                    LORA_SF7,
-                    LORA_BW_125_KHZ,
-                    LORA_CR_4_5,
-                    LOW_DATA_RATE_OPTIMIZE,
-                ),
-            })
+    /// Wait for a CAD started with [`start_cad`](Self::start_cad) to finish.
+    ///
+    /// Blocks until DIO1 fires with CAD_DONE or `timeout_ms` expires.
+    pub fn wait_cad_done(&mut self, timeout_ms: u32) -> Result<CadResult, RadioError> {
+        loop {
+            if !self.irq_signal.wait(timeout_ms) {
+                return Err(RadioError::Timeout);
+            }
+
+            // Read IRQ status before re-enabling interrupt to avoid race condition
+            self.wait_busy()?;
+            let irq = self
+                .device
+                .execute_command(GetIrqStatus)
+                .map_err(RadioError::Command)?;
+
+            if irq.irq_mask.contains(IrqMask::CAD_DONE) {
+                self.device
+                    .execute_command(ClearIrqStatus {
+                        irq_mask: IrqMask::all(),
+                    })
+                    .map_err(RadioError::Command)?;
+                // Re-enable interrupt after clearing source
+                self.dio1.enable_interrupt().map_err(RadioError::Gpio)?;
+                return Ok(CadResult {
+                    channel_active: irq.irq_mask.contains(IrqMask::CAD_DETECTED),
+                });
+            }
+
+            // Spurious interrupt - re-enable and continue waiting
+            self.dio1.enable_interrupt().map_err(RadioError::Gpio)?;
+        }
+    }
+
+    /// Verify the radio's register state after [`init`](Self::init).
+    ///
+    /// Reads back the packet type, RF frequency, and chip mode, then samples
+    /// the noise floor in RX mode. The radio is left in standby.
+    pub fn self_test(&mut self) -> Result<SelfTestReport, RadioError> {
+        if !self.initialized {
+            return Err(RadioError::NotInitialized);
+        }
+        if self.sleeping {
+            return Err(RadioError::Asleep);
+        }
+        if self.monitoring {
+            return Err(RadioError::InMonitorMode);
+        }
+
+        self.wait_busy()?;
+        let status = self
+            .device
+            .execute_command(GetStatusRaw)
             .map_err(RadioError::Command)?;
+        let chip_mode = (status.status >> 4) & 0x07;
+        let standby_ok = chip_mode == CHIP_MODE_STBY_RC || chip_mode == CHIP_MODE_STBY_XOSC;
+
         self.wait_busy()?;
+        let packet_type = self
+            .device
+            .execute_command(GetPacketTypeRaw)
+            .map_err(RadioError::Command)?;
+        let packet_type_ok = packet_type.value == PACKET_TYPE_LORA;
 
-        // Set packet parameters (raw bytes for LoRa mode)
-        // Format: [preamble_hi, preamble_lo, header_type, payload_len, crc_on, invert_iq, 0, 0, 0]
-        let packet_params = build_lora_packet_params(PREAMBLE_LENGTH, LORA_MTU as u8, true, false);
-        self.device
-            .execute_command(SetPacketParams {
-                params: packet_params,
+        self.wait_busy()?;
+        let frequency = self
+            .device
+            .execute_command(ReadRegister32 {
+                address: RegisterAddress(REG_RF_FREQUENCY),
             })
             .map_err(RadioError::Command)?;
-        self.wait_busy()?;
+        let expected = frequency_to_pll_steps(self.channel.frequency_hz);
+        // Allow one step of rounding difference in the driver's conversion
+        let frequency_ok = frequency.value.abs_diff(expected) <= 1;
 
-        // Configure PA for SX1262 (+22dBm capable)
+        // Sample the noise floor (RSSI is only valid in RX mode)
+        self.wait_busy()?;
         self.device
-            .execute_command(SetPaConfig {
-                config: PaConfig {
-                    duty_cycle: 0x04,
-                    hp_max: 0x07,
-                    device_sel: DeviceSelect::Sx1262,
-                    pa_lut: 0x01,
-                },
+            .execute_command(SetRx {
+                mode: RxMode::Continuous,
             })
             .map_err(RadioError::Command)?;
-        self.wait_busy()?;
-
-        // Set TX parameters
+        FreeRtos::delay_ms(RSSI_SETTLING_MS);
+        let rssi = self.get_rssi();
         self.device
-            .execute_command(SetTxParams {
-                params: TxParams {
-                    power: TX_POWER,
-                    ramp_time: RampTime::Micros200,
-                },
+            .execute_command(SetStandby {
+                config: StandbyConfig::Rc,
             })
             .map_err(RadioError::Command)?;
         self.wait_busy()?;
+        let raw_rssi_noise = rssi?;
 
-        // Configure DIO1 for TX done and RX done interrupts
-        self.configure_irq()?;
-
-        // Set up interrupt-driven notification for DIO1
-        self.setup_dio1_interrupt()?;
-
-        // Seed CSMA RNG from hardware random number generator
-        let seed = unsafe { esp_idf_sys::esp_random() };
-        self.csma.seed(seed);
+        let report = SelfTestReport {
+            packet_type_ok,
+            frequency_ok,
+            standby_ok,
+            raw_rssi_noise,
+        };
 
-        self.initialized = true;
-        info!(
-            "SX1262 initialized: {} MHz, SF{}, {}kHz, {} dBm",
-            self.region.frequency() / 1_000_000,
-            SPREADING_FACTOR,
-            BANDWIDTH_HZ / 1000,
-            TX_POWER
-        );
+        if report.all_ok() {
+            info!(
+                "Radio self-test passed (noise floor {} dBm)",
+                raw_rssi_noise
+            );
+        } else {
+            warn!(
+                "Radio self-test failed: status=0x{:02X} packet_type=0x{:02X} freq=0x{:08X} (expected 0x{:08X})",
+                status.status, packet_type.value, frequency.value, expected
+            );
+        }
 
-        Ok(())
+        Ok(report)
     }
 
-    /// Configure IRQ settings.
-    fn configure_irq(&mut self) -> Result<(), RadioError> {
-        let irq_mask = IrqMask::TX_DONE | IrqMask::RX_DONE | IrqMask::TIMEOUT;
+    /// Put the radio in listen-only mode for spectrum monitoring.
+    ///
+    /// The radio stays in continuous RX until [`stop_monitor_mode`](Self::stop_monitor_mode).
+    /// [`receive`](Self::receive) keeps working without re-issuing `SetRx`;
+    /// [`transmit`](Self::transmit) is refused.
+    pub fn start_monitor_mode(&mut self) -> Result<(), RadioError> {
+        if !self.initialized {
+            return Err(RadioError::NotInitialized);
+        }
+        if self.sleeping {
+            return Err(RadioError::Asleep);
+        }
+        if self.monitoring {
+            return Ok(());
+        }
+
+        self.wait_busy()?;
         self.device
-            .execute_command(SetDioIrqParams {
-                config: DioIrqConfig {
-                    irq_mask,
-                    dio1_mask: irq_mask,
-                    dio2_mask: IrqMask::empty(),
-                    dio3_mask: IrqMask::empty(),
-                },
+            .execute_command(ClearIrqStatus {
+                irq_mask: IrqMask::all(),
             })
             .map_err(RadioError::Command)?;
         self.wait_busy()?;
+        self.device
+            .execute_command(SetRx {
+                mode: RxMode::Continuous,
+            })
+            .map_err(RadioError::Command)?;
+
+        self.monitoring = true;
+        info!("LoRa monitor mode started");
         Ok(())
     }
 
-    /// Set up DIO1 interrupt for efficient TX/RX completion detection.
-    ///
-    /// Instead of polling the radio every 1ms, we use a GPIO interrupt on DIO1
-    /// which the SX1262 pulses on TX_DONE, RX_DONE, and TIMEOUT events.
-    fn setup_dio1_interrupt(&mut self) -> Result<(), RadioError> {
-        self.dio1
-            .set_interrupt_type(InterruptType::PosEdge)
-            .map_err(RadioError::Gpio)?;
-
-        let signal = self.irq_signal.clone();
-
-        // SAFETY: The signal is Arc-wrapped and Queue::send_back with timeout 0
-        // is documented as ISR-safe. The subscription is cleaned up in Drop.
-        unsafe {
-            self.dio1
-                .subscribe(move || signal.signal_from_isr())
-                .map_err(RadioError::Gpio)?;
+    /// Leave listen-only mode and return the radio to standby.
+    pub fn stop_monitor_mode(&mut self) -> Result<(), RadioError> {
+        if !self.monitoring {
+            return Ok(());
         }
 
-        self.dio1.enable_interrupt().map_err(RadioError::Gpio)?;
+        self.wait_busy()?;
+        self.device
+            .execute_command(SetStandby {
+                config: StandbyConfig::Rc,
+            })
+            .map_err(RadioError::Command)?;
 
-        debug!("DIO1 interrupt configured for TX/RX completion");
+        self.monitoring = false;
+        info!("LoRa monitor mode stopped");
         Ok(())
     }
 
-    /// Hardware reset the radio.
-    fn hardware_reset(&mut self) -> Result<(), RadioError> {
-        debug!("Resetting radio");
-        self.reset.set_low().map_err(RadioError::Gpio)?;
-        FreeRtos::delay_ms(1);
-        self.reset.set_high().map_err(RadioError::Gpio)?;
-        FreeRtos::delay_ms(10);
-        Ok(())
+    /// Check if the radio is in listen-only mode.
+    pub fn is_monitoring(&self) -> bool {
+        self.monitoring
     }
 
-    /// Wait for the radio to be ready (BUSY pin low).
-    fn wait_busy(&self) -> Result<(), RadioError> {
-        let start = std::time::Instant::now();
-        let timeout = Duration::from_millis(BUSY_TIMEOUT_MS as u64);
-
-        while self.busy.is_high() {
-            if start.elapsed() > timeout {
-                return Err(RadioError::Timeout);
-            }
-            FreeRtos::delay_ms(1);
+    /// Put the radio into sleep mode to save power while idle.
+    ///
+    /// Sleep draws about 0.6 µA instead of the ~5 mA of RX standby. Warm
+    /// start keeps the configuration registers, but [`wake`](Self::wake)
+    /// re-applies the configuration anyway. Transmit and receive fail with
+    /// [`RadioError::Asleep`] until then.
+    pub fn sleep(&mut self) -> Result<(), RadioError> {
+        if !self.initialized {
+            return Err(RadioError::NotInitialized);
         }
+        if self.monitoring {
+            return Err(RadioError::InMonitorMode);
+        }
+        if self.sleeping {
+            return Ok(());
+        }
+
+        self.wait_busy()?;
+        self.device
+            .execute_command(SetSleepRaw {
+                config: RawSleepConfig(SLEEP_WARM_START),
+            })
+            .map_err(RadioError::Command)?;
+        FreeRtos::delay_ms(SLEEP_ENTRY_MS);
 
+        self.sleeping = true;
+        debug!("LoRa radio asleep");
         Ok(())
     }
 
-    /// Read instantaneous RSSI from the radio.
+    /// Wake the radio from [`sleep`](Self::sleep) without a hardware reset.
     ///
-    /// Returns RSSI in dBm. Used for CSMA/CA channel sensing.
-    fn get_rssi(&mut self) -> Result<i16, RadioError> {
+    /// Any SPI transaction pulls NSS low, which wakes the SX1262; the command
+    /// itself is discarded. Once BUSY drops the configuration is re-applied
+    /// and the radio is left in standby.
+    pub fn wake(&mut self) -> Result<(), RadioError> {
+        if !self.sleeping {
+            return Ok(());
+        }
+
+        // The NSS pulse is what matters; the chip ignores this command
+        let _ = self.device.execute_command(GetStatusRaw);
         self.wait_busy()?;
-        let response = self
-            .device
-            .execute_command(GetRssiInst)
-            .map_err(RadioError::Command)?;
-        // RSSI = -raw_value/2 dBm (per SX1262 datasheet)
-        Ok(-(response.rssi as i16) / 2)
+        self.configure()?;
+
+        self.sleeping = false;
+        debug!("LoRa radio awake");
+        Ok(())
+    }
+
+    /// Check if the radio is in sleep mode.
+    pub fn is_sleeping(&self) -> bool {
+        self.sleeping
     }
 
     /// Transmit a packet.
@@ -420,10 +1282,28 @@ impl<'d> LoRaRadio<'d> {
     /// Uses CSMA/CA to avoid collisions on the shared frequency.
     /// Returns an error if the channel is busy after max retries or duty cycle is exceeded.
     pub fn transmit(&mut self, data: &[u8]) -> Result<(), RadioError> {
+        self.transmit_measured(data).map(|_| ())
+    }
+
+    /// Transmit a packet and return its measured time on air.
+    ///
+    /// Like [`transmit`](Self::transmit), but times the span from `SetTx` to
+    /// TX_DONE and corrects the duty cycle budget, which was reserved from the
+    /// calculated airtime, to the measured value. The measurement includes
+    /// DIO1 interrupt latency, so it slightly overestimates the on-air time.
+    pub fn transmit_measured(&mut self, data: &[u8]) -> Result<Duration, RadioError> {
         if !self.initialized {
             return Err(RadioError::NotInitialized);
         }
 
+        if self.sleeping {
+            return Err(RadioError::Asleep);
+        }
+
+        if self.monitoring {
+            return Err(RadioError::InMonitorMode);
+        }
+
         if data.is_empty() {
             return Err(RadioError::EmptyPacket);
         }
@@ -435,25 +1315,37 @@ impl<'d> LoRaRadio<'d> {
             });
         }
 
+        // Implicit header mode sends exactly the fixed length, and the
+        // receiver reads exactly that many bytes
+        if let Some(n) = self.implicit_header_len {
+            if data.len() != usize::from(n) {
+                return Err(RadioError::LengthMismatch {
+                    size: data.len(),
+                    expected: n.into(),
+                });
+            }
+        }
+
         // Calculate airtime for duty cycle check (done after CSMA succeeds)
-        let airtime_us = calculate_airtime_us(data.len(), &LoRaParams::default());
+        let airtime_us = calculate_airtime_us(data.len(), &self.lora_params());
 
         // CSMA/CA: check channel before transmitting
-        // Enter RX mode for channel sensing (stays in RX during backoff to detect activity)
-        self.device
-            .execute_command(SetRx {
-                mode: RxMode::Continuous,
-            })
-            .map_err(|e| {
-                self.csma.reset();
-                RadioError::Command(e)
-            })?;
+        // RSSI sensing needs RX mode (stays in RX during backoff to detect
+        // activity); CAD starts from standby and returns to it by itself
+        if self.csma.config().sensing_mode == SensingMode::Rssi {
+            self.device
+                .execute_command(SetRx {
+                    mode: RxMode::Continuous,
+                })
+                .map_err(|e| {
+                    self.csma.reset();
+                    RadioError::Command(e)
+                })?;
+        }
 
         loop {
-            FreeRtos::delay_ms(RSSI_SETTLING_MS);
-
-            let rssi = match self.get_rssi() {
-                Ok(r) => r,
+            let result = match self.sense_channel() {
+                Ok(result) => result,
                 Err(e) => {
                     self.csma.reset();
                     let _ = self.device.execute_command(SetStandby {
@@ -463,23 +1355,17 @@ impl<'d> LoRaRadio<'d> {
                 }
             };
 
-            match self.csma.try_access(rssi) {
+            match result {
                 CsmaResult::Transmit => {
-                    debug!(
-                        "Channel clear (RSSI {} dBm), transmitting {} bytes",
-                        rssi,
-                        data.len()
-                    );
+                    debug!("Channel clear, transmitting {} bytes", data.len());
                     break;
                 }
                 CsmaResult::Wait { ms } => {
                     debug!(
-                        "Channel busy (RSSI {} dBm), waiting {}ms (retry {})",
-                        rssi,
+                        "Channel busy, waiting {}ms (retry {})",
                         ms,
                         self.csma.retries()
                     );
-                    // Stay in RX mode during backoff to detect channel activity
                     FreeRtos::delay_ms(ms);
                 }
                 CsmaResult::GiveUp => {
@@ -515,8 +1401,12 @@ impl<'d> LoRaRadio<'d> {
         }
 
         // Set packet length for this transmission
-        let packet_params =
-            build_lora_packet_params(PREAMBLE_LENGTH, data.len() as u8, true, false);
+        let packet_params = build_lora_packet_params(
+            PREAMBLE_LENGTH,
+            data.len() as u8,
+            &self.lora_params(),
+            false,
+        );
         self.device
             .execute_command(SetPacketParams {
                 params: packet_params,
@@ -539,6 +1429,7 @@ impl<'d> LoRaRadio<'d> {
         self.wait_busy()?;
 
         // Start TX
+        let transmit_start = Instant::now();
         self.device
             .execute_command(SetTx {
                 timeout: Timeout(0),
@@ -547,6 +1438,16 @@ impl<'d> LoRaRadio<'d> {
 
         // Wait for TX done
         self.wait_tx_done()?;
+        let actual_airtime = transmit_start.elapsed();
+        let actual_airtime_us = actual_airtime.as_micros() as u64;
+        self.duty_cycle
+            .record_actual_airtime(airtime_us, actual_airtime_us);
+        if actual_airtime_us > airtime_us + airtime_us / 10 {
+            warn!(
+                "TX took {}us on air, over 10% above the calculated {}us",
+                actual_airtime_us, airtime_us
+            );
+        }
 
         // Return to standby
         self.device
@@ -555,7 +1456,11 @@ impl<'d> LoRaRadio<'d> {
             })
             .map_err(RadioError::Command)?;
 
-        Ok(())
+        if let Some(stats) = &self.stats {
+            stats.lora_radio.record_airtime(actual_airtime_us);
+        }
+
+        Ok(actual_airtime)
     }
 
     /// Receive a packet (blocking with timeout).
@@ -566,6 +1471,14 @@ impl<'d> LoRaRadio<'d> {
             return Err(RadioError::NotInitialized);
         }
 
+        if self.sleeping {
+            return Err(RadioError::Asleep);
+        }
+
+        if self.monitoring {
+            return self.receive_monitoring(timeout_ms);
+        }
+
         // Clear IRQ flags
         self.device
             .execute_command(ClearIrqStatus {
@@ -598,6 +1511,43 @@ impl<'d> LoRaRadio<'d> {
             return Ok(None);
         }
 
+        let packet = self.read_received_packet()?;
+
+        // Return to standby
+        self.device
+            .execute_command(SetStandby {
+                config: StandbyConfig::Rc,
+            })
+            .map_err(RadioError::Command)?;
+
+        Ok(packet)
+    }
+
+    /// Receive while in monitor mode.
+    ///
+    /// The radio is already in continuous RX, so neither `SetRx` nor
+    /// `SetStandby` is issued. IRQ flags are not cleared up front either, since
+    /// that would discard a packet that arrived between calls. Continuous RX
+    /// never raises the TIMEOUT IRQ, so the software timeout means "no packet".
+    fn receive_monitoring(
+        &mut self,
+        timeout_ms: u32,
+    ) -> Result<Option<ReceivedPacket>, RadioError> {
+        let irq = match self.wait_rx_done(timeout_ms) {
+            Ok(irq) => irq,
+            Err(RadioError::Timeout) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+
+        if !irq.contains(IrqMask::RX_DONE) {
+            return Ok(None);
+        }
+
+        self.read_received_packet()
+    }
+
+    /// Read the packet from the RX buffer after RX_DONE.
+    fn read_received_packet(&mut self) -> Result<Option<ReceivedPacket>, RadioError> {
         // Get RX buffer status
         let status = self
             .device
@@ -623,19 +1573,18 @@ impl<'d> LoRaRadio<'d> {
             .device
             .execute_command(GetPacketStatus)
             .map_err(RadioError::Command)?;
-
-        // Return to standby
-        self.device
-            .execute_command(SetStandby {
-                config: StandbyConfig::Rc,
-            })
-            .map_err(RadioError::Command)?;
+        self.wait_busy()?;
 
         // LoRa mode: status[0]=RSSI (-val/2 dBm), status[1]=SNR (val/4 dB)
         let rssi = -(packet_status.packet_status.status[0] as i16) / 2;
         let snr = (packet_status.packet_status.status[1] as i8) / 4;
 
-        Ok(Some(ReceivedPacket { data, rssi, snr }))
+        Ok(Some(ReceivedPacket {
+            data,
+            rssi,
+            snr,
+            channel: self.channel,
+        }))
     }
 
     /// Wait for TX to complete using interrupt-driven signaling.
@@ -675,6 +1624,8 @@ impl<'d> LoRaRadio<'d> {
     /// Wait for RX to complete using interrupt-driven signaling.
     ///
     /// Blocks until DIO1 fires (RX_DONE or TIMEOUT) or software timeout expires.
+    /// A packet that failed its CRC is counted and reported without RX_DONE,
+    /// so callers discard it instead of passing it to the transport.
     fn wait_rx_done(&mut self, timeout_ms: u32) -> Result<IrqMask, RadioError> {
         // Add 100ms margin for software overhead
         let wait_timeout_ms = timeout_ms.saturating_add(100);
@@ -702,6 +1653,14 @@ impl<'d> LoRaRadio<'d> {
                     .map_err(RadioError::Command)?;
                 // Re-enable interrupt after clearing source
                 self.dio1.enable_interrupt().map_err(RadioError::Gpio)?;
+
+                if irq.irq_mask.contains(IrqMask::CRC_ERR) {
+                    debug!("LoRa RX CRC error, discarding packet");
+                    if let Some(stats) = &self.stats {
+                        stats.lora_radio.record_crc_error();
+                    }
+                    return Ok(irq.irq_mask - IrqMask::RX_DONE);
+                }
                 return Ok(irq.irq_mask);
             }
 
@@ -739,19 +1698,23 @@ impl<'d> Drop for LoRaRadio<'d> {
 /// Build LoRa packet parameters as raw bytes.
 ///
 /// Format: [preamble_hi, preamble_lo, header_type, payload_len, crc_on, invert_iq, 0, 0, 0]
+///
+/// With `params.implicit_header_len` set, the header type is implicit and the
+/// length byte is the fixed length rather than `payload_len`.
 fn build_lora_packet_params(
     preamble: u16,
     payload_len: u8,
-    crc_enabled: bool,
+    params: &LoRaParams,
     invert_iq: bool,
 ) -> PacketParams {
+    let (header_type, length) = params.header_params(payload_len);
     PacketParams {
         params: [
             (preamble >> 8) as u8,   // Preamble high byte
             (preamble & 0xFF) as u8, // Preamble low byte
-            0x00,                    // Header type: 0=explicit, 1=implicit
-            payload_len,
-            if crc_enabled { 0x01 } else { 0x00 },
+            header_type,             // Header type: 0=explicit, 1=implicit
+            length,
+            if params.crc_enabled { 0x01 } else { 0x00 },
             if invert_iq { 0x01 } else { 0x00 },
             0,
             0,
@@ -769,6 +1732,8 @@ pub struct ReceivedPacket {
     pub rssi: i16,
     /// SNR in dB.
     pub snr: i8,
+    /// Channel the radio was tuned to when the packet arrived.
+    pub channel: LoRaChannel,
 }
 
 /// Radio errors.
@@ -792,6 +1757,18 @@ pub enum RadioError {
     PacketTooLarge { size: usize, max: usize },
     /// Empty packet.
     EmptyPacket,
+    /// Packet length differs from the fixed implicit header length.
+    LengthMismatch { size: usize, expected: usize },
+    /// Operation not allowed while in listen-only monitor mode.
+    InMonitorMode,
+    /// Operation not allowed while the radio is asleep.
+    Asleep,
+    /// Channel bandwidth the SX1262 can't be configured for (Hz).
+    UnsupportedBandwidth(u32),
+    /// Failed to spawn the dedicated radio thread.
+    ThreadSpawn(std::io::Error),
+    /// The dedicated radio thread has stopped (panicked or failed to initialize).
+    ThreadStopped,
 }
 
 impl fmt::Display for RadioError {
@@ -808,6 +1785,18 @@ impl fmt::Display for RadioError {
                 write!(f, "packet too large: {} bytes (max {})", size, max)
             }
             Self::EmptyPacket => write!(f, "empty packet"),
+            Self::LengthMismatch { size, expected } => {
+                write!(
+                    f,
+                    "packet length {} bytes (implicit header expects {})",
+                    size, expected
+                )
+            }
+            Self::InMonitorMode => write!(f, "radio in monitor mode"),
+            Self::Asleep => write!(f, "radio asleep"),
+            Self::UnsupportedBandwidth(hz) => write!(f, "unsupported bandwidth: {} Hz", hz),
+            Self::ThreadSpawn(e) => write!(f, "failed to spawn radio thread: {}", e),
+            Self::ThreadStopped => write!(f, "radio thread stopped"),
         }
     }
 }
@@ -0,0 +1,219 @@
+//! Dedicated OS thread that owns the LoRa radio.
+//!
+//! The DIO1 interrupt must be subscribed and re-enabled from the same thread,
+//! but `tokio::task::spawn_blocking` runs each call on whichever pool thread
+//! is free. [`RadioThread`] moves the [`LoRaRadio`] onto a single thread for
+//! its whole lifetime (including [`LoRaRadio::init`] and drop) and forwards
+//! work to it over a channel, so async callers never touch the driver.
+//! Settings without a command of their own (channel, sync word, CSMA,
+//! monitor mode, ...) are reached with [`RadioThread::with_radio`].
+//!
+//! # Backpressure
+//!
+//! The transport can hand over packets faster than LoRa can send them.
+//! [`RadioThread::queue_transmit`] takes one of [`MAX_PENDING_TX`] permits
+//! before queueing a packet and returns without waiting for it to be sent.
+//! The radio thread releases the permit once the transmission has finished,
+//! so callers can keep up to [`MAX_PENDING_TX`] packets in flight, then wait
+//! for a slot instead of growing the command queue.
+
+use super::radio::{LoRaRadio, RadioError, ReceivedPacket};
+use log::{debug, info};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use tokio::sync::{oneshot, OwnedSemaphorePermit, Semaphore};
+
+/// Stack size for the radio thread.
+///
+/// The ESP-IDF pthread default (3 KB) is too small for SPI transfers plus
+/// logging.
+const RADIO_THREAD_STACK_SIZE: usize = 8 * 1024;
+
+/// Maximum packets queued for or being sent by the radio thread.
+///
+/// An SF7 packet at the MTU takes about 0.5 s on air, so 4 packets are a
+/// couple of seconds of backlog: enough to keep the radio busy without
+/// holding stale packets.
+pub const MAX_PENDING_TX: usize = 4;
+
+/// Work sent to the radio thread.
+enum Command {
+    Transmit {
+        data: Vec<u8>,
+        reply: oneshot::Sender<Result<(), RadioError>>,
+        /// Released when the transmission finishes.
+        _permit: OwnedSemaphorePermit,
+    },
+    Receive {
+        timeout_ms: u32,
+        reply: oneshot::Sender<Result<Option<ReceivedPacket>, RadioError>>,
+    },
+    Sleep {
+        reply: oneshot::Sender<Result<(), RadioError>>,
+    },
+    Wake {
+        reply: oneshot::Sender<Result<(), RadioError>>,
+    },
+    /// Run a closure against the radio; it sends its own reply.
+    WithRadio(Box<dyn FnOnce(&mut LoRaRadio<'static>) + Send>),
+}
+
+/// A transmission queued with [`RadioThread::queue_transmit`].
+///
+/// Dropping it doesn't cancel the transmission, only discards its result.
+#[must_use = "the transmission result is only reported through wait()"]
+pub struct PendingTx {
+    response: oneshot::Receiver<Result<(), RadioError>>,
+}
+
+impl PendingTx {
+    /// Wait until the radio thread has sent the packet.
+    pub async fn wait(self) -> Result<(), RadioError> {
+        self.response.await.map_err(|_| RadioError::ThreadStopped)?
+    }
+}
+
+/// Handle to a radio pinned to its own OS thread.
+///
+/// Cloning the handle is cheap; the thread stops and drops the radio once
+/// every handle is gone.
+#[derive(Clone)]
+pub struct RadioThread {
+    commands: mpsc::Sender<Command>,
+    /// Shared by all handles, bounds queued transmissions.
+    tx_permits: Arc<Semaphore>,
+}
+
+impl RadioThread {
+    /// Move the radio onto a new thread and initialize it there.
+    ///
+    /// The radio is (re)initialized on the new thread so the DIO1 interrupt
+    /// subscription belongs to it, even if [`LoRaRadio::init`] already ran
+    /// elsewhere. Blocks until initialization finishes.
+    pub fn spawn(mut radio: LoRaRadio<'static>) -> Result<Self, RadioError> {
+        let (commands, command_rx) = mpsc::channel::<Command>();
+        let (init_tx, init_rx) = mpsc::channel();
+
+        thread::Builder::new()
+            .name("lora-radio".to_string())
+            .stack_size(RADIO_THREAD_STACK_SIZE)
+            .spawn(move || {
+                let init = radio.init();
+                let failed = init.is_err();
+                let _ = init_tx.send(init);
+                if failed {
+                    return;
+                }
+
+                // Ends when every handle has been dropped
+                for command in command_rx {
+                    match command {
+                        Command::Transmit {
+                            data,
+                            reply,
+                            _permit,
+                        } => {
+                            let _ = reply.send(radio.transmit(&data));
+                        }
+                        Command::Receive { timeout_ms, reply } => {
+                            let _ = reply.send(radio.receive(timeout_ms));
+                        }
+                        Command::Sleep { reply } => {
+                            let _ = reply.send(radio.sleep());
+                        }
+                        Command::Wake { reply } => {
+                            let _ = reply.send(radio.wake());
+                        }
+                        Command::WithRadio(f) => f(&mut radio),
+                    }
+                }
+                debug!("All radio handles dropped, stopping radio thread");
+            })
+            .map_err(RadioError::ThreadSpawn)?;
+
+        init_rx.recv().map_err(|_| RadioError::ThreadStopped)??;
+        info!("LoRa radio running on dedicated thread");
+        Ok(Self {
+            commands,
+            tx_permits: Arc::new(Semaphore::new(MAX_PENDING_TX)),
+        })
+    }
+
+    /// Transmit a packet on the radio thread and wait until it has been sent.
+    ///
+    /// Waits for a free slot first if [`MAX_PENDING_TX`] packets are already
+    /// queued or transmitting.
+    pub async fn transmit(&self, data: Vec<u8>) -> Result<(), RadioError> {
+        self.queue_transmit(data).await?.wait().await
+    }
+
+    /// Queue a packet for transmission without waiting for it to be sent.
+    ///
+    /// Waits for a free slot if [`MAX_PENDING_TX`] packets are already
+    /// queued or transmitting. Await [`PendingTx::wait`] for the result.
+    pub async fn queue_transmit(&self, data: Vec<u8>) -> Result<PendingTx, RadioError> {
+        let permit = self
+            .tx_permits
+            .clone()
+            .acquire_owned()
+            .await
+            .map_err(|_| RadioError::ThreadStopped)?;
+        let (reply, response) = oneshot::channel();
+        self.send(Command::Transmit {
+            data,
+            reply,
+            _permit: permit,
+        })?;
+        Ok(PendingTx { response })
+    }
+
+    /// Number of packets queued for or being sent by the radio thread.
+    pub fn pending_tx(&self) -> usize {
+        MAX_PENDING_TX - self.tx_permits.available_permits()
+    }
+
+    /// Wait up to `timeout_ms` for a packet on the radio thread.
+    pub async fn receive(&self, timeout_ms: u32) -> Result<Option<ReceivedPacket>, RadioError> {
+        let (reply, response) = oneshot::channel();
+        self.send(Command::Receive { timeout_ms, reply })?;
+        response.await.map_err(|_| RadioError::ThreadStopped)?
+    }
+
+    /// Put the radio to sleep on the radio thread.
+    pub async fn sleep(&self) -> Result<(), RadioError> {
+        let (reply, response) = oneshot::channel();
+        self.send(Command::Sleep { reply })?;
+        response.await.map_err(|_| RadioError::ThreadStopped)?
+    }
+
+    /// Wake the radio on the radio thread.
+    pub async fn wake(&self) -> Result<(), RadioError> {
+        let (reply, response) = oneshot::channel();
+        self.send(Command::Wake { reply })?;
+        response.await.map_err(|_| RadioError::ThreadStopped)?
+    }
+
+    /// Run `f` with exclusive access to the radio on the radio thread.
+    ///
+    /// An escape hatch for driver methods without a dedicated command, such
+    /// as [`LoRaRadio::set_channel`], [`LoRaRadio::set_sync_word`],
+    /// [`LoRaRadio::self_test`] or [`LoRaRadio::set_stats`]. `f` runs between
+    /// queued commands and blocks the radio thread, so keep it short.
+    pub async fn with_radio<F, T>(&self, f: F) -> Result<T, RadioError>
+    where
+        F: FnOnce(&mut LoRaRadio<'static>) -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let (reply, response) = oneshot::channel();
+        self.send(Command::WithRadio(Box::new(move |radio| {
+            let _ = reply.send(f(radio));
+        })))?;
+        response.await.map_err(|_| RadioError::ThreadStopped)
+    }
+
+    fn send(&self, command: Command) -> Result<(), RadioError> {
+        self.commands
+            .send(command)
+            .map_err(|_| RadioError::ThreadStopped)
+    }
+}
@@ -5,7 +5,7 @@
 
 use std::time::{Duration, Instant};
 
-/// Time-to-live for queued messages. Messages older than this are dropped
+/// Default time-to-live for queued messages. Messages older than this are dropped
 /// to prevent stale messages from being sent if a link takes too long to
 /// establish or never activates. 60 seconds is long enough for most link
 /// establishments (typically 2-10 seconds) while preventing indefinite buildup.
@@ -24,21 +24,37 @@ pub struct QueuedMessage {
     text: String,
     /// When the message was queued.
     queued_at: Instant,
+    /// How long the message may wait before it is dropped.
+    ttl: Duration,
 }
 
 impl QueuedMessage {
-    /// Create a new queued message with the current timestamp.
+    /// Create a new queued message with the current timestamp and
+    /// [`QUEUE_MESSAGE_TTL`].
     pub fn new(text: String) -> Self {
+        Self::with_ttl(text, QUEUE_MESSAGE_TTL)
+    }
+
+    /// Create a new queued message that expires after `ttl`.
+    ///
+    /// Lets urgent messages (e.g. a status ping) expire quickly while others
+    /// wait longer for the link.
+    pub fn with_ttl(text: String, ttl: Duration) -> Self {
         Self {
             text,
             queued_at: Instant::now(),
+            ttl,
         }
     }
 
     /// Create a queued message with a specific timestamp (for testing).
     #[cfg(test)]
     pub fn with_timestamp(text: String, queued_at: Instant) -> Self {
-        Self { text, queued_at }
+        Self {
+            text,
+            queued_at,
+            ttl: QUEUE_MESSAGE_TTL,
+        }
     }
 
     /// Returns the message text.
@@ -51,9 +67,24 @@ impl QueuedMessage {
         self.queued_at
     }
 
-    /// Returns true if this message has expired based on QUEUE_MESSAGE_TTL.
+    /// Returns the message's time-to-live.
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Returns true if this message has outlived its TTL.
     pub fn is_expired(&self) -> bool {
-        self.queued_at.elapsed() > QUEUE_MESSAGE_TTL
+        self.is_expired_after(self.ttl)
+    }
+
+    /// Returns how long until this message expires (zero once expired).
+    pub fn remaining_ttl(&self) -> Duration {
+        self.ttl.saturating_sub(self.queued_at.elapsed())
+    }
+
+    /// Returns true if this message expires within `threshold`.
+    pub fn is_near_expiry(&self, threshold: Duration) -> bool {
+        self.remaining_ttl() <= threshold
     }
 
     /// Returns true if this message would be expired after the given duration.
@@ -105,6 +136,24 @@ mod tests {
         assert!(!old_msg.is_expired_after(Duration::from_secs(10)));
     }
 
+    #[esp32_test]
+    fn test_custom_ttl() {
+        let msg = QueuedMessage::with_ttl("ping".to_string(), Duration::from_secs(10));
+        assert_eq!(msg.ttl(), Duration::from_secs(10));
+        assert!(!msg.is_expired());
+        assert!(msg.remaining_ttl() <= Duration::from_secs(10));
+        assert!(msg.remaining_ttl() > Duration::from_secs(9));
+        assert!(msg.is_near_expiry(Duration::from_secs(10)));
+        assert!(!msg.is_near_expiry(Duration::from_secs(5)));
+
+        // An already-elapsed TTL expires immediately
+        let msg = QueuedMessage::with_ttl("late".to_string(), Duration::ZERO);
+        std::thread::sleep(Duration::from_millis(1));
+        assert!(msg.is_expired());
+        assert_eq!(msg.remaining_ttl(), Duration::ZERO);
+        assert_eq!(QueuedMessage::new("x".to_string()).ttl(), QUEUE_MESSAGE_TTL);
+    }
+
     #[esp32_test]
     fn test_queue_constants() {
         // Verify constants are reasonable
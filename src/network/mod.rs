@@ -21,6 +21,7 @@
 //! println!("Connected, IP: {:?}", network.ip_addr());
 //! ```
 
+use crate::config::WpaType;
 use std::net::IpAddr;
 
 #[cfg(feature = "esp32")]
@@ -38,7 +39,7 @@ pub use wifi::WifiNetwork;
 #[cfg(not(feature = "esp32"))]
 pub use host::HostNetwork;
 
-pub use stats_server::{NodeStats, StatsServer, DEFAULT_STATS_PORT};
+pub use stats_server::{LoRaStats, NodeStats, StatsServer, DEFAULT_STATS_PORT};
 
 /// Network provider abstraction.
 ///
@@ -54,10 +55,21 @@ pub trait NetworkProvider: Send {
     /// Check if the network is connected.
     fn is_connected(&self) -> bool;
 
+    /// Re-establish a dropped connection.
+    ///
+    /// - On ESP32: Reconnects WiFi using the credentials stored in NVS
+    /// - On Host: No-op (the OS manages connectivity)
+    fn reconnect(&mut self) -> Result<(), NetworkError>;
+
     /// Get the local IP address.
     ///
     /// Returns `None` if not connected.
     fn ip_addr(&self) -> Option<IpAddr>;
+
+    /// Get the security mode of the connected network.
+    ///
+    /// Returns `None` if not connected.
+    fn wpa_type(&self) -> Option<WpaType>;
 }
 
 /// Network errors.
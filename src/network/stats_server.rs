@@ -1,38 +1,63 @@
 //! HTTP stats server for node monitoring.
 //!
-//! Provides a simple `/stats` endpoint that returns node statistics as JSON.
+//! Provides a simple `/stats` endpoint that returns node statistics as JSON,
+//! and a `/health` endpoint for container health probes.
 //! Uses `tiny_http` which works on both host and ESP32 (via std::net).
 //!
+//! Both endpoints reply with `Content-Type: application/json; charset=utf-8`.
+//! Every response, including redirects and errors, carries
+//! `Cache-Control: no-cache, no-store`.
+//!
 //! # Example Response
 //!
 //! ```json
 //! {
 //!   "uptime_secs": 3600,
 //!   "identity_hash": "/a1b2c3d4.../",
+//!   "epoch": 1,
 //!   "interfaces": {
-//!     "lora": { "tx": 150, "rx": 230 },
-//!     "ble": { "tx": 50, "rx": 45 },
-//!     "testnet": { "tx": 500, "rx": 480 }
+//!     "lora": { "tx": 150, "rx": 230, "tx_since_reset": 10, "rx_since_reset": 12, "lora_airtime_us_total": 5400000, "lora_crc_errors": 2, "lora_filtered_rx": 40 },
+//!     "ble": { "tx": 50, "rx": 45, "tx_since_reset": 0, "rx_since_reset": 3, "reassembler_oldest_pending_ms": 0 },
+//!     "testnet": { "tx": 500, "rx": 480, "tx_since_reset": 40, "rx_since_reset": 38 }
 //!   },
 //!   "routing": {
 //!     "announce_cache_size": 25,
-//!     "path_table_size": 8
+//!     "path_table_size": 8,
+//!     "known_destinations": 12,
+//!     "announces_lagged": 0
 //!   },
 //!   "queue": {
 //!     "queued_messages": 3,
+//!     "peak_queued_messages": 7,
 //!     "expired_messages": 12,
 //!     "dropped_on_close": 5
 //!   }
 //! }
 //! ```
+//!
+//! # Counter Resets
+//!
+//! Packet counters are never zeroed, so `tx`/`rx` stay monotonic (as the
+//! Prometheus data model requires). [`NodeStats::reset_counters`] instead
+//! records a baseline and bumps `epoch`; the `*_since_reset` fields report
+//! the delta from that baseline.
+//!
+//! # Health Probe
+//!
+//! `GET /health` returns HTTP 200 with
+//! `{"status":"ok","wifi":true,"lora":true}` when every critical interface is
+//! up, or HTTP 503 with `"status":"degraded"` otherwise. WiFi (the host network
+//! on host builds) is always critical. LoRa only counts once a radio is
+//! attached, so host builds without one can still be healthy.
 
 use log::{error, info, warn};
+use std::io::Cursor;
 use std::net::IpAddr;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::thread;
-use std::time::Instant;
-use tiny_http::{Method, Response, Server};
+use std::time::{Duration, Instant};
+use tiny_http::{Header, Method, Response, Server};
 
 /// Default port for the stats server.
 pub const DEFAULT_STATS_PORT: u16 = 8080;
@@ -44,6 +69,12 @@ pub struct InterfaceStats {
     pub tx: AtomicUsize,
     /// Packets received.
     pub rx: AtomicUsize,
+    /// `tx` value at the last counter reset.
+    tx_at_reset: AtomicUsize,
+    /// `rx` value at the last counter reset.
+    rx_at_reset: AtomicUsize,
+    /// Epoch of the last counter reset (0 = never reset).
+    reset_at_epoch: AtomicU64,
 }
 
 impl InterfaceStats {
@@ -62,12 +93,102 @@ impl InterfaceStats {
         self.rx.fetch_add(1, Ordering::Relaxed);
     }
 
+    /// Packets transmitted since the last counter reset.
+    pub fn tx_since_reset(&self) -> usize {
+        self.tx
+            .load(Ordering::Relaxed)
+            .saturating_sub(self.tx_at_reset.load(Ordering::Relaxed))
+    }
+
+    /// Packets received since the last counter reset.
+    pub fn rx_since_reset(&self) -> usize {
+        self.rx
+            .load(Ordering::Relaxed)
+            .saturating_sub(self.rx_at_reset.load(Ordering::Relaxed))
+    }
+
+    /// Epoch of the last counter reset (0 if never reset).
+    pub fn reset_at_epoch(&self) -> u64 {
+        self.reset_at_epoch.load(Ordering::Relaxed)
+    }
+
+    /// Record the current counters as the baseline for `*_since_reset`.
+    ///
+    /// Increments racing with this call land in the new delta rather than
+    /// being lost, since the absolute counters are never modified.
+    fn mark_reset(&self, epoch: u64) {
+        self.tx_at_reset
+            .store(self.tx.load(Ordering::Relaxed), Ordering::Relaxed);
+        self.rx_at_reset
+            .store(self.rx.load(Ordering::Relaxed), Ordering::Relaxed);
+        self.reset_at_epoch.store(epoch, Ordering::Relaxed);
+    }
+
     /// Serialize to JSON.
     fn to_json(&self) -> String {
+        self.to_json_with("")
+    }
+
+    /// Serialize to JSON, appending `extra` (`,"key":value` pairs) to the object.
+    fn to_json_with(&self, extra: &str) -> String {
         format!(
-            r#"{{"tx":{},"rx":{}}}"#,
+            r#"{{"tx":{},"rx":{},"tx_since_reset":{},"rx_since_reset":{}{}}}"#,
             self.tx.load(Ordering::Relaxed),
-            self.rx.load(Ordering::Relaxed)
+            self.rx.load(Ordering::Relaxed),
+            self.tx_since_reset(),
+            self.rx_since_reset(),
+            extra
+        )
+    }
+}
+
+/// LoRa radio statistics beyond the packet counts in [`InterfaceStats`].
+#[derive(Debug, Default)]
+pub struct LoRaStats {
+    /// Cumulative transmit airtime in microseconds.
+    pub airtime_us_total: AtomicU64,
+    /// Received packets discarded for a failed radio CRC.
+    ///
+    /// Counted separately from the interface's `rx`, which only includes
+    /// packets received intact, to tell RF corruption apart from parse errors.
+    /// Recorded by `LoRaRadio`, which gets these stats from its own
+    /// `set_stats` or from `LoRaInterface::set_stats`.
+    pub crc_errors: AtomicUsize,
+    /// Received packets dropped by a receive filter.
+    ///
+    /// These are also counted in the interface's `rx`.
+    pub filtered_rx: AtomicUsize,
+}
+
+impl LoRaStats {
+    /// Create new LoRa stats.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a received packet discarded for a failed CRC.
+    pub fn record_crc_error(&self) {
+        self.crc_errors.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record a received packet dropped by a receive filter.
+    pub fn record_filtered_rx(&self) {
+        self.filtered_rx.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record airtime used by a successful transmission.
+    pub fn record_airtime(&self, airtime_us: u64) {
+        self.airtime_us_total
+            .fetch_add(airtime_us, Ordering::Relaxed);
+    }
+
+    /// Serialize as `,"key":value` pairs for the `lora` interface object.
+    fn to_json_fields(&self) -> String {
+        format!(
+            r#","lora_airtime_us_total":{},"lora_crc_errors":{},"lora_filtered_rx":{}"#,
+            self.airtime_us_total.load(Ordering::Relaxed),
+            self.crc_errors.load(Ordering::Relaxed),
+            self.filtered_rx.load(Ordering::Relaxed)
         )
     }
 }
@@ -81,6 +202,8 @@ pub struct RoutingStats {
     pub path_table_size: AtomicUsize,
     /// Number of known destinations.
     pub known_destinations: AtomicUsize,
+    /// Cumulative count of announces skipped because the announce channel lagged.
+    pub announces_lagged: AtomicUsize,
 }
 
 impl RoutingStats {
@@ -92,10 +215,11 @@ impl RoutingStats {
     /// Serialize to JSON.
     fn to_json(&self) -> String {
         format!(
-            r#"{{"announce_cache_size":{},"path_table_size":{},"known_destinations":{}}}"#,
+            r#"{{"announce_cache_size":{},"path_table_size":{},"known_destinations":{},"announces_lagged":{}}}"#,
             self.announce_cache_size.load(Ordering::Relaxed),
             self.path_table_size.load(Ordering::Relaxed),
-            self.known_destinations.load(Ordering::Relaxed)
+            self.known_destinations.load(Ordering::Relaxed),
+            self.announces_lagged.load(Ordering::Relaxed)
         )
     }
 }
@@ -109,6 +233,9 @@ pub struct QueueStats {
     /// Current total queued messages across all destinations.
     /// This should stay below MAX_QUEUED_MESSAGES_PER_DEST * active_links.
     pub queued_messages: AtomicUsize,
+    /// Highest `queued_messages` seen since start (or the last counter reset).
+    /// Use for capacity planning; see [`record_queued`](Self::record_queued).
+    pub peak_queued_messages: AtomicUsize,
     /// Cumulative count of messages expired due to TTL.
     /// High values may indicate links failing to establish.
     pub expired_messages: AtomicUsize,
@@ -123,11 +250,27 @@ impl QueueStats {
         Self::default()
     }
 
+    /// Record a newly queued message, updating the peak depth.
+    pub fn record_queued(&self) {
+        let depth = self.queued_messages.fetch_add(1, Ordering::Relaxed) + 1;
+        self.peak_queued_messages
+            .fetch_max(depth, Ordering::Relaxed);
+    }
+
+    /// Restart peak tracking from the current depth.
+    fn reset_peak(&self) {
+        self.peak_queued_messages.store(
+            self.queued_messages.load(Ordering::Relaxed),
+            Ordering::Relaxed,
+        );
+    }
+
     /// Serialize to JSON.
     fn to_json(&self) -> String {
         format!(
-            r#"{{"queued_messages":{},"expired_messages":{},"dropped_on_close":{}}}"#,
+            r#"{{"queued_messages":{},"peak_queued_messages":{},"expired_messages":{},"dropped_on_close":{}}}"#,
             self.queued_messages.load(Ordering::Relaxed),
+            self.peak_queued_messages.load(Ordering::Relaxed),
             self.expired_messages.load(Ordering::Relaxed),
             self.dropped_on_close.load(Ordering::Relaxed)
         )
@@ -142,18 +285,33 @@ impl QueueStats {
 pub struct NodeStats {
     /// When the node started.
     start_time: Instant,
+    /// Number of counter resets so far.
+    epoch: AtomicU64,
     /// Node identity hash (hex string).
     pub identity_hash: String,
     /// LoRa interface statistics.
     pub lora: InterfaceStats,
+    /// LoRa radio statistics (airtime, CRC errors, filtered packets),
+    /// reported alongside [`lora`](Self::lora).
+    pub lora_radio: LoRaStats,
     /// BLE interface statistics.
     pub ble: InterfaceStats,
+    /// Age in milliseconds of the oldest pending BLE fragment reassembly
+    /// (0 if none). See [`record_reassembler_age`](Self::record_reassembler_age).
+    pub ble_reassembler_oldest_pending_ms: AtomicU64,
     /// Testnet (TCP) interface statistics.
     pub testnet: InterfaceStats,
     /// Routing statistics.
     pub routing: RoutingStats,
     /// Message queue statistics for memory monitoring.
     pub queue: QueueStats,
+    /// Whether WiFi (host network on host builds) is connected.
+    pub wifi_connected: AtomicBool,
+    /// Whether a LoRa radio is attached to these stats. Only then does a
+    /// radio that isn't initialized make the node unhealthy.
+    pub lora_configured: AtomicBool,
+    /// Whether the LoRa radio has been initialized.
+    pub lora_initialized: AtomicBool,
 }
 
 impl NodeStats {
@@ -165,12 +323,18 @@ impl NodeStats {
     pub fn new(identity_hash: String) -> Self {
         Self {
             start_time: Instant::now(),
+            epoch: AtomicU64::new(0),
             identity_hash,
             lora: InterfaceStats::new(),
+            lora_radio: LoRaStats::new(),
             ble: InterfaceStats::new(),
+            ble_reassembler_oldest_pending_ms: AtomicU64::new(0),
             testnet: InterfaceStats::new(),
             routing: RoutingStats::new(),
             queue: QueueStats::new(),
+            wifi_connected: AtomicBool::new(false),
+            lora_configured: AtomicBool::new(false),
+            lora_initialized: AtomicBool::new(false),
         }
     }
 
@@ -179,14 +343,66 @@ impl NodeStats {
         self.start_time.elapsed().as_secs()
     }
 
+    /// Get the current reset epoch (0 if counters were never reset).
+    pub fn epoch(&self) -> u64 {
+        self.epoch.load(Ordering::Relaxed)
+    }
+
+    /// Start a new "since reset" window for the interface packet counters.
+    ///
+    /// The absolute counters keep increasing; only the `*_since_reset`
+    /// deltas start over. The peak queue depth restarts from the current
+    /// depth. Returns the new epoch.
+    pub fn reset_counters(&self) -> u64 {
+        let epoch = self.epoch.fetch_add(1, Ordering::Relaxed) + 1;
+        self.lora.mark_reset(epoch);
+        self.ble.mark_reset(epoch);
+        self.testnet.mark_reset(epoch);
+        self.queue.reset_peak();
+        epoch
+    }
+
+    /// Record the BLE reassembler's
+    /// [`oldest_pending_age`](crate::ble::Reassembler::oldest_pending_age).
+    pub fn record_reassembler_age(&self, age: Option<Duration>) {
+        let ms = age.map_or(0, |age| age.as_millis() as u64);
+        self.ble_reassembler_oldest_pending_ms
+            .store(ms, Ordering::Relaxed);
+    }
+
+    /// Check if all critical interfaces are up.
+    ///
+    /// LoRa only counts once a radio is configured, so a node without one
+    /// (e.g. the host build) can still be healthy.
+    pub fn is_healthy(&self) -> bool {
+        let lora_ok = !self.lora_configured.load(Ordering::Relaxed)
+            || self.lora_initialized.load(Ordering::Relaxed);
+        self.wifi_connected.load(Ordering::Relaxed) && lora_ok
+    }
+
+    /// Serialize interface health to JSON for the `/health` endpoint.
+    pub fn health_json(&self) -> String {
+        format!(
+            r#"{{"status":"{}","wifi":{},"lora":{}}}"#,
+            if self.is_healthy() { "ok" } else { "degraded" },
+            self.wifi_connected.load(Ordering::Relaxed),
+            self.lora_initialized.load(Ordering::Relaxed)
+        )
+    }
+
     /// Serialize all statistics to JSON.
     pub fn to_json(&self) -> String {
         format!(
-            r#"{{"uptime_secs":{},"identity_hash":"{}","interfaces":{{"lora":{},"ble":{},"testnet":{}}},"routing":{},"queue":{}}}"#,
+            r#"{{"uptime_secs":{},"identity_hash":"{}","epoch":{},"interfaces":{{"lora":{},"ble":{},"testnet":{}}},"routing":{},"queue":{}}}"#,
             self.uptime_secs(),
-            self.identity_hash,
-            self.lora.to_json(),
-            self.ble.to_json(),
+            json_escape(&self.identity_hash),
+            self.epoch(),
+            self.lora.to_json_with(&self.lora_radio.to_json_fields()),
+            self.ble.to_json_with(&format!(
+                r#","reassembler_oldest_pending_ms":{}"#,
+                self.ble_reassembler_oldest_pending_ms
+                    .load(Ordering::Relaxed)
+            )),
             self.testnet.to_json(),
             self.routing.to_json(),
             self.queue.to_json()
@@ -194,12 +410,96 @@ impl NodeStats {
     }
 }
 
+/// Escape a string for use inside a JSON string literal.
+///
+/// `serde_json` is host-only, so the few string fields in the stats JSON are
+/// escaped by hand (RFC 8259 section 7).
+fn json_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
 impl Default for NodeStats {
     fn default() -> Self {
         Self::new("unknown".to_string())
     }
 }
 
+/// `Content-Type` of the JSON endpoints (`/stats` and `/health`).
+const JSON_CONTENT_TYPE: &str = "application/json; charset=utf-8";
+
+/// `Cache-Control` of the JSON endpoints, so proxies and browsers never serve
+/// stale stats.
+const NO_CACHE: &str = "no-cache, no-store";
+
+/// Headers attached to stats server responses.
+struct ResponseHeaders {
+    content_type: Header,
+    no_cache: Header,
+    location: Header,
+    allow_get: Header,
+}
+
+impl ResponseHeaders {
+    fn new() -> Self {
+        let header = |field: &str, value: &str| {
+            Header::from_bytes(field.as_bytes(), value.as_bytes()).expect("static header")
+        };
+        Self {
+            content_type: header("Content-Type", JSON_CONTENT_TYPE),
+            no_cache: header("Cache-Control", NO_CACHE),
+            location: header("Location", "/stats"),
+            allow_get: header("Allow", "GET"),
+        }
+    }
+}
+
+/// Build the response to a `method` request for `path`.
+///
+/// Every response carries `Cache-Control: no-cache, no-store`, so neither the
+/// stats nor a redirect or error is served from a cache.
+fn build_response(
+    headers: &ResponseHeaders,
+    method: &Method,
+    path: &str,
+    stats: &NodeStats,
+) -> Response<Cursor<Vec<u8>>> {
+    let response = if method != &Method::Get {
+        // Only allow GET requests
+        Response::from_string("Method Not Allowed")
+            .with_status_code(405)
+            .with_header(headers.allow_get.clone())
+    } else if path == "/stats" || path == "/stats/" {
+        Response::from_string(stats.to_json())
+            .with_header(headers.content_type.clone())
+            .with_status_code(200)
+    } else if path == "/health" {
+        let status = if stats.is_healthy() { 200 } else { 503 };
+        Response::from_string(stats.health_json())
+            .with_header(headers.content_type.clone())
+            .with_status_code(status)
+    } else if path == "/" {
+        // Redirect root to /stats
+        Response::from_string("See /stats for node statistics")
+            .with_status_code(302)
+            .with_header(headers.location.clone())
+    } else {
+        Response::from_string("Not Found").with_status_code(404)
+    };
+    response.with_header(headers.no_cache.clone())
+}
+
 /// HTTP stats server.
 ///
 /// Runs in a background thread and serves node statistics as JSON.
@@ -264,13 +564,7 @@ impl StatsServer {
         shutdown: Arc<std::sync::atomic::AtomicBool>,
     ) {
         // Pre-create headers to avoid repeated allocations
-        let content_type =
-            tiny_http::Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
-                .expect("static header");
-        let location =
-            tiny_http::Header::from_bytes(&b"Location"[..], &b"/stats"[..]).expect("static header");
-        let allow_get =
-            tiny_http::Header::from_bytes(&b"Allow"[..], &b"GET"[..]).expect("static header");
+        let headers = ResponseHeaders::new();
 
         loop {
             // Use Acquire ordering to ensure we see the shutdown flag from stop()
@@ -281,42 +575,10 @@ impl StatsServer {
 
             match server.recv_timeout(std::time::Duration::from_millis(100)) {
                 Ok(Some(request)) => {
-                    // Only allow GET requests
-                    if request.method() != &Method::Get {
-                        let response = Response::from_string("Method Not Allowed")
-                            .with_status_code(405)
-                            .with_header(allow_get.clone());
-                        let _ = request.respond(response);
-                        continue;
-                    }
-
-                    let path = request.url();
-
-                    if path == "/stats" || path == "/stats/" {
-                        let json = stats.to_json();
-                        let response = Response::from_string(json)
-                            .with_header(content_type.clone())
-                            .with_status_code(200);
-
-                        if let Err(e) = request.respond(response) {
-                            warn!("Failed to send response: {}", e);
-                        }
-                    } else if path == "/" {
-                        // Redirect root to /stats
-                        let response = Response::from_string("See /stats for node statistics")
-                            .with_status_code(302)
-                            .with_header(location.clone());
-
-                        if let Err(e) = request.respond(response) {
-                            warn!("Failed to send redirect: {}", e);
-                        }
-                    } else {
-                        // 404 for other paths
-                        let response = Response::from_string("Not Found").with_status_code(404);
-
-                        if let Err(e) = request.respond(response) {
-                            warn!("Failed to send 404: {}", e);
-                        }
+                    let response =
+                        build_response(&headers, request.method(), request.url(), &stats);
+                    if let Err(e) = request.respond(response) {
+                        warn!("Failed to send response: {}", e);
                     }
                 }
                 Ok(None) => {
@@ -383,6 +645,96 @@ mod tests {
         assert!(json.contains("\"queue\":"));
     }
 
+    #[esp32_test]
+    fn test_lora_airtime_total() {
+        let stats = NodeStats::new("abc123".to_string());
+        stats.lora_radio.record_airtime(51_456);
+        stats.lora_radio.record_airtime(51_456);
+
+        assert_eq!(
+            stats.lora_radio.airtime_us_total.load(Ordering::Relaxed),
+            102_912
+        );
+        let json = stats.to_json();
+        assert!(json.contains(r#""rx_since_reset":0,"lora_airtime_us_total":102912,"#));
+        assert_eq!(json.matches("airtime_us_total").count(), 1);
+    }
+
+    #[esp32_test]
+    fn test_lora_crc_errors() {
+        let stats = NodeStats::new("abc123".to_string());
+        stats.lora.record_rx();
+        stats.lora_radio.record_crc_error();
+        stats.lora_radio.record_crc_error();
+
+        assert_eq!(stats.lora.rx.load(Ordering::Relaxed), 1);
+        assert_eq!(stats.lora_radio.crc_errors.load(Ordering::Relaxed), 2);
+        let json = stats.to_json();
+        assert!(json.contains(r#""lora_crc_errors":2,"#));
+        assert_eq!(json.matches("crc_errors").count(), 1);
+    }
+
+    #[esp32_test]
+    fn test_lora_filtered_rx() {
+        let stats = NodeStats::new("abc123".to_string());
+        stats.lora.record_rx();
+        stats.lora.record_rx();
+        stats.lora_radio.record_filtered_rx();
+
+        assert_eq!(stats.lora.rx.load(Ordering::Relaxed), 2);
+        assert_eq!(stats.lora_radio.filtered_rx.load(Ordering::Relaxed), 1);
+        let json = stats.to_json();
+        assert!(json.contains(r#""lora_filtered_rx":1}"#));
+        assert_eq!(json.matches("filtered_rx").count(), 1);
+    }
+
+    #[esp32_test]
+    fn test_reassembler_age_in_ble_stats() {
+        let stats = NodeStats::new("abc123".to_string());
+        assert!(stats
+            .to_json()
+            .contains(r#""rx_since_reset":0,"reassembler_oldest_pending_ms":0}"#));
+
+        stats.record_reassembler_age(Some(Duration::from_millis(1500)));
+        let json = stats.to_json();
+        assert!(json.contains(r#""reassembler_oldest_pending_ms":1500}"#));
+        assert_eq!(json.matches("reassembler_oldest_pending_ms").count(), 1);
+
+        stats.record_reassembler_age(None);
+        assert!(stats
+            .to_json()
+            .contains(r#""reassembler_oldest_pending_ms":0}"#));
+    }
+
+    #[esp32_test]
+    fn test_json_escape() {
+        assert_eq!(json_escape("abc123"), "abc123");
+        assert_eq!(json_escape(r#"a"b\c"#), r#"a\"b\\c"#);
+        assert_eq!(json_escape("\n\u{1}"), r"\n\u0001");
+    }
+
+    #[cfg(not(target_os = "espidf"))]
+    #[esp32_test]
+    fn test_node_stats_json_valid_for_any_identity_hash() {
+        // Every single char in the ASCII range plus a few multi-byte ones,
+        // alone and surrounded by ordinary hex
+        let specials = (0u8..0x80).map(char::from).chain([
+            '\u{7f}',
+            '\u{a0}',
+            '\u{2028}',
+            '\u{fffd}',
+            '\u{1f600}',
+        ]);
+        for c in specials {
+            for hash in [c.to_string(), format!("ab{}cd", c)] {
+                let json = NodeStats::new(hash.clone()).to_json();
+                let value: serde_json::Value = serde_json::from_str(&json)
+                    .unwrap_or_else(|e| panic!("invalid JSON for {:?}: {} ({})", hash, e, json));
+                assert_eq!(value["identity_hash"], hash.as_str());
+            }
+        }
+    }
+
     #[esp32_test]
     fn test_queue_stats_new() {
         let stats = QueueStats::new();
@@ -404,6 +756,23 @@ mod tests {
         assert!(json.contains("\"dropped_on_close\":3"));
     }
 
+    #[esp32_test]
+    fn test_queue_stats_peak_depth() {
+        let stats = NodeStats::new("test".to_string());
+        for _ in 0..3 {
+            stats.queue.record_queued();
+        }
+        stats.queue.queued_messages.fetch_sub(2, Ordering::Relaxed);
+        stats.queue.record_queued();
+        assert_eq!(stats.queue.queued_messages.load(Ordering::Relaxed), 2);
+        assert_eq!(stats.queue.peak_queued_messages.load(Ordering::Relaxed), 3);
+        assert!(stats.to_json().contains("\"peak_queued_messages\":3"));
+
+        // Reset restarts from the current depth, not zero
+        stats.reset_counters();
+        assert_eq!(stats.queue.peak_queued_messages.load(Ordering::Relaxed), 2);
+    }
+
     #[esp32_test]
     fn test_node_stats_uptime() {
         let stats = NodeStats::new("test".to_string());
@@ -411,4 +780,111 @@ mod tests {
         // Uptime should be at least 0 (might be 0 if very fast)
         assert!(stats.uptime_secs() < 10);
     }
+
+    /// Value of the `name` header on `response`, if present.
+    fn header_value<'a>(response: &'a Response<Cursor<Vec<u8>>>, name: &str) -> Option<&'a str> {
+        response
+            .headers()
+            .iter()
+            .find(|h| h.field.equiv(name))
+            .map(|h| h.value.as_str())
+    }
+
+    #[esp32_test]
+    fn test_json_responses_headers() {
+        let headers = ResponseHeaders::new();
+        let stats = NodeStats::new("test".to_string());
+
+        for (path, status) in [("/stats", 200), ("/stats/", 200), ("/health", 503)] {
+            let response = build_response(&headers, &Method::Get, path, &stats);
+            assert_eq!(response.status_code().0, status, "{}", path);
+            assert_eq!(
+                header_value(&response, "Content-Type"),
+                Some("application/json; charset=utf-8"),
+                "{}",
+                path
+            );
+            assert_eq!(
+                header_value(&response, "Cache-Control"),
+                Some("no-cache, no-store"),
+                "{}",
+                path
+            );
+        }
+
+        stats.wifi_connected.store(true, Ordering::Relaxed);
+        let response = build_response(&headers, &Method::Get, "/health", &stats);
+        assert_eq!(response.status_code().0, 200);
+    }
+
+    #[esp32_test]
+    fn test_other_responses_not_cached() {
+        let headers = ResponseHeaders::new();
+        let stats = NodeStats::new("test".to_string());
+
+        let redirect = build_response(&headers, &Method::Get, "/", &stats);
+        assert_eq!(redirect.status_code().0, 302);
+        assert_eq!(header_value(&redirect, "Location"), Some("/stats"));
+
+        let not_found = build_response(&headers, &Method::Get, "/metrics", &stats);
+        assert_eq!(not_found.status_code().0, 404);
+
+        let not_allowed = build_response(&headers, &Method::Post, "/stats", &stats);
+        assert_eq!(not_allowed.status_code().0, 405);
+        assert_eq!(header_value(&not_allowed, "Allow"), Some("GET"));
+
+        for response in [redirect, not_found, not_allowed] {
+            assert_eq!(
+                header_value(&response, "Cache-Control"),
+                Some("no-cache, no-store")
+            );
+            assert_eq!(header_value(&response, "Content-Type"), None);
+        }
+    }
+
+    #[esp32_test]
+    fn test_health_json() {
+        let stats = NodeStats::new("test".to_string());
+        assert!(!stats.is_healthy());
+        assert!(stats.health_json().contains("\"status\":\"degraded\""));
+        assert!(stats.health_json().contains("\"wifi\":false"));
+
+        stats.wifi_connected.store(true, Ordering::Relaxed);
+        // No radio configured: LoRa doesn't count
+        assert!(stats.is_healthy());
+
+        // A configured radio that failed to initialize degrades health
+        stats.lora_configured.store(true, Ordering::Relaxed);
+        assert!(!stats.is_healthy());
+
+        stats.lora_initialized.store(true, Ordering::Relaxed);
+        assert!(stats.is_healthy());
+        assert_eq!(
+            stats.health_json(),
+            r#"{"status":"ok","wifi":true,"lora":true}"#
+        );
+    }
+
+    #[esp32_test]
+    fn test_reset_counters_keeps_absolute_values() {
+        let stats = NodeStats::new("test".to_string());
+        stats.lora.record_tx();
+        stats.lora.record_tx();
+        stats.lora.record_rx();
+
+        assert_eq!(stats.epoch(), 0);
+        assert_eq!(stats.reset_counters(), 1);
+        assert_eq!(stats.lora.reset_at_epoch(), 1);
+
+        stats.lora.record_tx();
+
+        // Absolute counters are monotonic, deltas start from the reset
+        assert_eq!(stats.lora.tx.load(Ordering::Relaxed), 3);
+        assert_eq!(stats.lora.tx_since_reset(), 1);
+        assert_eq!(stats.lora.rx_since_reset(), 0);
+
+        let json = stats.to_json();
+        assert!(json.contains("\"epoch\":1"));
+        assert!(json.contains(r#""lora":{"tx":3,"rx":1,"tx_since_reset":1,"rx_since_reset":0,"lora_airtime_us_total":0,"lora_crc_errors":0,"lora_filtered_rx":0}"#));
+    }
 }
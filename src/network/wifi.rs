@@ -4,11 +4,12 @@
 //! It loads credentials from NVS (configured via BLE) and connects automatically.
 
 use super::{NetworkError, NetworkProvider};
+use crate::config::WpaType;
 use crate::wifi::{load_wifi_config, WifiManager};
 use esp_idf_hal::modem::Modem;
 use esp_idf_svc::eventloop::EspSystemEventLoop;
 use esp_idf_svc::nvs::{EspNvs, NvsDefault};
-use log::{info, warn};
+use log::{debug, info, warn};
 use std::net::IpAddr;
 
 /// NVS namespace for WiFi configuration.
@@ -87,6 +88,18 @@ impl<'a> NetworkProvider for WifiNetwork<'a> {
         self.wifi.is_connected()
     }
 
+    fn reconnect(&mut self) -> Result<(), NetworkError> {
+        self.ip_addr = None;
+
+        // Tear down the stale association first; failure here is expected
+        // when the driver already noticed the drop
+        if let Err(e) = self.wifi.disconnect() {
+            debug!("WiFi disconnect before reconnect failed: {:?}", e);
+        }
+
+        self.connect()
+    }
+
     fn ip_addr(&self) -> Option<IpAddr> {
         if self.is_connected() {
             self.ip_addr
@@ -94,4 +107,8 @@ impl<'a> NetworkProvider for WifiNetwork<'a> {
             None
         }
     }
+
+    fn wpa_type(&self) -> Option<WpaType> {
+        self.wifi.wpa_type()
+    }
 }
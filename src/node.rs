@@ -22,6 +22,7 @@
 //! let (from, data) = node_b.recv_message(timeout).await?;
 //! ```
 
+use crate::announce::{spawn_announce_timer, AnnounceTimerError};
 use log::{debug, warn};
 use rand_core::OsRng;
 use reticulum::destination::link::{Link, LinkEvent, LinkStatus};
@@ -31,6 +32,7 @@ use reticulum::identity::PrivateIdentity;
 use reticulum::iface::tcp_client::TcpClient;
 use reticulum::transport::{Transport, TransportConfig};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::{broadcast, Mutex};
@@ -87,6 +89,47 @@ pub enum LinkActivationEvent {
     Closed(AddressHash),
 }
 
+/// Snapshot of a node's transport-level counters.
+///
+/// Returned by [`Node::connection_stats`]. Counts are totals since the node
+/// was created.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConnectionStats {
+    /// Announces received from other destinations.
+    pub announces_received: u64,
+    /// Links (inbound or outbound) that became active.
+    pub links_created: u64,
+    /// Links that were closed.
+    pub links_closed: u64,
+    /// Messages sent over links.
+    pub messages_sent: u64,
+    /// Messages received over links.
+    pub messages_received: u64,
+}
+
+/// Live counters behind [`ConnectionStats`], shared with the event task.
+#[derive(Debug, Default)]
+struct ConnectionCounters {
+    announces_received: AtomicU64,
+    links_created: AtomicU64,
+    links_closed: AtomicU64,
+    messages_sent: AtomicU64,
+    messages_received: AtomicU64,
+}
+
+impl ConnectionCounters {
+    /// Load every counter into a plain snapshot.
+    fn snapshot(&self) -> ConnectionStats {
+        ConnectionStats {
+            announces_received: self.announces_received.load(Ordering::Relaxed),
+            links_created: self.links_created.load(Ordering::Relaxed),
+            links_closed: self.links_closed.load(Ordering::Relaxed),
+            messages_sent: self.messages_sent.load(Ordering::Relaxed),
+            messages_received: self.messages_received.load(Ordering::Relaxed),
+        }
+    }
+}
+
 /// A Reticulum node that handles its own event processing.
 ///
 /// Each node runs a background task that processes announces, link events,
@@ -109,6 +152,8 @@ pub struct Node {
     announce_tx: broadcast::Sender<AddressHash>,
     /// Channel for link activation events.
     link_activation_tx: broadcast::Sender<LinkActivationEvent>,
+    /// Counters reported by [`connection_stats`](Self::connection_stats).
+    counters: Arc<ConnectionCounters>,
     /// Cancellation token for shutdown.
     cancel: CancellationToken,
     /// Background task handle.
@@ -162,6 +207,7 @@ impl Node {
         // Create shared state
         let links = Arc::new(Mutex::new(HashMap::new()));
         let known_destinations = Arc::new(Mutex::new(HashMap::new()));
+        let counters = Arc::new(ConnectionCounters::default());
 
         // Spawn background event processing task
         let cancel = CancellationToken::new();
@@ -172,6 +218,7 @@ impl Node {
             message_tx.clone(),
             announce_tx.clone(),
             link_activation_tx.clone(),
+            counters.clone(),
             cancel.clone(),
         )
         .await;
@@ -185,6 +232,7 @@ impl Node {
             message_tx,
             announce_tx,
             link_activation_tx,
+            counters,
             cancel,
             _task: task,
         }
@@ -195,6 +243,11 @@ impl Node {
         self.address_hash
     }
 
+    /// Get a snapshot of this node's announce, link and message counters.
+    pub fn connection_stats(&self) -> ConnectionStats {
+        self.counters.snapshot()
+    }
+
     /// Announce this node's presence to the network.
     pub async fn announce(&self) {
         let t = self.transport.lock().await;
@@ -202,6 +255,26 @@ impl Node {
         debug!("Node {} announced", format_hash_static(&self.address_hash));
     }
 
+    /// Re-announce this node every `interval` from a background task.
+    ///
+    /// The first timed announce goes out after one `interval`; call
+    /// [`announce`](Self::announce) for an immediate one. Cancel the returned
+    /// token to stop the timer. It is a child of the node's own token, so
+    /// dropping the node stops it too. Fails for a zero `interval`.
+    pub fn broadcast_announce(
+        &self,
+        interval: Duration,
+    ) -> Result<CancellationToken, AnnounceTimerError> {
+        let address_hash = self.address_hash;
+        spawn_announce_timer(
+            self.transport.clone(),
+            self.destination.clone(),
+            interval,
+            &self.cancel,
+            move || debug!("Node {} announced", format_hash_static(&address_hash)),
+        )
+    }
+
     /// Wait for an announce from a specific address hash.
     ///
     /// Returns the destination descriptor when found.
@@ -300,7 +373,12 @@ impl Node {
     /// Send a message to a destination.
     ///
     /// The link must already be established (call `create_link` first).
-    pub async fn send_message(&self, dest_hash: AddressHash, data: &[u8]) -> Result<(), NodeError> {
+    /// Accepts anything byte-like: `&[u8]`, `&[u8; N]`, `Vec<u8>`, `String`...
+    pub async fn send_message<D: AsRef<[u8]>>(
+        &self,
+        dest_hash: AddressHash,
+        data: D,
+    ) -> Result<(), NodeError> {
         let link = {
             let links = self.links.lock().await;
             links
@@ -315,15 +393,27 @@ impl Node {
                 return Err(NodeError::LinkClosed);
             }
             link_guard
-                .data_packet(data)
+                .data_packet(data.as_ref())
                 .map_err(|e| NodeError::PacketError(format!("{:?}", e)))?
         };
 
         let t = self.transport.lock().await;
         t.send_packet(packet).await;
+        self.counters.messages_sent.fetch_add(1, Ordering::Relaxed);
         Ok(())
     }
 
+    /// Send a text message to a destination.
+    ///
+    /// Shorthand for [`send_message`](Self::send_message) with `text.as_bytes()`.
+    pub async fn send_message_str(
+        &self,
+        dest_hash: AddressHash,
+        text: &str,
+    ) -> Result<(), NodeError> {
+        self.send_message(dest_hash, text.as_bytes()).await
+    }
+
     /// Receive a message from the network.
     ///
     /// Blocks until a message is received or timeout expires.
@@ -339,6 +429,49 @@ impl Node {
         .map_err(|_| NodeError::Timeout)?
     }
 
+    /// Receive a message from a specific sender.
+    ///
+    /// Messages from other senders are discarded. `sender` is matched against
+    /// [`IncomingMessage::from`], so it is the link ID the message arrived on.
+    pub async fn wait_for_message_from(
+        &self,
+        sender: AddressHash,
+        timeout_duration: Duration,
+    ) -> Result<IncomingMessage, NodeError> {
+        let mut rx = self.message_tx.subscribe();
+        timeout(timeout_duration, recv_message_from(&mut rx, sender))
+            .await
+            .map_err(|_| NodeError::Timeout)?
+    }
+
+    /// Receive the first message arriving at any of several nodes.
+    ///
+    /// Returns the index of the receiving node in `nodes` along with the
+    /// message. Useful in tests that don't know which node receives first.
+    pub async fn recv_message_any(
+        nodes: &[&Node],
+        timeout_duration: Duration,
+    ) -> Result<(usize, IncomingMessage), NodeError> {
+        // Subscribe to every node before waiting so no message is missed
+        let mut waiters = tokio::task::JoinSet::new();
+        for (index, node) in nodes.iter().enumerate() {
+            let mut rx = node.message_tx.subscribe();
+            waiters.spawn(async move { (index, recv_message(&mut rx).await) });
+        }
+
+        // Dropping the set aborts the remaining waiters
+        timeout(timeout_duration, async {
+            while let Some(joined) = waiters.join_next().await {
+                if let Ok((index, Ok(msg))) = joined {
+                    return Ok((index, msg));
+                }
+            }
+            Err(NodeError::ChannelClosed)
+        })
+        .await
+        .map_err(|_| NodeError::Timeout)?
+    }
+
     /// Spawn the background event processing task.
     async fn spawn_event_task(
         transport: Arc<Mutex<Transport>>,
@@ -347,6 +480,7 @@ impl Node {
         message_tx: broadcast::Sender<IncomingMessage>,
         announce_tx: broadcast::Sender<AddressHash>,
         link_activation_tx: broadcast::Sender<LinkActivationEvent>,
+        counters: Arc<ConnectionCounters>,
         cancel: CancellationToken,
     ) -> tokio::task::JoinHandle<()> {
         // Get channel receivers
@@ -377,6 +511,7 @@ impl Node {
                                 drop(dest);
 
                                 debug!("Node received announce from {}", format_hash_static(&hash));
+                                counters.announces_received.fetch_add(1, Ordering::Relaxed);
 
                                 // Store destination
                                 {
@@ -402,6 +537,7 @@ impl Node {
                                 &links,
                                 &message_tx,
                                 &link_activation_tx,
+                                &counters,
                             ).await;
                         }
                     }
@@ -415,6 +551,7 @@ impl Node {
                                 &links,
                                 &message_tx,
                                 &link_activation_tx,
+                                &counters,
                             ).await;
                         }
                     }
@@ -437,6 +574,7 @@ async fn handle_link_event(
     links: &LinkMap,
     message_tx: &broadcast::Sender<IncomingMessage>,
     link_activation_tx: &broadcast::Sender<LinkActivationEvent>,
+    counters: &ConnectionCounters,
 ) {
     match event.event {
         LinkEvent::Activated => {
@@ -445,10 +583,12 @@ async fn handle_link_event(
                 direction,
                 format_hash_static(&event.id)
             );
+            counters.links_created.fetch_add(1, Ordering::Relaxed);
             let _ = link_activation_tx.send(LinkActivationEvent::Activated(event.id));
         }
         LinkEvent::Data(payload) => {
             debug!("{} data from {}", direction, format_hash_static(&event.id));
+            counters.messages_received.fetch_add(1, Ordering::Relaxed);
             let _ = message_tx.send(IncomingMessage {
                 from: event.id,
                 data: payload.as_slice().to_vec(),
@@ -460,12 +600,61 @@ async fn handle_link_event(
                 direction,
                 format_hash_static(&event.id)
             );
+            counters.links_closed.fetch_add(1, Ordering::Relaxed);
             links.lock().await.remove(&event.id);
             let _ = link_activation_tx.send(LinkActivationEvent::Closed(event.id));
         }
     }
 }
 
+/// Receive the next message from `rx`.
+///
+/// A lagged receiver skips the overwritten messages and keeps waiting, so
+/// only a closed channel ends the wait without a message.
+async fn recv_message(
+    rx: &mut broadcast::Receiver<IncomingMessage>,
+) -> Result<IncomingMessage, NodeError> {
+    loop {
+        match rx.recv().await {
+            Ok(msg) => return Ok(msg),
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                warn!("Skipped {} messages while waiting for a message", skipped);
+            }
+            Err(broadcast::error::RecvError::Closed) => return Err(NodeError::ChannelClosed),
+        }
+    }
+}
+
+/// Receive from `rx` until a message from `sender` arrives.
+///
+/// Falling behind only skips messages, which may not have been from
+/// `sender` anyway, so a lagged receiver keeps waiting.
+async fn recv_message_from(
+    rx: &mut broadcast::Receiver<IncomingMessage>,
+    sender: AddressHash,
+) -> Result<IncomingMessage, NodeError> {
+    loop {
+        match rx.recv().await {
+            Ok(msg) if msg.from == sender => return Ok(msg),
+            Ok(msg) => {
+                debug!(
+                    "Discarding message from {} while waiting for {}",
+                    format_hash_static(&msg.from),
+                    format_hash_static(&sender)
+                );
+            }
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                warn!(
+                    "Skipped {} messages while waiting for {}",
+                    skipped,
+                    format_hash_static(&sender)
+                );
+            }
+            Err(broadcast::error::RecvError::Closed) => return Err(NodeError::ChannelClosed),
+        }
+    }
+}
+
 /// Format an address hash for logging (first 8 hex chars).
 fn format_hash_static(hash: &AddressHash) -> String {
     hash.to_hex_string().chars().take(8).collect()
@@ -484,6 +673,96 @@ mod tests {
     const LINK_TIMEOUT: Duration = Duration::from_secs(60);
     const MESSAGE_TIMEOUT: Duration = Duration::from_secs(30);
 
+    #[test]
+    fn test_connection_counters_snapshot() {
+        let counters = ConnectionCounters::default();
+        assert_eq!(counters.snapshot(), ConnectionStats::default());
+
+        counters.announces_received.fetch_add(3, Ordering::Relaxed);
+        counters.links_created.fetch_add(2, Ordering::Relaxed);
+        counters.links_closed.fetch_add(1, Ordering::Relaxed);
+        counters.messages_sent.fetch_add(5, Ordering::Relaxed);
+        counters.messages_received.fetch_add(4, Ordering::Relaxed);
+
+        assert_eq!(
+            counters.snapshot(),
+            ConnectionStats {
+                announces_received: 3,
+                links_created: 2,
+                links_closed: 1,
+                messages_sent: 5,
+                messages_received: 4,
+            }
+        );
+    }
+
+    #[test]
+    fn test_recv_message_from_survives_lag() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            let other = AddressHash::new([1; 16]);
+            let sender = AddressHash::new([2; 16]);
+            let (tx, mut rx) = broadcast::channel(2);
+
+            // Overflow the channel so the receiver lags before the wanted message
+            for i in 0..4 {
+                tx.send(IncomingMessage {
+                    from: other,
+                    data: vec![i],
+                })
+                .unwrap();
+            }
+            tx.send(IncomingMessage {
+                from: sender,
+                data: b"hello".to_vec(),
+            })
+            .unwrap();
+
+            let msg = recv_message_from(&mut rx, sender).await.unwrap();
+            assert_eq!(msg.from, sender);
+            assert_eq!(msg.data, b"hello");
+
+            drop(tx);
+            assert!(matches!(
+                recv_message_from(&mut rx, sender).await,
+                Err(NodeError::ChannelClosed)
+            ));
+        });
+    }
+
+    #[test]
+    fn test_recv_message_survives_lag() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            let from = AddressHash::new([1; 16]);
+            let (tx, mut rx) = broadcast::channel(2);
+
+            // Overflow the channel so the receiver lags
+            for i in 0..4 {
+                tx.send(IncomingMessage {
+                    from,
+                    data: vec![i],
+                })
+                .unwrap();
+            }
+
+            // The oldest messages were overwritten; the wait resumes after them
+            let msg = recv_message(&mut rx).await.unwrap();
+            assert_eq!(msg.data, vec![2]);
+
+            drop(tx);
+            assert_eq!(recv_message(&mut rx).await.unwrap().data, vec![3]);
+            assert!(matches!(
+                recv_message(&mut rx).await,
+                Err(NodeError::ChannelClosed)
+            ));
+        });
+    }
+
     /// Two-node communication test.
     ///
     /// This test validates end-to-end communication:
@@ -562,13 +841,10 @@ mod tests {
             info!("Link established!");
 
             // Node A sends message
-            let test_message = b"Hello from Node A!";
-            info!(
-                "Node A sending message: {:?}",
-                std::str::from_utf8(test_message)
-            );
+            let test_message = "Hello from Node A!";
+            info!("Node A sending message: {:?}", test_message);
             node_a
-                .send_message(node_b.address_hash(), test_message)
+                .send_message_str(node_b.address_hash(), test_message)
                 .await
                 .expect("Failed to send message");
 
@@ -580,7 +856,11 @@ mod tests {
                 .expect("Failed to receive message");
 
             info!("Node B received: {:?}", std::str::from_utf8(&msg.data));
-            assert_eq!(msg.data, test_message, "Message content should match");
+            assert_eq!(
+                msg.data,
+                test_message.as_bytes(),
+                "Message content should match"
+            );
 
             info!("=== Two-Node Integration Test PASSED ===");
         });
@@ -8,14 +8,23 @@
 //! ```ignore
 //! use reticulum_rs_esp32::persistence_host;
 //!
-//! let identity = persistence_host::load_or_create_identity()?;
+//! let identity = persistence_host::load_or_create_identity_locked()?;
 //! log::info!("Node identity: {}", identity.address_hash());
 //! ```
+//!
+//! # Concurrent Processes
+//!
+//! Two node processes started at the same time would otherwise both find no
+//! identity file, both generate one, and the last writer wins. The `_locked`
+//! functions serialize access through an advisory lock on a `.lock` sidecar
+//! file next to the identity file.
 
+use fs2::FileExt;
 use log::info;
 use rand_core::OsRng;
 use reticulum::identity::PrivateIdentity;
-use std::fs;
+use std::ffi::OsString;
+use std::fs::{self, OpenOptions};
 use std::io;
 use std::path::{Path, PathBuf};
 
@@ -107,14 +116,56 @@ pub fn load_or_create_identity_at(path: &Path) -> io::Result<PrivateIdentity> {
     Ok(identity)
 }
 
+/// Load existing identity or create and persist a new one using the default path.
+///
+/// Does not guard against concurrent processes; prefer
+/// [`load_or_create_identity_locked`].
+pub fn load_or_create_identity_unlocked() -> io::Result<PrivateIdentity> {
+    let path = default_identity_path()?;
+    load_or_create_identity_at(&path)
+}
+
+/// Get the lock sidecar path for an identity file (`identity.hex.lock`).
+fn lock_path_for(path: &Path) -> PathBuf {
+    let mut name = path
+        .file_name()
+        .map(OsString::from)
+        .unwrap_or_else(|| OsString::from("identity"));
+    name.push(".lock");
+    path.with_file_name(name)
+}
+
+/// Like [`load_or_create_identity_at`], but holds an exclusive advisory lock
+/// on the `.lock` sidecar file while reading or creating the identity.
+///
+/// Blocks until any other holder of the lock releases it.
+pub fn load_or_create_identity_locked_at(path: &Path) -> io::Result<PrivateIdentity> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let lock_file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(lock_path_for(path))?;
+    lock_file.lock_exclusive()?;
+
+    let result = load_or_create_identity_at(path);
+
+    // Release before returning; dropping the file would also release it
+    lock_file.unlock()?;
+    result
+}
+
 /// Load existing identity or create and persist a new one using the default path.
 ///
 /// This is the main entry point for identity management. On first run,
 /// creates a new random identity and saves it. On subsequent runs,
-/// loads the existing identity.
-pub fn load_or_create_identity() -> io::Result<PrivateIdentity> {
+/// loads the existing identity. Safe to call from several processes at once.
+pub fn load_or_create_identity_locked() -> io::Result<PrivateIdentity> {
     let path = default_identity_path()?;
-    load_or_create_identity_at(&path)
+    load_or_create_identity_locked_at(&path)
 }
 
 #[cfg(test)]
@@ -163,4 +214,30 @@ mod tests {
 
         let _ = fs::remove_file(&path);
     }
+
+    #[esp32_test]
+    fn test_locked_concurrent_create_yields_same_identity() {
+        let path = unique_identity_path();
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    load_or_create_identity_locked_at(&path)
+                        .expect("Failed to load/create")
+                        .to_hex_string()
+                })
+            })
+            .collect();
+        let hexes: Vec<String> = handles
+            .into_iter()
+            .map(|h| h.join().expect("thread panicked"))
+            .collect();
+
+        assert!(hexes.windows(2).all(|w| w[0] == w[1]));
+        assert_eq!(load_identity_from(&path).unwrap().to_hex_string(), hexes[0]);
+
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(lock_path_for(&path));
+    }
 }
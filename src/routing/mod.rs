@@ -6,5 +6,6 @@
 mod path_table;
 
 pub use path_table::{
-    InterfaceType, PathEntry, PathTable, PathTableConfig, PathTableError, RoutingMetrics,
+    ConflictPolicy, InterfaceType, NextHopHash, PathEntry, PathTable, PathTableBuilder,
+    PathTableConfig, PathTableError, RoutingMetrics, MAX_NEXT_HOPS,
 };
@@ -2,7 +2,7 @@
 //!
 //! Reticulum transport nodes maintain a path table that tracks known routes
 //! to destinations. Each path entry contains:
-//! - The interface type (LoRa, BLE, WiFi) and optional next-hop identifier
+//! - The interface type (LoRa, BLE, WiFi) and the next-hop identifiers
 //! - Routing metrics (hop count, timestamp, signal quality)
 //! - Path validation status
 //!
@@ -11,8 +11,17 @@
 //! - Automatic path selection based on scoring
 //! - TTL-based path expiration
 //! - Path updates when better routes are discovered
-
+//! - Equal-cost multi-path (ECMP): several next hops with the same hop count
+//!   on one interface, e.g. two gateways on the same LoRa channel
+//! - Checkpoint/restore with serde (`serde` feature). Timestamps are stored
+//!   as seconds elapsed, since `Instant` has no absolute representation
+
+use rand_core::RngCore;
+use rustc_hash::FxBuildHasher;
+use smallvec::SmallVec;
+use std::collections::hash_map::RandomState;
 use std::collections::HashMap;
+use std::hash::BuildHasher;
 use std::time::{Duration, Instant};
 
 /// Hash identifying a destination (typically 16 bytes in Reticulum).
@@ -21,8 +30,14 @@ pub type DestinationHash = [u8; 16];
 /// Hash identifying a next hop node.
 pub type NextHopHash = [u8; 16];
 
+/// Maximum equal-cost next hops kept per path entry.
+///
+/// Bounds memory use when many relays announce the same destination.
+pub const MAX_NEXT_HOPS: usize = 4;
+
 /// Type of interface for a path.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum InterfaceType {
     /// LoRa radio interface.
     LoRa,
@@ -44,6 +59,7 @@ impl std::fmt::Display for InterfaceType {
 
 /// Routing metrics for path scoring.
 #[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RoutingMetrics {
     /// Number of hops to destination.
     pub hops: u8,
@@ -52,6 +68,11 @@ pub struct RoutingMetrics {
     pub rssi_dbm: Option<i16>,
     /// Whether this path has been validated by a response.
     pub validated: bool,
+    /// Interface the last hop was received on, if known.
+    ///
+    /// Differs from the path's interface when the path was relayed, e.g. a
+    /// LoRa path learned through a BLE neighbour.
+    pub last_hop_interface: Option<InterfaceType>,
 }
 
 impl RoutingMetrics {
@@ -62,6 +83,9 @@ impl RoutingMetrics {
     /// - Higher RSSI is better (secondary factor)
     /// - Validated paths get a bonus
     ///
+    /// Ignores [`last_hop_interface`](Self::last_hop_interface); see
+    /// [`score_via`](Self::score_via).
+    ///
     /// Returns a score where higher values indicate better paths.
     pub fn score(&self) -> i32 {
         // Start with inverse hop count (fewer hops = higher score)
@@ -77,12 +101,45 @@ impl RoutingMetrics {
 
         hop_score + rssi_score + validation_bonus
     }
+
+    /// Calculate a score for this path when used via `interface`.
+    ///
+    /// Same as [`score`](Self::score), plus a bonus of 200 when the last hop
+    /// was received on `interface` itself, so a directly-received path beats
+    /// an otherwise equal one relayed from another interface.
+    pub fn score_via(&self, interface: InterfaceType) -> i32 {
+        let direct_bonus = if self.last_hop_interface == Some(interface) {
+            200
+        } else {
+            0
+        };
+        self.score() + direct_bonus
+    }
+}
+
+/// How to resolve competing paths to the same destination via the same interface.
+///
+/// When two nodes report different routes over one interface, only one can be
+/// kept. Blindly accepting better-scoring paths lets a rogue node poison routes
+/// by advertising low hop counts, so gateways may prefer [`TrustFirst`](Self::TrustFirst).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConflictPolicy {
+    /// Keep whichever path scores best (ties go to the newer path).
+    #[default]
+    BestScore,
+    /// Always replace the existing path with the newest report.
+    MostRecent,
+    /// Keep the first-seen path until it expires. Reports via the same next
+    /// hop still refresh it; reports via a different next hop are rejected.
+    TrustFirst,
 }
 
 /// Configuration for the path table.
 ///
 /// Note: This is `Copy` for efficient passing to constructors.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PathTableConfig {
     /// Maximum number of destinations to track.
     pub max_destinations: usize,
@@ -90,6 +147,8 @@ pub struct PathTableConfig {
     pub max_paths_per_dest: usize,
     /// Time-to-live for path entries.
     pub path_ttl: Duration,
+    /// How to resolve competing paths via the same interface.
+    pub conflict_policy: ConflictPolicy,
 }
 
 impl Default for PathTableConfig {
@@ -98,6 +157,7 @@ impl Default for PathTableConfig {
             max_destinations: 128,
             max_paths_per_dest: 4,
             path_ttl: Duration::from_secs(1800), // 30 minutes
+            conflict_policy: ConflictPolicy::BestScore,
         }
     }
 }
@@ -143,11 +203,16 @@ impl std::error::Error for PathTableError {}
 
 /// A single path entry in the routing table.
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "PathEntrySnapshot", into = "PathEntrySnapshot")
+)]
 pub struct PathEntry {
     /// Interface type for this path.
     pub interface: InterfaceType,
-    /// Next hop node hash (None if direct/local).
-    pub next_hop: Option<NextHopHash>,
+    /// Equal-cost next hop node hashes (empty if direct/local).
+    pub next_hops: SmallVec<[NextHopHash; 2]>,
     /// Routing metrics for scoring.
     pub metrics: RoutingMetrics,
     /// When this path was first learned.
@@ -166,13 +231,18 @@ impl PathEntry {
         let now = Instant::now();
         Self {
             interface,
-            next_hop,
+            next_hops: next_hop.into_iter().collect(),
             metrics,
             learned_at: now,
             last_refreshed: now,
         }
     }
 
+    /// Score this path, including the direct delivery bonus for its interface.
+    pub fn score(&self) -> i32 {
+        self.metrics.score_via(self.interface)
+    }
+
     /// Check if this path has expired.
     pub fn is_expired(&self, ttl: Duration) -> bool {
         self.last_refreshed.elapsed() > ttl
@@ -182,6 +252,99 @@ impl PathEntry {
     pub fn refresh(&mut self) {
         self.last_refreshed = Instant::now();
     }
+
+    /// Check if this path is direct (no next hop).
+    pub fn is_direct(&self) -> bool {
+        self.next_hops.is_empty()
+    }
+
+    /// Get all equal-cost next hops for inspection.
+    pub fn all_next_hops(&self) -> &[NextHopHash] {
+        &self.next_hops
+    }
+
+    /// Pick one of the next hops at random for load balancing.
+    ///
+    /// Returns None for direct paths.
+    pub fn best_next_hop(&self, rng: &mut impl RngCore) -> Option<NextHopHash> {
+        if self.next_hops.is_empty() {
+            return None;
+        }
+        let idx = rng.next_u32() as usize % self.next_hops.len();
+        Some(self.next_hops[idx])
+    }
+
+    /// Check if this path shares a next hop with `next_hops` (empty matches direct).
+    fn shares_next_hop(&self, next_hops: &[NextHopHash]) -> bool {
+        if next_hops.is_empty() {
+            return self.next_hops.is_empty();
+        }
+        next_hops.iter().any(|hop| self.next_hops.contains(hop))
+    }
+
+    /// Add equal-cost next hops, ignoring duplicates and hops beyond
+    /// [`MAX_NEXT_HOPS`]. A direct path stays direct.
+    fn merge_next_hops(&mut self, next_hops: &[NextHopHash]) {
+        if self.next_hops.is_empty() {
+            return;
+        }
+        for hop in next_hops {
+            if self.next_hops.len() >= MAX_NEXT_HOPS {
+                break;
+            }
+            if !self.next_hops.contains(hop) {
+                self.next_hops.push(*hop);
+            }
+        }
+    }
+
+    /// Replace all next hops (empty for direct).
+    fn set_next_hops(&mut self, next_hops: &[NextHopHash]) {
+        self.next_hops.clear();
+        self.next_hops.extend_from_slice(next_hops);
+    }
+}
+
+/// Serialized form of [`PathEntry`], with timestamps as seconds elapsed.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct PathEntrySnapshot {
+    interface: InterfaceType,
+    next_hops: Vec<NextHopHash>,
+    metrics: RoutingMetrics,
+    secs_since_learned: u64,
+    secs_since_refreshed: u64,
+}
+
+#[cfg(feature = "serde")]
+impl From<PathEntry> for PathEntrySnapshot {
+    fn from(entry: PathEntry) -> Self {
+        Self {
+            interface: entry.interface,
+            next_hops: entry.next_hops.to_vec(),
+            metrics: entry.metrics,
+            secs_since_learned: entry.learned_at.elapsed().as_secs(),
+            secs_since_refreshed: entry.last_refreshed.elapsed().as_secs(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<PathEntrySnapshot> for PathEntry {
+    fn from(snapshot: PathEntrySnapshot) -> Self {
+        let now = Instant::now();
+        // Clamp to now if the offset reaches back before the clock's origin
+        let ago = |secs| now.checked_sub(Duration::from_secs(secs)).unwrap_or(now);
+        Self {
+            interface: snapshot.interface,
+            // A hand-edited or foreign checkpoint may list more hops than
+            // add_path would ever keep
+            next_hops: snapshot.next_hops.into_iter().take(MAX_NEXT_HOPS).collect(),
+            metrics: snapshot.metrics,
+            learned_at: ago(snapshot.secs_since_learned),
+            last_refreshed: ago(snapshot.secs_since_refreshed),
+        }
+    }
 }
 
 /// Routing table for tracking paths to destinations.
@@ -202,19 +365,23 @@ impl PathEntry {
 ///
 /// let dest = [0u8; 16];
 /// let next_hop = [1u8; 16];
-/// let metrics = RoutingMetrics { hops: 2, rssi_dbm: Some(-80), validated: true };
+/// let metrics = RoutingMetrics { hops: 2, rssi_dbm: Some(-80), validated: true, ..Default::default() };
 ///
 /// // Add a path via LoRa
-/// table.add_path(dest, InterfaceType::LoRa, Some(next_hop), metrics);
+/// table.add_path(dest, PathEntry::new(InterfaceType::LoRa, Some(next_hop), metrics));
 ///
 /// // Get the best path to this destination
 /// let best = table.best_path(&dest);
 /// assert!(best.is_some());
 /// ```
-pub struct PathTable {
+///
+/// The table is generic over the map's hasher. The default `SipHash` resists
+/// collision attacks; [`new_fx`](PathTable::new_fx) trades that for faster
+/// lookups, which is safe here because keys are cryptographic hashes.
+pub struct PathTable<S = RandomState> {
     config: PathTableConfig,
     /// Map from destination hash to list of paths.
-    paths: HashMap<DestinationHash, Vec<PathEntry>>,
+    paths: HashMap<DestinationHash, Vec<PathEntry>, S>,
 }
 
 impl Default for PathTable {
@@ -226,28 +393,128 @@ impl Default for PathTable {
 impl PathTable {
     /// Create a new path table with the given configuration.
     pub fn new(config: PathTableConfig) -> Result<Self, PathTableError> {
-        config.validate()?;
-        Ok(Self {
-            config,
-            paths: HashMap::with_capacity(config.max_destinations),
+        PathTableBuilder::new(config).with_hasher(RandomState::new())
+    }
+
+    /// Create a new path table using the Fx hash function.
+    pub fn new_fx(config: PathTableConfig) -> Result<PathTable<FxBuildHasher>, PathTableError> {
+        PathTableBuilder::new(config).with_hasher(FxBuildHasher)
+    }
+}
+
+/// Serialized form of [`PathTable`].
+///
+/// Destinations are a list rather than a map because JSON object keys must
+/// be strings.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct PathTableSnapshot {
+    config: PathTableConfig,
+    paths: Vec<(DestinationHash, Vec<PathEntry>)>,
+}
+
+#[cfg(feature = "serde")]
+impl<S: BuildHasher> serde::Serialize for PathTable<S> {
+    fn serialize<Ser: serde::Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        PathTableSnapshot {
+            config: self.config,
+            paths: self
+                .paths
+                .iter()
+                .map(|(dest, paths)| (*dest, paths.clone()))
+                .collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, S: BuildHasher + Default> serde::Deserialize<'de> for PathTable<S> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let snapshot = PathTableSnapshot::deserialize(deserializer)?;
+        if snapshot.paths.len() > snapshot.config.max_destinations {
+            return Err(D::Error::custom("more destinations than max_destinations"));
+        }
+        let max_paths = snapshot.config.max_paths_per_dest;
+        if snapshot
+            .paths
+            .iter()
+            .any(|(_, paths)| paths.len() > max_paths)
+        {
+            return Err(D::Error::custom("more paths than max_paths_per_dest"));
+        }
+        let mut table = PathTableBuilder::new(snapshot.config)
+            .with_hasher(S::default())
+            .map_err(D::Error::custom)?;
+        table.paths.extend(snapshot.paths);
+        Ok(table)
+    }
+}
+
+/// Builder for path tables with a custom hash function.
+///
+/// # Example
+///
+/// ```
+/// use reticulum_rs_esp32::routing::{PathTableBuilder, PathTableConfig};
+/// use std::collections::hash_map::RandomState;
+///
+/// let table = PathTableBuilder::new(PathTableConfig::default())
+///     .with_hasher(RandomState::new())
+///     .unwrap();
+/// assert!(table.is_empty());
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct PathTableBuilder {
+    config: PathTableConfig,
+}
+
+impl PathTableBuilder {
+    /// Start building a path table with the given configuration.
+    pub fn new(config: PathTableConfig) -> Self {
+        Self { config }
+    }
+
+    /// Build the table with the given hasher.
+    ///
+    /// Fails if the configuration is invalid.
+    pub fn with_hasher<H: BuildHasher>(self, hasher: H) -> Result<PathTable<H>, PathTableError> {
+        self.config.validate()?;
+        Ok(PathTable {
+            config: self.config,
+            paths: HashMap::with_capacity_and_hasher(self.config.max_destinations, hasher),
         })
     }
+}
 
+impl<S: BuildHasher> PathTable<S> {
     /// Add or update a path to a destination.
     ///
-    /// If a path via the same interface already exists, it will be updated
-    /// if the new metrics are better. Otherwise, a new path is added.
+    /// If a path via the same interface already exists, the configured
+    /// [`ConflictPolicy`] decides whether it is replaced. A different next hop
+    /// with the same hop count is merged into the existing entry as an
+    /// equal-cost alternative (except under [`ConflictPolicy::TrustFirst`]).
+    /// Otherwise, a new path is added.
     ///
     /// Returns true if the path was added or updated, false if rejected
     /// (e.g., worse metrics than existing path via same interface).
-    pub fn add_path(
-        &mut self,
-        destination: DestinationHash,
-        interface: InterfaceType,
-        next_hop: Option<NextHopHash>,
-        metrics: RoutingMetrics,
-    ) -> bool {
+    ///
+    /// A stored entry counts as learned and refreshed now, whenever `entry`
+    /// itself was created.
+    pub fn add_path(&mut self, destination: DestinationHash, mut entry: PathEntry) -> bool {
         let now = Instant::now();
+        let ttl = self.config.path_ttl;
+        let policy = self.config.conflict_policy;
+        let interface = entry.interface;
+        entry.learned_at = now;
+        entry.last_refreshed = now;
+        entry.next_hops.truncate(MAX_NEXT_HOPS);
+        // Unless told otherwise, the last hop arrived on the receiving interface
+        entry.metrics.last_hop_interface = entry.metrics.last_hop_interface.or(Some(interface));
+        let metrics = entry.metrics;
+        let next_hops = &entry.next_hops[..];
 
         // Get or create the path list for this destination
         let path_list = self.paths.entry(destination).or_default();
@@ -255,23 +522,60 @@ impl PathTable {
         // Look for existing path via same interface
         for path in path_list.iter_mut() {
             if path.interface == interface {
-                // Update if better metrics or to refresh timestamp
-                if metrics.score() >= path.metrics.score() {
-                    path.next_hop = next_hop;
-                    path.metrics = metrics;
-                    path.last_refreshed = now;
-                    return true;
-                } else {
-                    // Existing path is better, just refresh it
-                    path.last_refreshed = now;
-                    return false;
+                match policy {
+                    ConflictPolicy::BestScore => {
+                        // Equal cost: keep both next hops
+                        if metrics.hops == path.metrics.hops {
+                            path.merge_next_hops(next_hops);
+                            if metrics.score_via(interface) >= path.score() {
+                                path.metrics = metrics;
+                            }
+                            path.last_refreshed = now;
+                            return true;
+                        }
+                        // Update if better metrics or to refresh timestamp
+                        if metrics.score_via(interface) >= path.score() {
+                            path.set_next_hops(next_hops);
+                            path.metrics = metrics;
+                            path.last_refreshed = now;
+                            return true;
+                        } else {
+                            // Existing path is better, just refresh it
+                            path.last_refreshed = now;
+                            return false;
+                        }
+                    }
+                    ConflictPolicy::MostRecent => {
+                        if metrics.hops == path.metrics.hops {
+                            path.merge_next_hops(next_hops);
+                        } else {
+                            path.set_next_hops(next_hops);
+                        }
+                        path.metrics = metrics;
+                        path.last_refreshed = now;
+                        return true;
+                    }
+                    ConflictPolicy::TrustFirst => {
+                        if path.is_expired(ttl) {
+                            // Trusted path lapsed, start over with the new one
+                            *path = entry;
+                            return true;
+                        }
+                        if path.shares_next_hop(next_hops) {
+                            path.metrics = metrics;
+                            path.last_refreshed = now;
+                            return true;
+                        }
+                        // Competing next hop - don't let it refresh the trusted path
+                        return false;
+                    }
                 }
             }
         }
 
         // New interface for this destination
         if path_list.len() < self.config.max_paths_per_dest {
-            path_list.push(PathEntry::new(interface, next_hop, metrics));
+            path_list.push(entry);
             return true;
         }
 
@@ -279,11 +583,11 @@ impl PathTable {
         if let Some(worst_idx) = path_list
             .iter()
             .enumerate()
-            .min_by_key(|(_, p)| p.metrics.score())
+            .min_by_key(|(_, p)| p.score())
             .map(|(i, _)| i)
         {
-            if metrics.score() > path_list[worst_idx].metrics.score() {
-                path_list[worst_idx] = PathEntry::new(interface, next_hop, metrics);
+            if metrics.score_via(interface) > path_list[worst_idx].score() {
+                path_list[worst_idx] = entry;
                 return true;
             }
         }
@@ -291,6 +595,20 @@ impl PathTable {
         false
     }
 
+    /// Add or update a path from its individual fields.
+    ///
+    /// Equivalent to `add_path(destination, PathEntry::new(interface, next_hop, metrics))`.
+    #[deprecated = "use add_path with PathEntry directly"]
+    pub fn add_path_from(
+        &mut self,
+        destination: DestinationHash,
+        interface: InterfaceType,
+        next_hop: Option<NextHopHash>,
+        metrics: RoutingMetrics,
+    ) -> bool {
+        self.add_path(destination, PathEntry::new(interface, next_hop, metrics))
+    }
+
     /// Get the best path to a destination.
     ///
     /// Returns the path with the highest score, or None if no paths exist
@@ -301,7 +619,7 @@ impl PathTable {
             .get(destination)?
             .iter()
             .filter(|p| !p.is_expired(ttl))
-            .max_by_key(|p| p.metrics.score())
+            .max_by_key(|p| p.score())
     }
 
     /// Get all paths to a destination, sorted by score (best first).
@@ -312,10 +630,75 @@ impl PathTable {
             .get(destination)
             .map(|list| list.iter().filter(|p| !p.is_expired(ttl)).collect())
             .unwrap_or_default();
-        paths.sort_by_key(|p| std::cmp::Reverse(p.metrics.score()));
+        paths.sort_by_key(|p| std::cmp::Reverse(p.score()));
         paths
     }
 
+    /// Get all non-expired paths learned via an interface.
+    ///
+    /// Useful for dumping or logging routes before tearing an interface down.
+    pub fn paths_via_interface(
+        &self,
+        interface: InterfaceType,
+    ) -> Vec<(&DestinationHash, &PathEntry)> {
+        let ttl = self.config.path_ttl;
+        self.paths
+            .iter()
+            .flat_map(|(dest, list)| list.iter().map(move |p| (dest, p)))
+            .filter(|(_, p)| p.interface == interface && !p.is_expired(ttl))
+            .collect()
+    }
+
+    /// Remove all paths learned via an interface (e.g. when it goes down).
+    ///
+    /// Destinations left without paths are removed. Returns the number of
+    /// paths removed, including expired ones.
+    pub fn remove_paths_via_interface(&mut self, interface: InterfaceType) -> usize {
+        let mut removed = 0;
+
+        self.paths.retain(|_, path_list| {
+            let before = path_list.len();
+            path_list.retain(|p| p.interface != interface);
+            removed += before - path_list.len();
+            !path_list.is_empty()
+        });
+
+        removed
+    }
+
+    /// Make all paths via an interface appear `factor` times older.
+    ///
+    /// Penalizes an unreliable interface (e.g. LoRa during a burst of CRC
+    /// errors): its paths expire sooner than their natural TTL and lose ties
+    /// to fresher paths. Factors that aren't finite and greater than 1.0 are
+    /// ignored, so this never extends a path's lifetime. Ages are capped just
+    /// past the path TTL, which also keeps huge factors from overflowing.
+    ///
+    /// Not called yet; see "CRC-driven path aging" in `docs/future-work.md`.
+    pub fn age_paths_by_interface(&mut self, interface: InterfaceType, factor: f32) {
+        if !factor.is_finite() || factor <= 1.0 {
+            return;
+        }
+        let max_age = self
+            .config
+            .path_ttl
+            .saturating_add(Duration::from_millis(1));
+        let now = Instant::now();
+        for path in self.paths.values_mut().flatten() {
+            if path.interface != interface {
+                continue;
+            }
+            let elapsed = now.saturating_duration_since(path.last_refreshed);
+            let aged = Duration::try_from_secs_f32(elapsed.as_secs_f32() * factor)
+                .unwrap_or(max_age)
+                .min(max_age);
+            // Shortly after boot `now - aged` may predate the clock's origin
+            if let Some(last_refreshed) = now.checked_sub(aged) {
+                path.last_refreshed = last_refreshed;
+            }
+        }
+    }
+
     /// Check if we have any path to a destination.
     pub fn has_path(&self, destination: &DestinationHash) -> bool {
         self.best_path(destination).is_some()
@@ -364,15 +747,20 @@ impl PathTable {
 
     /// Remove all expired paths.
     ///
-    /// Returns the number of paths removed.
-    pub fn cleanup_expired(&mut self) -> usize {
+    /// Returns the destination, interface and metrics of every removed path,
+    /// so callers can log pruned routes and count expiries per interface.
+    pub fn cleanup_expired(&mut self) -> Vec<(DestinationHash, InterfaceType, RoutingMetrics)> {
         let ttl = self.config.path_ttl;
-        let mut removed = 0;
+        let mut removed = Vec::new();
 
-        self.paths.retain(|_, path_list| {
-            let before = path_list.len();
-            path_list.retain(|p| !p.is_expired(ttl));
-            removed += before - path_list.len();
+        self.paths.retain(|destination, path_list| {
+            path_list.retain(|p| {
+                let expired = p.is_expired(ttl);
+                if expired {
+                    removed.push((*destination, p.interface, p.metrics));
+                }
+                !expired
+            });
             !path_list.is_empty()
         });
 
@@ -412,6 +800,30 @@ mod tests {
         assert_eq!(table.path_count(), 0);
     }
 
+    #[esp32_test]
+    fn test_custom_hasher() {
+        let mut table = PathTable::new_fx(PathTableConfig::default()).unwrap();
+        let metrics = RoutingMetrics {
+            hops: 1,
+            ..Default::default()
+        };
+        assert!(table.add_path(
+            make_dest(1),
+            PathEntry::new(InterfaceType::LoRa, None, metrics)
+        ));
+        assert!(table.has_path(&make_dest(1)));
+        assert!(!table.has_path(&make_dest(2)));
+
+        let config = PathTableConfig {
+            max_destinations: 0,
+            ..Default::default()
+        };
+        assert!(matches!(
+            PathTableBuilder::new(config).with_hasher(FxBuildHasher),
+            Err(PathTableError::InvalidConfig(_))
+        ));
+    }
+
     #[esp32_test]
     fn test_invalid_config_zero_destinations() {
         let config = PathTableConfig {
@@ -457,15 +869,83 @@ mod tests {
             hops: 2,
             rssi_dbm: Some(-80),
             validated: false,
+            last_hop_interface: None,
         };
 
-        let added = table.add_path(dest, InterfaceType::LoRa, Some(next_hop), metrics);
+        let added = table.add_path(
+            dest,
+            PathEntry::new(InterfaceType::LoRa, Some(next_hop), metrics),
+        );
         assert!(added);
         assert_eq!(table.destination_count(), 1);
         assert_eq!(table.path_count(), 1);
         assert!(table.has_path(&dest));
     }
 
+    #[esp32_test]
+    fn test_add_path_keeps_entry_next_hops() {
+        let mut table = PathTable::new(PathTableConfig::default()).unwrap();
+        let dest = make_dest(1);
+        let mut entry = PathEntry::new(InterfaceType::LoRa, Some(make_next_hop(2)), hops(2));
+        entry.next_hops.push(make_next_hop(3));
+
+        assert!(table.add_path(dest, entry));
+
+        let path = table.best_path(&dest).unwrap();
+        assert_eq!(path.all_next_hops(), &[make_next_hop(2), make_next_hop(3)]);
+        assert_eq!(path.metrics.last_hop_interface, Some(InterfaceType::LoRa));
+    }
+
+    #[esp32_test]
+    fn test_add_path_stamps_stale_entry() {
+        let mut table = PathTable::new(PathTableConfig {
+            path_ttl: Duration::from_millis(50),
+            conflict_policy: ConflictPolicy::TrustFirst,
+            ..Default::default()
+        })
+        .unwrap();
+        let dest = make_dest(1);
+        let stale = |next_hop| {
+            let mut entry = PathEntry::new(InterfaceType::LoRa, Some(next_hop), hops(2));
+            entry.last_refreshed = entry
+                .last_refreshed
+                .checked_sub(Duration::from_millis(100))
+                .unwrap_or(entry.last_refreshed);
+            entry.learned_at = entry.last_refreshed;
+            entry
+        };
+
+        // A new entry built long ago is not expired on arrival
+        assert!(table.add_path(dest, stale(make_next_hop(2))));
+        let path = table.best_path(&dest).unwrap();
+        assert!(path.last_refreshed.elapsed() < Duration::from_secs(1));
+        assert!(path.learned_at.elapsed() < Duration::from_secs(1));
+
+        // Nor is one that replaces a lapsed trusted path
+        let trusted = &mut table.paths.get_mut(&dest).unwrap()[0];
+        trusted.last_refreshed = trusted
+            .last_refreshed
+            .checked_sub(Duration::from_millis(100))
+            .unwrap_or(trusted.last_refreshed);
+        assert!(table.add_path(dest, stale(make_next_hop(3))));
+        let path = table.best_path(&dest).unwrap();
+        assert_eq!(path.all_next_hops(), &[make_next_hop(3)]);
+        assert!(path.last_refreshed.elapsed() < Duration::from_secs(1));
+    }
+
+    #[esp32_test]
+    #[allow(deprecated)]
+    fn test_add_path_from_matches_add_path() {
+        let mut table = PathTable::new(PathTableConfig::default()).unwrap();
+        let dest = make_dest(1);
+
+        assert!(table.add_path_from(dest, InterfaceType::Ble, Some(make_next_hop(2)), hops(1)));
+
+        let path = table.best_path(&dest).unwrap();
+        assert_eq!(path.interface, InterfaceType::Ble);
+        assert_eq!(path.all_next_hops(), &[make_next_hop(2)]);
+    }
+
     #[esp32_test]
     fn test_multiple_interfaces_same_dest() {
         let mut table = PathTable::new(PathTableConfig::default()).unwrap();
@@ -476,16 +956,21 @@ mod tests {
             hops: 3,
             rssi_dbm: Some(-90),
             validated: false,
+            last_hop_interface: None,
         };
-        table.add_path(dest, InterfaceType::LoRa, None, metrics_lora);
+        table.add_path(
+            dest,
+            PathEntry::new(InterfaceType::LoRa, None, metrics_lora),
+        );
 
         // Add BLE path
         let metrics_ble = RoutingMetrics {
             hops: 2,
             rssi_dbm: Some(-70),
             validated: true,
+            last_hop_interface: None,
         };
-        table.add_path(dest, InterfaceType::Ble, None, metrics_ble);
+        table.add_path(dest, PathEntry::new(InterfaceType::Ble, None, metrics_ble));
 
         assert_eq!(table.destination_count(), 1);
         assert_eq!(table.path_count(), 2);
@@ -503,23 +988,27 @@ mod tests {
         // Add path with 5 hops
         table.add_path(
             dest,
-            InterfaceType::LoRa,
-            None,
-            RoutingMetrics {
-                hops: 5,
-                ..Default::default()
-            },
+            PathEntry::new(
+                InterfaceType::LoRa,
+                None,
+                RoutingMetrics {
+                    hops: 5,
+                    ..Default::default()
+                },
+            ),
         );
 
         // Add path with 2 hops
         table.add_path(
             dest,
-            InterfaceType::Ble,
-            None,
-            RoutingMetrics {
-                hops: 2,
-                ..Default::default()
-            },
+            PathEntry::new(
+                InterfaceType::Ble,
+                None,
+                RoutingMetrics {
+                    hops: 2,
+                    ..Default::default()
+                },
+            ),
         );
 
         let best = table.best_path(&dest).unwrap();
@@ -534,25 +1023,31 @@ mod tests {
         // Add initial path
         table.add_path(
             dest,
-            InterfaceType::LoRa,
-            None,
-            RoutingMetrics {
-                hops: 5,
-                rssi_dbm: Some(-100),
-                validated: false,
-            },
+            PathEntry::new(
+                InterfaceType::LoRa,
+                None,
+                RoutingMetrics {
+                    hops: 5,
+                    rssi_dbm: Some(-100),
+                    validated: false,
+                    last_hop_interface: None,
+                },
+            ),
         );
 
         // Update with better metrics
         let updated = table.add_path(
             dest,
-            InterfaceType::LoRa,
-            None,
-            RoutingMetrics {
-                hops: 3,
-                rssi_dbm: Some(-80),
-                validated: true,
-            },
+            PathEntry::new(
+                InterfaceType::LoRa,
+                None,
+                RoutingMetrics {
+                    hops: 3,
+                    rssi_dbm: Some(-80),
+                    validated: true,
+                    last_hop_interface: None,
+                },
+            ),
         );
 
         assert!(updated);
@@ -570,25 +1065,31 @@ mod tests {
         // Add good path
         table.add_path(
             dest,
-            InterfaceType::LoRa,
-            None,
-            RoutingMetrics {
-                hops: 2,
-                rssi_dbm: Some(-60),
-                validated: true,
-            },
+            PathEntry::new(
+                InterfaceType::LoRa,
+                None,
+                RoutingMetrics {
+                    hops: 2,
+                    rssi_dbm: Some(-60),
+                    validated: true,
+                    last_hop_interface: None,
+                },
+            ),
         );
 
         // Try to update with worse metrics
         let updated = table.add_path(
             dest,
-            InterfaceType::LoRa,
-            None,
-            RoutingMetrics {
-                hops: 5,
-                rssi_dbm: Some(-100),
-                validated: false,
-            },
+            PathEntry::new(
+                InterfaceType::LoRa,
+                None,
+                RoutingMetrics {
+                    hops: 5,
+                    rssi_dbm: Some(-100),
+                    validated: false,
+                    last_hop_interface: None,
+                },
+            ),
         );
 
         assert!(!updated);
@@ -602,7 +1103,10 @@ mod tests {
         let mut table = PathTable::new(PathTableConfig::default()).unwrap();
         let dest = make_dest(1);
 
-        table.add_path(dest, InterfaceType::LoRa, None, RoutingMetrics::default());
+        table.add_path(
+            dest,
+            PathEntry::new(InterfaceType::LoRa, None, RoutingMetrics::default()),
+        );
 
         assert!(!table.best_path(&dest).unwrap().metrics.validated);
 
@@ -616,7 +1120,10 @@ mod tests {
         let mut table = PathTable::new(PathTableConfig::default()).unwrap();
         let dest = make_dest(1);
 
-        table.add_path(dest, InterfaceType::LoRa, None, RoutingMetrics::default());
+        table.add_path(
+            dest,
+            PathEntry::new(InterfaceType::LoRa, None, RoutingMetrics::default()),
+        );
         assert!(table.has_path(&dest));
 
         let removed = table.remove_destination(&dest);
@@ -631,9 +1138,7 @@ mod tests {
         for i in 0..5 {
             table.add_path(
                 make_dest(i),
-                InterfaceType::LoRa,
-                None,
-                RoutingMetrics::default(),
+                PathEntry::new(InterfaceType::LoRa, None, RoutingMetrics::default()),
             );
         }
         assert_eq!(table.destination_count(), 5);
@@ -650,30 +1155,36 @@ mod tests {
         // Add paths with different hop counts
         table.add_path(
             dest,
-            InterfaceType::LoRa,
-            None,
-            RoutingMetrics {
-                hops: 5,
-                ..Default::default()
-            },
+            PathEntry::new(
+                InterfaceType::LoRa,
+                None,
+                RoutingMetrics {
+                    hops: 5,
+                    ..Default::default()
+                },
+            ),
         );
         table.add_path(
             dest,
-            InterfaceType::Ble,
-            None,
-            RoutingMetrics {
-                hops: 2,
-                ..Default::default()
-            },
+            PathEntry::new(
+                InterfaceType::Ble,
+                None,
+                RoutingMetrics {
+                    hops: 2,
+                    ..Default::default()
+                },
+            ),
         );
         table.add_path(
             dest,
-            InterfaceType::Wifi,
-            None,
-            RoutingMetrics {
-                hops: 3,
-                ..Default::default()
-            },
+            PathEntry::new(
+                InterfaceType::Wifi,
+                None,
+                RoutingMetrics {
+                    hops: 3,
+                    ..Default::default()
+                },
+            ),
         );
 
         let paths = table.paths_to(&dest);
@@ -701,11 +1212,13 @@ mod tests {
             hops: 2,
             rssi_dbm: Some(-60),
             validated: false,
+            last_hop_interface: None,
         };
         let m4 = RoutingMetrics {
             hops: 2,
             rssi_dbm: Some(-90),
             validated: false,
+            last_hop_interface: None,
         };
         assert!(m3.score() > m4.score());
 
@@ -714,15 +1227,38 @@ mod tests {
             hops: 2,
             rssi_dbm: Some(-70),
             validated: true,
+            last_hop_interface: None,
         };
         let m6 = RoutingMetrics {
             hops: 2,
             rssi_dbm: Some(-70),
             validated: false,
+            last_hop_interface: None,
         };
         assert!(m5.score() > m6.score());
     }
 
+    #[esp32_test]
+    fn test_direct_delivery_bonus() {
+        let relayed = RoutingMetrics {
+            hops: 2,
+            last_hop_interface: Some(InterfaceType::Ble),
+            ..Default::default()
+        };
+        assert_eq!(relayed.score_via(InterfaceType::LoRa), relayed.score());
+        assert_eq!(relayed.score_via(InterfaceType::Ble), relayed.score() + 200);
+
+        // add_path defaults the last hop to the receiving interface
+        let mut table = PathTable::new(PathTableConfig::default()).unwrap();
+        let dest = make_dest(1);
+        table.add_path(dest, PathEntry::new(InterfaceType::LoRa, None, relayed));
+        table.add_path(dest, PathEntry::new(InterfaceType::Wifi, None, hops(2)));
+
+        let best = table.best_path(&dest).unwrap();
+        assert_eq!(best.interface, InterfaceType::Wifi);
+        assert_eq!(best.metrics.last_hop_interface, Some(InterfaceType::Wifi));
+    }
+
     #[esp32_test]
     fn test_interface_type_display() {
         assert_eq!(format!("{}", InterfaceType::LoRa), "LoRa");
@@ -736,6 +1272,310 @@ mod tests {
         assert_eq!(config.max_destinations, 128);
         assert_eq!(config.max_paths_per_dest, 4);
         assert_eq!(config.path_ttl, Duration::from_secs(1800));
+        assert_eq!(config.conflict_policy, ConflictPolicy::BestScore);
+    }
+
+    // ==================== Conflict Policy Tests ====================
+
+    fn table_with_policy(conflict_policy: ConflictPolicy) -> PathTable {
+        PathTable::new(PathTableConfig {
+            conflict_policy,
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    fn hops(hops: u8) -> RoutingMetrics {
+        RoutingMetrics {
+            hops,
+            ..Default::default()
+        }
+    }
+
+    #[esp32_test]
+    fn test_most_recent_overwrites_better_path() {
+        let mut table = table_with_policy(ConflictPolicy::MostRecent);
+        let dest = make_dest(1);
+
+        table.add_path(
+            dest,
+            PathEntry::new(InterfaceType::LoRa, Some(make_next_hop(2)), hops(1)),
+        );
+        let updated = table.add_path(
+            dest,
+            PathEntry::new(InterfaceType::LoRa, Some(make_next_hop(3)), hops(5)),
+        );
+
+        assert!(updated);
+        let path = table.best_path(&dest).unwrap();
+        assert_eq!(path.all_next_hops(), &[make_next_hop(3)]);
+        assert_eq!(path.metrics.hops, 5);
+    }
+
+    #[esp32_test]
+    fn test_trust_first_rejects_competing_next_hop() {
+        let mut table = table_with_policy(ConflictPolicy::TrustFirst);
+        let dest = make_dest(1);
+
+        table.add_path(
+            dest,
+            PathEntry::new(InterfaceType::LoRa, Some(make_next_hop(2)), hops(5)),
+        );
+
+        // A "better" route from another node must not displace the first one
+        let updated = table.add_path(
+            dest,
+            PathEntry::new(InterfaceType::LoRa, Some(make_next_hop(3)), hops(1)),
+        );
+        assert!(!updated);
+        let path = table.best_path(&dest).unwrap();
+        assert_eq!(path.all_next_hops(), &[make_next_hop(2)]);
+        assert_eq!(path.metrics.hops, 5);
+
+        // The trusted next hop may still update its own metrics
+        let updated = table.add_path(
+            dest,
+            PathEntry::new(InterfaceType::LoRa, Some(make_next_hop(2)), hops(4)),
+        );
+        assert!(updated);
+        assert_eq!(table.best_path(&dest).unwrap().metrics.hops, 4);
+    }
+
+    #[esp32_test]
+    fn test_age_paths_by_interface() {
+        let mut table = PathTable::new(PathTableConfig::default()).unwrap();
+        let dest = make_dest(1);
+        table.add_path(dest, PathEntry::new(InterfaceType::LoRa, None, hops(1)));
+        table.add_path(dest, PathEntry::new(InterfaceType::Ble, None, hops(1)));
+
+        // Backdate both paths rather than sleeping
+        for path in table.paths.get_mut(&dest).unwrap() {
+            path.last_refreshed = path
+                .last_refreshed
+                .checked_sub(Duration::from_millis(100))
+                .expect("clock running longer than 100ms");
+        }
+
+        table.age_paths_by_interface(InterfaceType::LoRa, 3.0);
+        // Factors that would make paths younger are ignored
+        table.age_paths_by_interface(InterfaceType::Ble, 0.5);
+        table.age_paths_by_interface(InterfaceType::Ble, f32::NAN);
+
+        let paths = table.paths_to(&dest);
+        let age = |interface| {
+            paths
+                .iter()
+                .find(|p| p.interface == interface)
+                .unwrap()
+                .last_refreshed
+                .elapsed()
+        };
+        let lora_age = age(InterfaceType::LoRa);
+        assert!(lora_age >= Duration::from_millis(300) && lora_age < Duration::from_millis(500));
+        let ble_age = age(InterfaceType::Ble);
+        assert!(ble_age >= Duration::from_millis(100) && ble_age < Duration::from_millis(300));
+    }
+
+    #[esp32_test]
+    fn test_age_paths_by_interface_caps_at_ttl() {
+        let mut table = PathTable::new(PathTableConfig {
+            path_ttl: Duration::from_millis(50),
+            ..Default::default()
+        })
+        .unwrap();
+        let dest = make_dest(1);
+        table.add_path(dest, PathEntry::new(InterfaceType::LoRa, None, hops(1)));
+        for path in table.paths.get_mut(&dest).unwrap() {
+            path.last_refreshed = path
+                .last_refreshed
+                .checked_sub(Duration::from_millis(10))
+                .expect("clock running longer than 10ms");
+        }
+
+        // Would overflow Duration if not capped
+        table.age_paths_by_interface(InterfaceType::LoRa, f32::MAX);
+
+        let path = &table.paths[&dest][0];
+        let age = path.last_refreshed.elapsed();
+        assert!(age > Duration::from_millis(50) && age < Duration::from_millis(250));
+        assert!(!table.has_path(&dest));
+    }
+
+    #[esp32_test]
+    fn test_cleanup_expired_reports_removed_paths() {
+        let mut table = PathTable::new(PathTableConfig {
+            path_ttl: Duration::from_millis(5),
+            ..Default::default()
+        })
+        .unwrap();
+        table.add_path(
+            make_dest(1),
+            PathEntry::new(InterfaceType::LoRa, None, hops(3)),
+        );
+        table.add_path(
+            make_dest(1),
+            PathEntry::new(InterfaceType::Ble, None, hops(1)),
+        );
+        std::thread::sleep(Duration::from_millis(10));
+        table.add_path(
+            make_dest(2),
+            PathEntry::new(InterfaceType::LoRa, None, hops(2)),
+        );
+
+        let mut removed = table.cleanup_expired();
+        removed.sort_by_key(|(_, interface, _)| interface.to_string());
+        let summary: Vec<_> = removed
+            .iter()
+            .map(|(dest, interface, metrics)| (*dest, *interface, metrics.hops))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (make_dest(1), InterfaceType::Ble, 1),
+                (make_dest(1), InterfaceType::LoRa, 3),
+            ]
+        );
+        assert!(!table.has_path(&make_dest(1)));
+        assert!(table.has_path(&make_dest(2)));
+    }
+
+    #[esp32_test]
+    fn test_trust_first_replaced_after_expiry() {
+        let mut table = PathTable::new(PathTableConfig {
+            path_ttl: Duration::from_millis(5),
+            conflict_policy: ConflictPolicy::TrustFirst,
+            ..Default::default()
+        })
+        .unwrap();
+        let dest = make_dest(1);
+
+        table.add_path(
+            dest,
+            PathEntry::new(InterfaceType::LoRa, Some(make_next_hop(2)), hops(1)),
+        );
+        std::thread::sleep(Duration::from_millis(10));
+
+        let updated = table.add_path(
+            dest,
+            PathEntry::new(InterfaceType::LoRa, Some(make_next_hop(3)), hops(3)),
+        );
+        assert!(updated);
+        let path = table.best_path(&dest).unwrap();
+        assert_eq!(path.all_next_hops(), &[make_next_hop(3)]);
+    }
+
+    // ==================== Interface Tests ====================
+
+    #[esp32_test]
+    fn test_paths_via_interface() {
+        let mut table = PathTable::new(PathTableConfig::default()).unwrap();
+        let (a, b) = (make_dest(1), make_dest(2));
+        table.add_path(a, PathEntry::new(InterfaceType::LoRa, None, hops(1)));
+        table.add_path(a, PathEntry::new(InterfaceType::Ble, None, hops(1)));
+        table.add_path(b, PathEntry::new(InterfaceType::LoRa, None, hops(2)));
+
+        let mut via_lora: Vec<_> = table
+            .paths_via_interface(InterfaceType::LoRa)
+            .into_iter()
+            .map(|(dest, path)| (*dest, path.metrics.hops))
+            .collect();
+        via_lora.sort();
+        assert_eq!(via_lora, vec![(a, 1), (b, 2)]);
+
+        assert_eq!(table.remove_paths_via_interface(InterfaceType::LoRa), 2);
+        assert!(table.paths_via_interface(InterfaceType::LoRa).is_empty());
+        // `a` keeps its BLE path, `b` had only LoRa and is gone
+        assert!(table.has_path(&a));
+        assert!(!table.has_path(&b));
+        assert_eq!(table.destination_count(), 1);
+    }
+
+    // ==================== ECMP Tests ====================
+
+    /// Deterministic RNG that cycles through the given values.
+    struct SeqRng(u32);
+
+    impl RngCore for SeqRng {
+        fn next_u32(&mut self) -> u32 {
+            self.0 = self.0.wrapping_add(1);
+            self.0
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            self.next_u32() as u64
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            dest.fill(0);
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    #[esp32_test]
+    fn test_equal_cost_next_hops_merged() {
+        let mut table = PathTable::new(PathTableConfig::default()).unwrap();
+        let dest = make_dest(1);
+
+        table.add_path(
+            dest,
+            PathEntry::new(InterfaceType::LoRa, Some(make_next_hop(2)), hops(3)),
+        );
+        assert!(table.add_path(
+            dest,
+            PathEntry::new(InterfaceType::LoRa, Some(make_next_hop(3)), hops(3))
+        ));
+        // Duplicate is not added twice
+        table.add_path(
+            dest,
+            PathEntry::new(InterfaceType::LoRa, Some(make_next_hop(3)), hops(3)),
+        );
+
+        let path = table.best_path(&dest).unwrap();
+        assert_eq!(table.path_count(), 1);
+        assert_eq!(path.all_next_hops(), &[make_next_hop(2), make_next_hop(3)]);
+
+        // Both next hops get picked for load balancing
+        let mut rng = SeqRng(0);
+        let picks: Vec<_> = (0..4)
+            .filter_map(|_| path.best_next_hop(&mut rng))
+            .collect();
+        assert!(picks.contains(&make_next_hop(2)));
+        assert!(picks.contains(&make_next_hop(3)));
+
+        // A shorter route replaces the equal-cost set
+        table.add_path(
+            dest,
+            PathEntry::new(InterfaceType::LoRa, Some(make_next_hop(4)), hops(1)),
+        );
+        let path = table.best_path(&dest).unwrap();
+        assert_eq!(path.all_next_hops(), &[make_next_hop(4)]);
+    }
+
+    #[esp32_test]
+    fn test_next_hops_capped_and_direct_has_none() {
+        let mut table = PathTable::new(PathTableConfig::default()).unwrap();
+        let dest = make_dest(1);
+
+        for id in 0..(MAX_NEXT_HOPS as u8 + 2) {
+            table.add_path(
+                dest,
+                PathEntry::new(InterfaceType::LoRa, Some(make_next_hop(id)), hops(2)),
+            );
+        }
+        assert_eq!(
+            table.best_path(&dest).unwrap().all_next_hops().len(),
+            MAX_NEXT_HOPS
+        );
+
+        let direct = make_dest(2);
+        table.add_path(direct, PathEntry::new(InterfaceType::Ble, None, hops(0)));
+        let path = table.best_path(&direct).unwrap();
+        assert!(path.is_direct());
+        assert_eq!(path.best_next_hop(&mut SeqRng(0)), None);
     }
 
     #[esp32_test]
@@ -743,4 +1583,66 @@ mod tests {
         let err = PathTableError::InvalidConfig("test message");
         assert_eq!(format!("{}", err), "invalid config: test message");
     }
+
+    // ==================== Serde Tests ====================
+
+    #[cfg(all(feature = "serde", not(target_os = "espidf")))]
+    #[esp32_test]
+    fn test_serde_checkpoint_restore() {
+        let mut table = PathTable::new(PathTableConfig::default()).unwrap();
+        let dest = make_dest(1);
+        table.add_path(
+            dest,
+            PathEntry::new(InterfaceType::LoRa, Some(make_dest(9)), hops(3)),
+        );
+        table.add_path(dest, PathEntry::new(InterfaceType::Ble, None, hops(1)));
+
+        // Age the LoRa path so the restored offset is visible
+        for path in table.paths.get_mut(&dest).unwrap() {
+            if path.interface == InterfaceType::LoRa {
+                path.last_refreshed = path
+                    .last_refreshed
+                    .checked_sub(Duration::from_secs(60))
+                    .unwrap_or(path.last_refreshed);
+                path.learned_at = path.last_refreshed;
+            }
+        }
+
+        let json = serde_json::to_string(&table).unwrap();
+        let restored: PathTable = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.destination_count(), 1);
+        let paths = restored.paths_to(&dest);
+        assert_eq!(paths.len(), 2);
+        let ble = paths
+            .iter()
+            .find(|p| p.interface == InterfaceType::Ble)
+            .unwrap();
+        assert!(ble.is_direct());
+        let lora = paths
+            .iter()
+            .find(|p| p.interface == InterfaceType::LoRa)
+            .unwrap();
+        assert_eq!(lora.all_next_hops(), &[make_dest(9)]);
+        assert_eq!(lora.metrics.hops, 3);
+        assert_eq!(lora.metrics.last_hop_interface, Some(InterfaceType::LoRa));
+        let age = lora.last_refreshed.elapsed();
+        assert!(age >= Duration::from_secs(59) && age < Duration::from_secs(62));
+    }
+
+    #[cfg(feature = "serde")]
+    #[esp32_test]
+    fn test_snapshot_restore_caps_next_hops() {
+        let snapshot = PathEntrySnapshot {
+            interface: InterfaceType::LoRa,
+            next_hops: (0..MAX_NEXT_HOPS as u8 + 2).map(make_next_hop).collect(),
+            metrics: hops(2),
+            secs_since_learned: 0,
+            secs_since_refreshed: 0,
+        };
+
+        let entry = PathEntry::from(snapshot);
+        assert_eq!(entry.all_next_hops().len(), MAX_NEXT_HOPS);
+        assert_eq!(entry.all_next_hops()[0], make_next_hop(0));
+    }
 }
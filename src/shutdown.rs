@@ -0,0 +1,156 @@
+//! Shutdown hooks for saving state before the node exits.
+//!
+//! Components register hooks during startup. When the node is asked to stop,
+//! [`ShutdownManager::run`] executes the hooks in registration order on a
+//! helper thread and waits for them up to a total timeout, so a hung hook
+//! (e.g. a stuck flash write) cannot block the exit forever.
+
+use log::{debug, warn};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Total time allowed for all shutdown hooks.
+///
+/// Long enough for NVS/file writes, short enough that a user pressing
+/// Ctrl-C (or a supervisor waiting on the process) isn't left hanging.
+pub const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A hook run once during shutdown.
+type ShutdownHook = Box<dyn FnOnce() + Send + 'static>;
+
+/// Collects shutdown hooks and runs them sequentially with a total timeout.
+pub struct ShutdownManager {
+    hooks: Vec<ShutdownHook>,
+    timeout: Duration,
+}
+
+impl Default for ShutdownManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ShutdownManager {
+    /// Create a manager with [`DEFAULT_SHUTDOWN_TIMEOUT`].
+    pub fn new() -> Self {
+        Self {
+            hooks: Vec::new(),
+            timeout: DEFAULT_SHUTDOWN_TIMEOUT,
+        }
+    }
+
+    /// Set the total time allowed for all hooks.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Register a hook. Hooks run in registration order.
+    pub fn register(&mut self, hook: impl FnOnce() + Send + 'static) {
+        self.hooks.push(Box::new(hook));
+    }
+
+    /// Get the number of registered hooks.
+    pub fn len(&self) -> usize {
+        self.hooks.len()
+    }
+
+    /// Check if no hooks are registered.
+    pub fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+
+    /// Run all hooks sequentially, waiting at most the configured timeout.
+    ///
+    /// Returns the number of hooks that finished. If the timeout expires, the
+    /// remaining hooks are abandoned (the helper thread is detached, since a
+    /// running closure cannot be interrupted). A panicking hook is logged and
+    /// the following hooks still run.
+    pub fn run(self) -> usize {
+        let total = self.hooks.len();
+        if total == 0 {
+            return 0;
+        }
+
+        let deadline = Instant::now() + self.timeout;
+        let (done_tx, done_rx) = mpsc::channel();
+
+        let spawned = thread::Builder::new()
+            .name("shutdown".to_string())
+            .spawn(move || {
+                for (index, hook) in self.hooks.into_iter().enumerate() {
+                    if std::panic::catch_unwind(std::panic::AssertUnwindSafe(hook)).is_err() {
+                        warn!("Shutdown hook {} panicked", index);
+                    }
+                    if done_tx.send(index).is_err() {
+                        // Caller gave up waiting
+                        return;
+                    }
+                }
+            });
+        if let Err(e) = spawned {
+            warn!("Failed to spawn shutdown thread: {}", e);
+            return 0;
+        }
+
+        let mut completed = 0;
+        while completed < total {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match done_rx.recv_timeout(remaining) {
+                Ok(index) => {
+                    debug!("Shutdown hook {}/{} done", index + 1, total);
+                    completed += 1;
+                }
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    warn!(
+                        "Shutdown timed out after {:?}, {} of {} hook(s) skipped",
+                        self.timeout,
+                        total - completed,
+                        total
+                    );
+                    break;
+                }
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+            }
+        }
+
+        completed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reticulum_rs_esp32_macros::esp32_test;
+    use std::sync::{Arc, Mutex};
+
+    #[esp32_test]
+    fn test_hooks_run_in_order() {
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let mut manager = ShutdownManager::new();
+        for i in 0..3 {
+            let order = order.clone();
+            manager.register(move || order.lock().unwrap().push(i));
+        }
+        assert_eq!(manager.len(), 3);
+
+        assert_eq!(manager.run(), 3);
+        assert_eq!(*order.lock().unwrap(), vec![0, 1, 2]);
+    }
+
+    #[esp32_test]
+    fn test_timeout_abandons_remaining_hooks() {
+        let ran_last = Arc::new(Mutex::new(false));
+        let mut manager = ShutdownManager::new().with_timeout(Duration::from_millis(50));
+        manager.register(|| {});
+        manager.register(|| thread::sleep(Duration::from_millis(500)));
+        let flag = ran_last.clone();
+        manager.register(move || *flag.lock().unwrap() = true);
+
+        let start = Instant::now();
+        assert_eq!(manager.run(), 1);
+        assert!(start.elapsed() < Duration::from_millis(400));
+        assert!(!*ran_last.lock().unwrap());
+    }
+}
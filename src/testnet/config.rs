@@ -11,12 +11,35 @@ pub struct TestnetServer {
     pub host: &'static str,
     /// TCP port.
     pub port: u16,
+    /// Whether the server requires TLS (needs the `tls` feature, host only).
+    ///
+    /// Only honoured by [`TestnetTransport`](super::TestnetTransport); the
+    /// node's reticulum `TcpClient` connection is always plain TCP.
+    pub tls: bool,
 }
 
 impl TestnetServer {
     /// Create a new testnet server configuration.
     pub const fn new(name: &'static str, host: &'static str, port: u16) -> Self {
-        Self { name, host, port }
+        Self {
+            name,
+            host,
+            port,
+            tls: false,
+        }
+    }
+
+    /// Mark this server as requiring TLS.
+    ///
+    /// ```
+    /// use reticulum_rs_esp32::TestnetServer;
+    ///
+    /// const SECURE: TestnetServer =
+    ///     TestnetServer::new("Secure", "testnet.example.org", 4965).with_tls();
+    /// assert!(SECURE.tls);
+    /// ```
+    pub const fn with_tls(self) -> Self {
+        Self { tls: true, ..self }
     }
 
     /// Get the address string for connection (host:port).
@@ -65,6 +88,14 @@ mod tests {
         assert!(!SERVERS.is_empty());
     }
 
+    #[esp32_test]
+    fn test_with_tls() {
+        assert!(!DUBLIN.tls);
+        let secure = DUBLIN.with_tls();
+        assert!(secure.tls);
+        assert_eq!(secure.address(), DUBLIN.address());
+    }
+
     #[esp32_test]
     fn test_default_server_in_list() {
         assert!(SERVERS.iter().any(|s| s.host == DEFAULT_SERVER.host));
@@ -17,13 +17,33 @@
 //!
 //! // Connect to default server
 //! let mut transport = TestnetTransport::connect(DEFAULT_SERVER)?;
-//! println!("Connected to {}", transport.server_name());
+//! println!("Connected to {}", transport.server().name);
 //!
 //! // Or try any available server
 //! let mut transport = TestnetTransport::connect_any(SERVERS)?;
 //! # Ok::<(), reticulum_rs_esp32::testnet::TransportError>(())
 //! ```
 //!
+//! # Split Channels
+//!
+//! A gateway can split one connection into announce and directed channels:
+//!
+//! ```no_run
+//! use reticulum_rs_esp32::testnet::{TestnetTransport, DEFAULT_SERVER};
+//!
+//! let (announces, directed) = TestnetTransport::connect(DEFAULT_SERVER)?.split()?;
+//! for packet in announces.receiver.iter() {
+//!     println!("announce: {} bytes", packet.len());
+//! }
+//! # drop(directed);
+//! # Ok::<(), reticulum_rs_esp32::testnet::TransportError>(())
+//! ```
+//!
+//! # Multiple Servers
+//!
+//! A gateway can push announces to several servers through a
+//! [`TestnetPool`], which reconnects dead connections on demand.
+//!
 //! # ESP32 Usage
 //!
 //! On ESP32, ensure WiFi is connected before attempting testnet connection:
@@ -38,7 +58,11 @@
 //! ```
 
 mod config;
+mod pool;
+mod split;
 mod transport;
 
 pub use config::{TestnetServer, BETWEEN_THE_BORDERS, DEFAULT_SERVER, DUBLIN, FRANKFURT, SERVERS};
+pub use pool::TestnetPool;
+pub use split::{AnnounceChannel, Channel, DirectedChannel};
 pub use transport::{TestnetTransport, TransportError};
@@ -0,0 +1,170 @@
+//! Connections to several testnet servers at once.
+//!
+//! In gateway mode a node proxies LoRa announces to multiple testnet servers
+//! to maximize reachability. [`TestnetPool`] holds one connection per server
+//! and reconnects dead ones (detected by a failed write) with exponential
+//! backoff before giving up on that server for the current packet.
+
+use super::config::TestnetServer;
+use super::split::hdlc_frame;
+use super::transport::{TestnetTransport, TransportError};
+use log::{info, warn};
+use std::thread;
+use std::time::Duration;
+
+/// Reconnect attempts for a dead connection before reporting an error.
+const RECONNECT_ATTEMPTS: u32 = 3;
+
+/// Delay before the first reconnect attempt (doubles each attempt).
+const RECONNECT_BACKOFF_MS: u64 = 100;
+
+/// A set of testnet connections that receive the same announces.
+///
+/// # Example
+///
+/// ```no_run
+/// use reticulum_rs_esp32::testnet::{TestnetPool, DUBLIN, FRANKFURT};
+///
+/// let mut pool = TestnetPool::new();
+/// pool.add_connection(&FRANKFURT)?;
+/// pool.add_connection(&DUBLIN)?;
+///
+/// let announce = [0x01, 0x00];
+/// for result in pool.send_announce_to_all(&announce) {
+///     if let Err(e) = result {
+///         println!("announce not delivered: {}", e);
+///     }
+/// }
+/// # Ok::<(), reticulum_rs_esp32::testnet::TransportError>(())
+/// ```
+#[derive(Debug, Default)]
+pub struct TestnetPool {
+    connections: Vec<TestnetTransport>,
+}
+
+impl TestnetPool {
+    /// Create an empty pool.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Connect to `server` and add the connection to the pool.
+    pub fn add_connection(&mut self, server: &TestnetServer) -> Result<(), TransportError> {
+        let transport = TestnetTransport::connect(server)?;
+        self.connections.push(transport);
+        Ok(())
+    }
+
+    /// Send an announce packet to every server in the pool.
+    ///
+    /// The packet is HDLC-framed like all Reticulum TCP traffic. Returns one
+    /// result per connection, in the order they were added. A connection
+    /// whose write fails is reconnected with backoff and the packet resent;
+    /// the slot only reports an error if every reconnect attempt fails.
+    pub fn send_announce_to_all(&mut self, packet: &[u8]) -> Vec<Result<(), TransportError>> {
+        let frame = hdlc_frame(packet);
+        self.connections
+            .iter_mut()
+            .map(|connection| send_or_reconnect(connection, &frame))
+            .collect()
+    }
+
+    /// Get the pooled connections.
+    pub fn connections(&self) -> &[TestnetTransport] {
+        &self.connections
+    }
+
+    /// Get the number of pooled connections.
+    pub fn len(&self) -> usize {
+        self.connections.len()
+    }
+
+    /// Check if the pool has no connections.
+    pub fn is_empty(&self) -> bool {
+        self.connections.is_empty()
+    }
+}
+
+/// Send `frame`, replacing the connection with a fresh one if the write fails.
+fn send_or_reconnect(
+    connection: &mut TestnetTransport,
+    frame: &[u8],
+) -> Result<(), TransportError> {
+    let mut last_error = match connection.send_all(frame) {
+        Ok(()) => return Ok(()),
+        Err(e) => e,
+    };
+    let server = connection.server().clone();
+    warn!("Testnet {} write failed: {}", server.name, last_error);
+
+    for attempt in 0..RECONNECT_ATTEMPTS {
+        thread::sleep(Duration::from_millis(RECONNECT_BACKOFF_MS << attempt));
+        match TestnetTransport::connect(&server) {
+            Ok(fresh) => {
+                info!("Reconnected to testnet {}", server.name);
+                *connection = fresh;
+                match connection.send_all(frame) {
+                    Ok(()) => return Ok(()),
+                    Err(e) => last_error = e,
+                }
+            }
+            Err(e) => last_error = e,
+        }
+    }
+
+    warn!(
+        "Giving up on testnet {} after {} reconnect attempts",
+        server.name, RECONNECT_ATTEMPTS
+    );
+    Err(last_error)
+}
+
+// Loopback TCP would crash the lwip stack in QEMU, so host only.
+#[cfg(all(test, not(feature = "esp32")))]
+mod tests {
+    use super::*;
+    use reticulum_rs_esp32_macros::esp32_test;
+    use std::io::Read;
+    use std::net::TcpListener;
+
+    fn loopback() -> (TcpListener, TestnetServer) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        (listener, TestnetServer::new("Loopback", "127.0.0.1", port))
+    }
+
+    #[esp32_test]
+    fn test_send_announce_to_all() {
+        let (listener_a, server_a) = loopback();
+        let (listener_b, server_b) = loopback();
+
+        let mut pool = TestnetPool::new();
+        pool.add_connection(&server_a).unwrap();
+        pool.add_connection(&server_b).unwrap();
+        assert_eq!(pool.len(), 2);
+        let (mut peer_a, _) = listener_a.accept().unwrap();
+        let (mut peer_b, _) = listener_b.accept().unwrap();
+
+        let results = pool.send_announce_to_all(&[0x01, 0x7E]);
+        assert!(results.iter().all(Result::is_ok));
+
+        for peer in [&mut peer_a, &mut peer_b] {
+            let mut buffer = [0u8; 5];
+            peer.read_exact(&mut buffer).unwrap();
+            assert_eq!(buffer, [0x7E, 0x01, 0x7D, 0x5E, 0x7E]);
+        }
+    }
+
+    #[esp32_test]
+    fn test_add_connection_failure() {
+        let (listener, server) = loopback();
+        drop(listener);
+
+        let mut pool = TestnetPool::new();
+        assert!(matches!(
+            pool.add_connection(&server),
+            Err(TransportError::Connection(_))
+        ));
+        assert!(pool.is_empty());
+    }
+}
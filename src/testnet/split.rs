@@ -0,0 +1,246 @@
+//! Logical channels over a single testnet connection.
+//!
+//! A gateway between LoRa and the testnet handles announces and directed
+//! packets separately. Rather than opening two TCP connections, a
+//! [`TestnetTransport`](super::TestnetTransport) can be split into an
+//! [`AnnounceChannel`] and a [`DirectedChannel`] that share one socket.
+//!
+//! Reticulum's TCP interface frames every packet with HDLC-style byte
+//! stuffing. An I/O thread decodes incoming frames and routes each packet by
+//! its packet type (the low two bits of the first header byte), so no extra
+//! bytes are put on the wire and the remote side needs no changes. Outgoing
+//! packets from either channel are framed and written by the same thread.
+
+use log::{debug, warn};
+use std::io::{self, Read, Write};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::thread;
+
+/// HDLC frame delimiter.
+const FLAG: u8 = 0x7E;
+
+/// HDLC escape byte.
+const ESC: u8 = 0x7D;
+
+/// XOR mask applied to escaped bytes.
+const ESC_MASK: u8 = 0x20;
+
+/// Packet type for announces (low two bits of the header's first byte).
+const PACKET_TYPE_ANNOUNCE: u8 = 0x01;
+
+/// Longest frame accepted before it is dropped as garbage.
+///
+/// Well above Reticulum's 500-byte MTU; bounds memory if the stream never
+/// sends a closing flag.
+const MAX_FRAME_LEN: usize = 4096;
+
+/// One half of a split transport.
+///
+/// `sender` queues packets for transmission; `receiver` yields packets routed
+/// to this channel. Packets are raw Reticulum packets without HDLC framing.
+/// Dropping both channels stops the I/O thread.
+#[derive(Debug)]
+pub struct Channel {
+    /// Queue packets for transmission.
+    pub sender: Sender<Vec<u8>>,
+    /// Packets received for this channel.
+    pub receiver: Receiver<Vec<u8>>,
+}
+
+/// Channel carrying announce packets.
+pub type AnnounceChannel = Channel;
+
+/// Channel carrying all other packets (data, link requests, proofs).
+pub type DirectedChannel = Channel;
+
+/// Frame a packet for Reticulum's TCP interface.
+pub(super) fn hdlc_frame(packet: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(packet.len() + 2);
+    frame.push(FLAG);
+    for &byte in packet {
+        if byte == FLAG || byte == ESC {
+            frame.push(ESC);
+            frame.push(byte ^ ESC_MASK);
+        } else {
+            frame.push(byte);
+        }
+    }
+    frame.push(FLAG);
+    frame
+}
+
+/// Incremental HDLC frame decoder.
+#[derive(Debug, Default)]
+struct HdlcDecoder {
+    buffer: Vec<u8>,
+    in_frame: bool,
+    escape: bool,
+}
+
+impl HdlcDecoder {
+    /// Feed one byte, returning a packet when a frame completes.
+    ///
+    /// Bytes before the first flag, empty frames and oversized frames are
+    /// discarded.
+    fn push(&mut self, byte: u8) -> Option<Vec<u8>> {
+        if byte == FLAG {
+            let packet = std::mem::take(&mut self.buffer);
+            let complete = self.in_frame && !self.escape;
+            self.in_frame = true;
+            self.escape = false;
+            return (complete && !packet.is_empty()).then_some(packet);
+        }
+        if !self.in_frame {
+            return None;
+        }
+
+        if self.escape {
+            self.buffer.push(byte ^ ESC_MASK);
+            self.escape = false;
+        } else if byte == ESC {
+            self.escape = true;
+        } else {
+            self.buffer.push(byte);
+        }
+
+        if self.buffer.len() > MAX_FRAME_LEN {
+            warn!("Dropping oversized testnet frame");
+            self.buffer.clear();
+            self.in_frame = false;
+            self.escape = false;
+        }
+        None
+    }
+}
+
+/// Check if a packet is an announce.
+fn is_announce(packet: &[u8]) -> bool {
+    packet
+        .first()
+        .is_some_and(|header| header & 0x03 == PACKET_TYPE_ANNOUNCE)
+}
+
+/// Spawn the I/O thread and return both channels.
+///
+/// The stream must have a short read timeout set, since the thread
+/// alternates between reading and flushing queued writes.
+pub(super) fn spawn<S>(
+    mut stream: S,
+    server_name: &str,
+) -> Result<(AnnounceChannel, DirectedChannel), io::Error>
+where
+    S: Read + Write + Send + 'static,
+{
+    let (out_tx, out_rx) = mpsc::channel::<Vec<u8>>();
+    let (announce_tx, announce_rx) = mpsc::channel();
+    let (directed_tx, directed_rx) = mpsc::channel();
+
+    thread::Builder::new()
+        .name(format!("testnet-split-{}", server_name))
+        .spawn(move || {
+            let mut decoder = HdlcDecoder::default();
+            let mut buffer = [0u8; 512];
+            loop {
+                // Flush everything queued by either channel
+                loop {
+                    match out_rx.try_recv() {
+                        Ok(packet) => {
+                            if let Err(e) = stream.write_all(&hdlc_frame(&packet)) {
+                                warn!("Testnet write failed: {}", e);
+                                return;
+                            }
+                        }
+                        Err(TryRecvError::Empty) => break,
+                        Err(TryRecvError::Disconnected) => {
+                            debug!("Both testnet channels dropped, stopping");
+                            return;
+                        }
+                    }
+                }
+
+                match stream.read(&mut buffer) {
+                    Ok(0) => {
+                        debug!("Testnet connection closed");
+                        return;
+                    }
+                    Ok(n) => {
+                        for packet in buffer[..n].iter().filter_map(|&b| decoder.push(b)) {
+                            let target = if is_announce(&packet) {
+                                &announce_tx
+                            } else {
+                                &directed_tx
+                            };
+                            // A dropped receiver only means nobody wants this kind
+                            let _ = target.send(packet);
+                        }
+                    }
+                    Err(e)
+                        if matches!(
+                            e.kind(),
+                            io::ErrorKind::WouldBlock
+                                | io::ErrorKind::TimedOut
+                                | io::ErrorKind::Interrupted
+                        ) => {}
+                    Err(e) => {
+                        warn!("Testnet read failed: {}", e);
+                        return;
+                    }
+                }
+            }
+        })?;
+
+    Ok((
+        Channel {
+            sender: out_tx.clone(),
+            receiver: announce_rx,
+        },
+        Channel {
+            sender: out_tx,
+            receiver: directed_rx,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reticulum_rs_esp32_macros::esp32_test;
+
+    fn decode_all(bytes: &[u8]) -> Vec<Vec<u8>> {
+        let mut decoder = HdlcDecoder::default();
+        bytes.iter().filter_map(|&b| decoder.push(b)).collect()
+    }
+
+    #[esp32_test]
+    fn test_hdlc_roundtrip_with_escapes() {
+        let packet = vec![0x01, FLAG, 0x42, ESC, ESC_MASK, 0xFF];
+        let frame = hdlc_frame(&packet);
+        assert_eq!(frame.first(), Some(&FLAG));
+        assert_eq!(frame.last(), Some(&FLAG));
+        assert_eq!(frame.iter().filter(|&&b| b == FLAG).count(), 2);
+
+        // Leading garbage and empty frames are skipped; back-to-back frames
+        // may share a flag
+        let mut stream = vec![0x55, 0x66, FLAG, FLAG];
+        stream.extend_from_slice(&frame);
+        stream.extend_from_slice(&hdlc_frame(&[0x02])[1..]);
+        assert_eq!(decode_all(&stream), vec![packet, vec![0x02]]);
+    }
+
+    #[esp32_test]
+    fn test_oversized_frame_dropped() {
+        let mut stream = vec![FLAG];
+        stream.resize(MAX_FRAME_LEN + 2, 0x11);
+        stream.extend_from_slice(&hdlc_frame(&[0x01, 0x02]));
+        assert_eq!(decode_all(&stream), vec![vec![0x01, 0x02]]);
+    }
+
+    #[esp32_test]
+    fn test_is_announce() {
+        assert!(is_announce(&[0x01, 0x00]));
+        assert!(is_announce(&[0x51]));
+        assert!(!is_announce(&[0x00]));
+        assert!(!is_announce(&[0x02]));
+        assert!(!is_announce(&[]));
+    }
+}
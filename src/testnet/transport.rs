@@ -8,8 +8,21 @@
 //! - **Host**: Works directly with std::net
 //! - **ESP32**: Requires WiFi to be connected first (caller's responsibility)
 //! - **QEMU**: Will fail at runtime (no network emulation)
+//!
+//! # TLS
+//!
+//! Servers marked with [`TestnetServer::with_tls`] are wrapped in a rustls
+//! client session when the `tls` feature is enabled on a host build. The
+//! server certificate is validated against the webpki root store. Without
+//! the feature, and always on ESP32, connecting to such a server fails with
+//! [`TransportError::Tls`].
+//!
+//! TLS only covers connections made through [`TestnetTransport`]. The node
+//! binary and `Node` connect with reticulum's `TcpClient`, which is always
+//! plain TCP.
 
 use super::config::TestnetServer;
+use super::split::{self, AnnounceChannel, DirectedChannel};
 use log::{debug, error, info, warn};
 use std::io::{self, Read, Write};
 use std::net::{TcpStream, ToSocketAddrs};
@@ -21,10 +34,97 @@ const CONNECT_TIMEOUT_SECS: u64 = 10;
 /// Default read timeout in seconds.
 const READ_TIMEOUT_SECS: u64 = 30;
 
+/// Read timeout in milliseconds once split, bounding the write latency of
+/// the shared I/O thread.
+const SPLIT_POLL_MS: u64 = 20;
+
+/// Underlying connection, either plain TCP or TLS over TCP.
+enum Stream {
+    Plain(TcpStream),
+    #[cfg(all(feature = "tls", not(target_os = "espidf")))]
+    Tls(Box<rustls::StreamOwned<rustls::ClientConnection, TcpStream>>),
+}
+
+impl Stream {
+    fn tcp(&self) -> &TcpStream {
+        match self {
+            Self::Plain(s) => s,
+            #[cfg(all(feature = "tls", not(target_os = "espidf")))]
+            Self::Tls(s) => s.get_ref(),
+        }
+    }
+
+    fn tcp_mut(&mut self) -> &mut TcpStream {
+        match self {
+            Self::Plain(s) => s,
+            #[cfg(all(feature = "tls", not(target_os = "espidf")))]
+            Self::Tls(s) => s.get_mut(),
+        }
+    }
+}
+
+impl Read for Stream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Self::Plain(s) => s.read(buf),
+            #[cfg(all(feature = "tls", not(target_os = "espidf")))]
+            Self::Tls(s) => s.read(buf),
+        }
+    }
+}
+
+impl Write for Stream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Plain(s) => s.write(buf),
+            #[cfg(all(feature = "tls", not(target_os = "espidf")))]
+            Self::Tls(s) => s.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Plain(s) => s.flush(),
+            #[cfg(all(feature = "tls", not(target_os = "espidf")))]
+            Self::Tls(s) => s.flush(),
+        }
+    }
+}
+
+/// Wrap a connected TCP stream in a TLS client session.
+///
+/// The server certificate is validated against the webpki root store.
+#[cfg(all(feature = "tls", not(target_os = "espidf")))]
+fn wrap_tls(server: &TestnetServer, stream: TcpStream) -> Result<Stream, TransportError> {
+    use std::sync::Arc;
+
+    let roots = rustls::RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    };
+    let config = rustls::ClientConfig::builder()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    let server_name = rustls::pki_types::ServerName::try_from(server.host)
+        .map_err(|e| TransportError::Tls(format!("invalid server name: {}", e)))?;
+    let conn = rustls::ClientConnection::new(Arc::new(config), server_name)
+        .map_err(|e| TransportError::Tls(e.to_string()))?;
+
+    Ok(Stream::Tls(Box::new(rustls::StreamOwned::new(
+        conn, stream,
+    ))))
+}
+
+#[cfg(not(all(feature = "tls", not(target_os = "espidf"))))]
+fn wrap_tls(_server: &TestnetServer, _stream: TcpStream) -> Result<Stream, TransportError> {
+    Err(TransportError::Tls(
+        "TLS support not compiled in (enable the `tls` feature)".to_string(),
+    ))
+}
+
 /// Testnet transport connection.
 pub struct TestnetTransport {
-    stream: TcpStream,
-    server_name: String,
+    stream: Stream,
+    server: TestnetServer,
 }
 
 impl TestnetTransport {
@@ -90,9 +190,16 @@ impl TestnetTransport {
                 .unwrap_or_default()
         );
 
+        let stream = if server.tls {
+            // The handshake itself runs lazily on the first read/write
+            wrap_tls(server, stream)?
+        } else {
+            Stream::Plain(stream)
+        };
+
         Ok(Self {
             stream,
-            server_name: server.name.to_string(),
+            server: server.clone(),
         })
     }
 
@@ -115,9 +222,12 @@ impl TestnetTransport {
         Err(last_error.unwrap_or(TransportError::NoServers))
     }
 
-    /// Get the name of the connected server.
-    pub fn server_name(&self) -> &str {
-        &self.server_name
+    /// Get the connected server.
+    ///
+    /// Use `server().name` for logging and `server().address()` for the
+    /// host and port.
+    pub fn server(&self) -> &TestnetServer {
+        &self.server
     }
 
     /// Best-effort check if the connection may still be alive.
@@ -126,7 +236,38 @@ impl TestnetTransport {
     /// peer may have closed the connection but we won't know until we try I/O.
     /// The only reliable way to detect disconnection is to attempt read/write.
     pub fn may_be_connected(&self) -> bool {
-        self.stream.peer_addr().is_ok()
+        self.stream.tcp().peer_addr().is_ok()
+    }
+
+    /// Check if the connection is still open, without blocking or consuming data.
+    ///
+    /// Peeks at the socket in non-blocking mode. End of stream (the server
+    /// closed an idle connection) or a reset means disconnected; pending
+    /// data or "would block" means alive. A peer that vanished without
+    /// closing the connection still only shows up on the next write.
+    pub fn is_connected(&self) -> bool {
+        let tcp = self.stream.tcp();
+        if tcp.set_nonblocking(true).is_err() {
+            return false;
+        }
+        let mut probe = [0u8; 1];
+        let result = tcp.peek(&mut probe);
+        if let Err(e) = tcp.set_nonblocking(false) {
+            warn!("Failed to restore blocking mode: {}", e);
+            return false;
+        }
+
+        match result {
+            Ok(0) => false,
+            Ok(_) => true,
+            Err(e) => !matches!(
+                e.kind(),
+                io::ErrorKind::BrokenPipe
+                    | io::ErrorKind::ConnectionReset
+                    | io::ErrorKind::ConnectionAborted
+                    | io::ErrorKind::NotConnected
+            ),
+        }
     }
 
     /// Send raw bytes to the testnet.
@@ -134,6 +275,11 @@ impl TestnetTransport {
         self.stream.write(data).map_err(TransportError::Io)
     }
 
+    /// Send all of `data`, retrying partial writes.
+    pub(super) fn send_all(&mut self, data: &[u8]) -> Result<(), TransportError> {
+        self.stream.write_all(data).map_err(TransportError::Io)
+    }
+
     /// Receive bytes from the testnet.
     ///
     /// Returns the number of bytes read into the buffer.
@@ -142,25 +288,51 @@ impl TestnetTransport {
         self.stream.read(buffer).map_err(TransportError::Io)
     }
 
+    /// Whether this connection is wrapped in TLS.
+    pub fn is_tls(&self) -> bool {
+        !matches!(self.stream, Stream::Plain(_))
+    }
+
     /// Get the underlying TCP stream for advanced use.
+    ///
+    /// For TLS connections this is the raw socket beneath the TLS session;
+    /// writing to it directly bypasses encryption.
     pub fn stream(&self) -> &TcpStream {
-        &self.stream
+        self.stream.tcp()
     }
 
     /// Get mutable access to the underlying TCP stream.
+    ///
+    /// See [`stream`](Self::stream) for the caveat on TLS connections.
     pub fn stream_mut(&mut self) -> &mut TcpStream {
-        &mut self.stream
+        self.stream.tcp_mut()
+    }
+
+    /// Split the connection into announce and directed channels.
+    ///
+    /// Spawns an I/O thread that owns the connection, decodes incoming
+    /// packets and routes them by packet type. Both channels' senders feed
+    /// the same connection. The thread exits when the connection closes or
+    /// both channels are dropped.
+    pub fn split(self) -> Result<(AnnounceChannel, DirectedChannel), TransportError> {
+        self.stream
+            .tcp()
+            .set_read_timeout(Some(Duration::from_millis(SPLIT_POLL_MS)))
+            .map_err(TransportError::Io)?;
+        split::spawn(self.stream, self.server.name).map_err(TransportError::Io)
     }
 }
 
 impl std::fmt::Debug for TestnetTransport {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("TestnetTransport")
-            .field("server", &self.server_name)
+            .field("server", &self.server.name)
+            .field("tls", &self.is_tls())
             .field(
                 "peer",
                 &self
                     .stream
+                    .tcp()
                     .peer_addr()
                     .map(|a| a.to_string())
                     .unwrap_or_else(|_| "unknown".to_string()),
@@ -182,6 +354,8 @@ pub enum TransportError {
     Io(io::Error),
     /// No servers provided.
     NoServers,
+    /// TLS setup failed or TLS support is not compiled in.
+    Tls(String),
 }
 
 impl std::fmt::Display for TransportError {
@@ -192,6 +366,7 @@ impl std::fmt::Display for TransportError {
             Self::Connection(e) => write!(f, "connection failed: {}", e),
             Self::Io(e) => write!(f, "I/O error: {}", e),
             Self::NoServers => write!(f, "no servers provided"),
+            Self::Tls(msg) => write!(f, "TLS error: {}", msg),
         }
     }
 }
@@ -274,7 +449,8 @@ mod tests {
 
         let transport = transport.unwrap();
         assert!(transport.may_be_connected());
-        assert_eq!(transport.server_name(), DEFAULT_SERVER.name);
+        assert_eq!(transport.server().name, DEFAULT_SERVER.name);
+        assert_eq!(transport.server().address(), DEFAULT_SERVER.address());
     }
 
     #[esp32_test]
@@ -300,4 +476,92 @@ mod tests {
         let result = TestnetTransport::connect_any(&[]);
         assert!(matches!(result, Err(TransportError::NoServers)));
     }
+
+    /// Loopback TCP would crash the lwip stack in QEMU, so host only.
+    #[cfg(not(feature = "esp32"))]
+    #[esp32_test]
+    fn test_split_routes_by_packet_type() {
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = TestnetServer::new("Loopback", "127.0.0.1", port);
+
+        let transport = TestnetTransport::connect(&server).unwrap();
+        let (mut peer, _) = listener.accept().unwrap();
+        let (announces, directed) = transport.split().unwrap();
+
+        // HDLC-framed announce (type 0x01) followed by a data packet (type 0x00)
+        peer.write_all(&[0x7E, 0x01, 0xAA, 0x7E, 0x00, 0x7D, 0x5E, 0x7E])
+            .unwrap();
+        let timeout = Duration::from_secs(2);
+        assert_eq!(
+            announces.receiver.recv_timeout(timeout).unwrap(),
+            vec![0x01, 0xAA]
+        );
+        assert_eq!(
+            directed.receiver.recv_timeout(timeout).unwrap(),
+            vec![0x00, 0x7E]
+        );
+
+        // Both halves write to the same connection
+        directed.sender.send(vec![0x02, 0x7D]).unwrap();
+        let mut buffer = [0u8; 5];
+        peer.read_exact(&mut buffer).unwrap();
+        assert_eq!(buffer, [0x7E, 0x02, 0x7D, 0x5D, 0x7E]);
+    }
+
+    /// Loopback TCP would crash the lwip stack in QEMU, so host only.
+    #[cfg(not(feature = "esp32"))]
+    #[esp32_test]
+    fn test_is_connected() {
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = TestnetServer::new("Loopback", "127.0.0.1", port);
+
+        let mut transport = TestnetTransport::connect(&server).unwrap();
+        let (mut peer, _) = listener.accept().unwrap();
+        assert!(transport.is_connected());
+
+        // Pending data is left for the next receive
+        peer.write_all(&[0x7E, 0x01]).unwrap();
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(transport.is_connected());
+        let mut buffer = [0u8; 2];
+        assert_eq!(transport.receive(&mut buffer).unwrap(), 2);
+        assert_eq!(buffer, [0x7E, 0x01]);
+
+        // The server closing the connection is noticed without any I/O
+        drop(peer);
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(!transport.is_connected());
+    }
+
+    /// No public testnet server speaks TLS yet; run manually once one does.
+    #[cfg(all(feature = "tls", not(target_os = "espidf")))]
+    #[esp32_test]
+    #[ignore = "requires a TLS-enabled testnet server"]
+    fn test_connect_tls() {
+        use crate::testnet::config::TestnetServer;
+
+        if !wait_for_network(NETWORK_TIMEOUT_SECS) {
+            info!(
+                "Skipping network test - network not available after {}s",
+                NETWORK_TIMEOUT_SECS
+            );
+            return;
+        }
+
+        let server =
+            TestnetServer::new("Testnet TLS", "testnet.reticulum.network", 4965).with_tls();
+        let mut transport = TestnetTransport::connect(&server).expect("TLS connect failed");
+        assert!(transport.is_tls());
+
+        // Drive the handshake by exchanging data
+        let mut buffer = [0u8; 64];
+        transport.send(&[]).expect("TLS handshake failed");
+        let _ = transport.receive(&mut buffer);
+    }
 }
@@ -3,17 +3,32 @@
 //! This module wraps ESP-IDF WiFi driver functionality for connecting
 //! to access points.
 
-use crate::config::WifiConfig;
+use crate::config::{EapConfig, EapMethod, WifiConfig, WpaType};
 use esp_idf_hal::modem::Modem;
 use esp_idf_svc::eventloop::EspSystemEventLoop;
-use esp_idf_svc::wifi::{AuthMethod, BlockingWifi, ClientConfiguration, Configuration, EspWifi};
-use esp_idf_sys::EspError;
+use esp_idf_svc::wifi::{
+    AuthMethod, BlockingWifi, ClientConfiguration, Configuration, EspWifi, ScanMethod,
+    ScanSortMethod,
+};
+use esp_idf_sys::{
+    esp, esp_eap_client_clear_ca_cert, esp_eap_client_set_ca_cert, esp_eap_client_set_identity,
+    esp_eap_client_set_password, esp_eap_client_set_ttls_phase2_method,
+    esp_eap_client_set_username, esp_eap_ttls_phase2_types_ESP_EAP_TTLS_PHASE2_MSCHAPV2,
+    esp_wifi_sta_enterprise_disable, esp_wifi_sta_enterprise_enable, esp_wifi_sta_get_ap_info,
+    wifi_ap_record_t, wifi_auth_mode_t, wifi_auth_mode_t_WIFI_AUTH_OPEN,
+    wifi_auth_mode_t_WIFI_AUTH_OWE, wifi_auth_mode_t_WIFI_AUTH_WEP,
+    wifi_auth_mode_t_WIFI_AUTH_WPA2_WPA3_PSK, wifi_auth_mode_t_WIFI_AUTH_WPA3_ENT_192,
+    wifi_auth_mode_t_WIFI_AUTH_WPA3_PSK, wifi_auth_mode_t_WIFI_AUTH_WPA_PSK, EspError,
+};
 use log::info;
 
 /// WiFi connection manager.
 pub struct WifiManager<'a> {
     /// ESP-IDF WiFi driver.
     wifi: BlockingWifi<EspWifi<'a>>,
+    /// NUL-terminated CA certificate of the current enterprise network.
+    /// The supplicant keeps a pointer to it rather than a copy.
+    ca_cert: Option<Vec<u8>>,
 }
 
 impl<'a> WifiManager<'a> {
@@ -22,7 +37,10 @@ impl<'a> WifiManager<'a> {
         let esp_wifi = EspWifi::new(modem, sysloop.clone(), None)?;
         let wifi = BlockingWifi::wrap(esp_wifi, sysloop)?;
 
-        Ok(Self { wifi })
+        Ok(Self {
+            wifi,
+            ca_cert: None,
+        })
     }
 
     /// Connect to a WiFi network.
@@ -32,7 +50,9 @@ impl<'a> WifiManager<'a> {
         info!("Connecting to WiFi: {}", config.ssid);
 
         // Determine auth method
-        let auth_method = if config.is_open() {
+        let auth_method = if config.is_enterprise() {
+            AuthMethod::WPA2Enterprise
+        } else if config.is_open() {
             AuthMethod::None
         } else {
             AuthMethod::WPA2Personal
@@ -51,10 +71,17 @@ impl<'a> WifiManager<'a> {
                 .try_into()
                 .map_err(|_| WifiError::InvalidPassword)?,
             auth_method,
+            // Hidden networks need an all-channel scan with directed probe requests
+            scan_method: if config.hidden_ssid {
+                ScanMethod::CompleteScan(ScanSortMethod::Signal)
+            } else {
+                ScanMethod::default()
+            },
             ..Default::default()
         });
 
         self.wifi.set_configuration(&wifi_config)?;
+        self.configure_enterprise(config.enterprise.as_ref())?;
 
         // Start WiFi
         self.wifi.start()?;
@@ -73,6 +100,55 @@ impl<'a> WifiManager<'a> {
         Ok(ip)
     }
 
+    /// Pass WPA2-Enterprise credentials to the supplicant, or turn enterprise
+    /// mode off for a personal or open network.
+    ///
+    /// ESP-IDF negotiates PEAP or TTLS with the server by itself; the method
+    /// only decides whether the TTLS inner method is set.
+    fn configure_enterprise(&mut self, eap: Option<&EapConfig>) -> Result<(), WifiError> {
+        let Some(eap) = eap else {
+            esp!(unsafe { esp_wifi_sta_enterprise_disable() })?;
+            self.ca_cert = None;
+            return Ok(());
+        };
+
+        // The supplicant copies identity, username and password
+        esp!(unsafe {
+            esp_eap_client_set_identity(eap.identity.as_ptr(), eap.identity.len() as _)
+        })?;
+        esp!(unsafe {
+            esp_eap_client_set_username(eap.username.as_ptr(), eap.username.len() as _)
+        })?;
+        esp!(unsafe {
+            esp_eap_client_set_password(eap.password.as_ptr(), eap.password.len() as _)
+        })?;
+
+        match &eap.ca_cert_pem {
+            Some(pem) => {
+                // mbedTLS parses PEM only with the NUL terminator counted in the length
+                let mut cert = pem.as_bytes().to_vec();
+                cert.push(0);
+                esp!(unsafe { esp_eap_client_set_ca_cert(cert.as_ptr(), cert.len() as _) })?;
+                self.ca_cert = Some(cert);
+            }
+            None => {
+                unsafe { esp_eap_client_clear_ca_cert() };
+                self.ca_cert = None;
+            }
+        }
+
+        if eap.eap_method == EapMethod::Ttls {
+            esp!(unsafe {
+                esp_eap_client_set_ttls_phase2_method(
+                    esp_eap_ttls_phase2_types_ESP_EAP_TTLS_PHASE2_MSCHAPV2,
+                )
+            })?;
+        }
+
+        esp!(unsafe { esp_wifi_sta_enterprise_enable() })?;
+        Ok(())
+    }
+
     /// Disconnect from the current network.
     pub fn disconnect(&mut self) -> Result<(), EspError> {
         info!("Disconnecting from WiFi");
@@ -98,6 +174,36 @@ impl<'a> WifiManager<'a> {
             .ok()
             .map(|info| format!("{}", info.ip))
     }
+
+    /// Get the security mode of the current network if connected.
+    ///
+    /// Reads the mode the access point actually uses rather than the
+    /// configured minimum, so a WPA2-only config that joined a WPA2/WPA3
+    /// network reports WPA3.
+    pub fn wpa_type(&self) -> Option<WpaType> {
+        if !self.is_connected() {
+            return None;
+        }
+        let mut info = wifi_ap_record_t::default();
+        esp!(unsafe { esp_wifi_sta_get_ap_info(&mut info) }).ok()?;
+        Some(wpa_type_from_authmode(info.authmode))
+    }
+}
+
+/// Map the ESP-IDF auth mode of an access point to its security mode.
+#[allow(non_upper_case_globals)]
+fn wpa_type_from_authmode(authmode: wifi_auth_mode_t) -> WpaType {
+    match authmode {
+        wifi_auth_mode_t_WIFI_AUTH_OPEN => WpaType::Open,
+        wifi_auth_mode_t_WIFI_AUTH_WEP => WpaType::Wep,
+        wifi_auth_mode_t_WIFI_AUTH_WPA_PSK => WpaType::Wpa,
+        wifi_auth_mode_t_WIFI_AUTH_WPA3_PSK
+        | wifi_auth_mode_t_WIFI_AUTH_WPA2_WPA3_PSK
+        | wifi_auth_mode_t_WIFI_AUTH_WPA3_ENT_192
+        | wifi_auth_mode_t_WIFI_AUTH_OWE => WpaType::Wpa3,
+        // WPA/WPA2 mixed, enterprise and WAPI all negotiate WPA2-level keys
+        _ => WpaType::Wpa2,
+    }
 }
 
 /// Errors that can occur during WiFi operations.
@@ -21,4 +21,7 @@ mod storage;
 #[cfg(feature = "esp32")]
 pub use connection::{WifiError, WifiManager};
 #[cfg(feature = "esp32")]
-pub use storage::{clear_wifi_config, init_nvs, load_wifi_config, save_wifi_config};
+pub use storage::{
+    add_network, clear_wifi_config, init_nvs, list_networks, load_wifi_config, remove_network,
+    save_wifi_config,
+};
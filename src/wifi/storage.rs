@@ -1,13 +1,17 @@
 //! NVS persistence for WiFi credentials.
 //!
 //! This module stores WiFi credentials in ESP32's Non-Volatile Storage (NVS)
-//! so they persist across reboots.
+//! so they persist across reboots: a single network under `credentials`, and
+//! a prioritized [`WifiConfigList`] under the indexed keys `wifi_0`,
+//! `wifi_1`, ... (one slot per network, up to [`MAX_WIFI_NETWORKS`]).
 
 #![allow(dead_code)] // Functions will be used when main.rs is integrated
 
-use crate::config::{WifiConfig, MAX_PASSWORD_LEN, MAX_SSID_LEN};
+use crate::config::{
+    WifiConfig, WifiConfigList, MAX_PASSWORD_LEN, MAX_SSID_LEN, MAX_WIFI_NETWORKS,
+};
 use esp_idf_svc::nvs::{EspNvs, NvsDefault};
-use esp_idf_sys::EspError;
+use esp_idf_sys::{EspError, ESP_ERR_NOT_SUPPORTED, ESP_ERR_NVS_NOT_ENOUGH_SPACE};
 
 /// NVS namespace for WiFi configuration.
 const NVS_NAMESPACE: &str = "wifi_config";
@@ -16,8 +20,8 @@ const NVS_NAMESPACE: &str = "wifi_config";
 const NVS_KEY: &str = "credentials";
 
 /// Maximum buffer size for WiFi config serialization.
-/// Format: [ssid_len:1][ssid:32][password_len:1][password:64] = 98 bytes.
-const MAX_CONFIG_BUFFER_SIZE: usize = 1 + MAX_SSID_LEN + 1 + MAX_PASSWORD_LEN;
+/// Format: [version:1][ssid_len:1][ssid:32][password_len:1][password:64][hidden:1][checksum:1] = 101 bytes.
+const MAX_CONFIG_BUFFER_SIZE: usize = 1 + 1 + MAX_SSID_LEN + 1 + MAX_PASSWORD_LEN + 1 + 1;
 
 /// Load WiFi configuration from NVS.
 ///
@@ -29,7 +33,11 @@ pub fn load_wifi_config(nvs: &EspNvs<NvsDefault>) -> Option<WifiConfig> {
 }
 
 /// Save WiFi configuration to NVS.
+///
+/// Fails with `ESP_ERR_NOT_SUPPORTED` for WPA2-Enterprise configs, which the
+/// storage format can't hold yet.
 pub fn save_wifi_config(nvs: &mut EspNvs<NvsDefault>, config: &WifiConfig) -> Result<(), EspError> {
+    ensure_storable(config)?;
     let bytes = config.to_bytes();
     nvs.set_raw(NVS_KEY, &bytes)?;
     Ok(())
@@ -41,6 +49,91 @@ pub fn clear_wifi_config(nvs: &mut EspNvs<NvsDefault>) -> Result<(), EspError> {
     Ok(())
 }
 
+/// Reject configs that [`WifiConfig::to_bytes`] would store incompletely.
+fn ensure_storable(config: &WifiConfig) -> Result<(), EspError> {
+    if config.is_enterprise() {
+        return Err(EspError::from_infallible::<ESP_ERR_NOT_SUPPORTED>());
+    }
+    Ok(())
+}
+
+/// NVS key for the network slot at `index`.
+fn network_key(index: usize) -> String {
+    format!("wifi_{}", index)
+}
+
+/// Read the network slot at `index` as `(priority, config)`.
+///
+/// Slot format: `[priority:1][config bytes]` (see [`WifiConfig::to_bytes`]).
+fn load_network_slot(nvs: &EspNvs<NvsDefault>, index: usize) -> Option<(u8, WifiConfig)> {
+    let mut buf = [0u8; 1 + MAX_CONFIG_BUFFER_SIZE];
+    let bytes = nvs.get_raw(&network_key(index), &mut buf).ok()??;
+    let (&priority, config) = bytes.split_first()?;
+    Some((priority, WifiConfig::from_bytes(config).ok()?))
+}
+
+/// Load all stored networks with their priorities.
+///
+/// Empty or corrupted slots are skipped.
+pub fn list_networks(nvs: &EspNvs<NvsDefault>) -> WifiConfigList {
+    let mut list = WifiConfigList::new();
+    for index in 0..MAX_WIFI_NETWORKS {
+        if let Some((priority, config)) = load_network_slot(nvs, index) {
+            // Can't fail: at most MAX_WIFI_NETWORKS slots, and a duplicate
+            // SSID just replaces the earlier entry
+            let _ = list.add_network(priority, config);
+        }
+    }
+    list
+}
+
+/// Store a network with a priority, replacing any stored network with the
+/// same SSID.
+///
+/// Fails with `ESP_ERR_NVS_NOT_ENOUGH_SPACE` if all slots are taken, and with
+/// `ESP_ERR_NOT_SUPPORTED` for WPA2-Enterprise configs.
+pub fn add_network(
+    nvs: &mut EspNvs<NvsDefault>,
+    priority: u8,
+    config: &WifiConfig,
+) -> Result<(), EspError> {
+    ensure_storable(config)?;
+    let mut free_slot = None;
+    let mut target = None;
+    for index in 0..MAX_WIFI_NETWORKS {
+        match load_network_slot(nvs, index) {
+            Some((_, stored)) if stored.ssid == config.ssid => {
+                target = Some(index);
+                break;
+            }
+            Some(_) => {}
+            None => {
+                free_slot.get_or_insert(index);
+            }
+        }
+    }
+    let index = target
+        .or(free_slot)
+        .ok_or_else(EspError::from_infallible::<ESP_ERR_NVS_NOT_ENOUGH_SPACE>)?;
+
+    let mut bytes = vec![priority];
+    bytes.extend_from_slice(&config.to_bytes());
+    nvs.set_raw(&network_key(index), &bytes)?;
+    Ok(())
+}
+
+/// Remove the stored network with the given SSID.
+///
+/// Returns whether a network was removed.
+pub fn remove_network(nvs: &mut EspNvs<NvsDefault>, ssid: &str) -> Result<bool, EspError> {
+    for index in 0..MAX_WIFI_NETWORKS {
+        if load_network_slot(nvs, index).is_some_and(|(_, stored)| stored.ssid == ssid) {
+            return nvs.remove(&network_key(index));
+        }
+    }
+    Ok(false)
+}
+
 /// Initialize NVS for WiFi configuration.
 ///
 /// Uses a shared partition handle to ensure `EspNvsPartition::take()` is only
@@ -140,4 +233,31 @@ mod tests {
         assert_eq!(loaded, config2);
         assert_ne!(loaded, config1);
     }
+
+    #[esp32_test]
+    fn test_network_list_roundtrip() {
+        crate::ensure_esp_initialized();
+        let mut nvs = init_nvs().expect("Failed to init NVS");
+        for (_, config) in list_networks(&nvs).list_networks() {
+            let ssid = config.ssid.clone();
+            remove_network(&mut nvs, &ssid).expect("Failed to clear network");
+        }
+
+        let office = WifiConfig::new("Office", "password123").expect("Failed to create config");
+        let home = WifiConfig::open("Home").expect("Failed to create config");
+        add_network(&mut nvs, 1, &office).expect("Failed to add office");
+        add_network(&mut nvs, 5, &home).expect("Failed to add home");
+        // Re-adding replaces the slot instead of taking a new one
+        add_network(&mut nvs, 2, &office).expect("Failed to update office");
+
+        let list = list_networks(&nvs);
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.best_available(&["Office", "Home"]), Some(&home));
+        assert_eq!(list.best_available(&["Office"]), Some(&office));
+
+        assert!(remove_network(&mut nvs, "Home").expect("Failed to remove"));
+        assert!(!remove_network(&mut nvs, "Home").expect("Failed to remove"));
+        assert_eq!(list_networks(&nvs).len(), 1);
+        remove_network(&mut nvs, "Office").expect("Failed to remove");
+    }
 }